@@ -7,7 +7,7 @@ use tendermint_proto::Protobuf;
 use ibc_proto::ibc::apps::transfer::v1::MsgTransfer as RawMsgTransfer;
 
 use crate::applications::ics20_fungible_token_transfer::error::Error;
-use crate::core::ics02_client::height::Height;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::signer::Signer;
 use crate::timestamp::Timestamp;
@@ -29,8 +29,8 @@ pub struct MsgTransfer {
     /// the recipient address on the destination chain
     pub receiver: Signer,
     /// Timeout height relative to the current block height.
-    /// The timeout is disabled when set to 0.
-    pub timeout_height: Height,
+    /// The timeout is disabled when set to `TimeoutHeight::Never`.
+    pub timeout_height: TimeoutHeight,
     /// Timeout timestamp relative to the current block timestamp.
     /// The timeout is disabled when set to 0.
     pub timeout_timestamp: Timestamp,
@@ -59,10 +59,8 @@ impl TryFrom<RawMsgTransfer> for MsgTransfer {
             .map_err(|_| Error::invalid_packet_timeout_timestamp(raw_msg.timeout_timestamp))?;
 
         let timeout_height = match raw_msg.timeout_height.clone() {
-            None => Height::zero(),
-            Some(raw_height) => raw_height.try_into().map_err(|e| {
-                Error::invalid_packet_timeout_height(format!("invalid timeout height {}", e))
-            })?,
+            None => TimeoutHeight::Never,
+            Some(raw_height) => raw_height.into(),
         };
 
         Ok(MsgTransfer {
@@ -125,7 +123,8 @@ pub mod test_util {
             timeout_height: Height {
                 revision_number: 0,
                 revision_height: height,
-            },
+            }
+            .into(),
         }
     }
 }
@@ -17,6 +17,7 @@ use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::Error as Ics02Error;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::packet::Sequence;
 
 use crate::core::ics23_commitment::commitment::{
@@ -242,7 +243,7 @@ impl ClientDef for TendermintClient {
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-        _data: String,
+        _commitment: PacketCommitment,
     ) -> Result<(), Ics02Error> {
         todo!()
     }
@@ -255,7 +256,7 @@ impl ClientDef for TendermintClient {
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-        _data: Vec<u8>,
+        _ack: AcknowledgementCommitment,
     ) -> Result<(), Ics02Error> {
         todo!()
     }
@@ -34,6 +34,19 @@ pub struct ClientState {
     frozen_height: Option<Height>,
 }
 
+/// The parameters of a Tendermint host chain which a counterparty's client of
+/// that chain must agree with, see [`ClientState::validate_self_client`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HostParams {
+    pub chain_id: ChainId,
+    pub current_height: Height,
+    pub unbonding_period: Duration,
+    pub proof_specs: ProofSpecs,
+    /// The upgrade path of the host chain. If empty, the upgrade path of the
+    /// client is not checked.
+    pub upgrade_path: Vec<String>,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AllowUpdate {
     pub after_expiry: bool,
@@ -154,6 +167,83 @@ impl ClientState {
         elapsed > self.trusting_period
     }
 
+    /// Validates this client state, as held by a counterparty chain, against the
+    /// parameters of the host chain it tracks. This is the Tendermint counterpart
+    /// of `ValidateSelfClient` from ibc-go, and is used by the connection handshake
+    /// to reject counterparties that track the host with an invalid or stale client.
+    pub fn validate_self_client(&self, host: &HostParams) -> Result<(), Error> {
+        if let Some(frozen_height) = self.frozen_height {
+            return Err(Error::invalid_self_client(format!(
+                "client is frozen at height {}",
+                frozen_height
+            )));
+        }
+
+        if self.chain_id != host.chain_id {
+            return Err(Error::invalid_self_client(format!(
+                "chain id {} does not match host chain id {}",
+                self.chain_id, host.chain_id
+            )));
+        }
+
+        if self.latest_height.revision_number != host.chain_id.version() {
+            return Err(Error::invalid_self_client(format!(
+                "latest height {} does not match the revision number {} of host chain {}",
+                self.latest_height,
+                host.chain_id.version(),
+                host.chain_id
+            )));
+        }
+
+        if self.latest_height >= host.current_height {
+            return Err(Error::invalid_self_client(format!(
+                "latest height {} must be lower than the current host height {}",
+                self.latest_height, host.current_height
+            )));
+        }
+
+        // The trust level must be in the range [1/3, 1].
+        let (numerator, denominator) = (
+            self.trust_level.numerator() as u128,
+            self.trust_level.denominator() as u128,
+        );
+        if denominator == 0 || numerator > denominator || 3 * numerator < denominator {
+            return Err(Error::invalid_self_client(format!(
+                "trust level {}/{} must be within [1/3, 1]",
+                numerator, denominator
+            )));
+        }
+
+        if self.unbonding_period != host.unbonding_period {
+            return Err(Error::invalid_self_client(format!(
+                "unbonding period {:?} does not match host unbonding period {:?}",
+                self.unbonding_period, host.unbonding_period
+            )));
+        }
+
+        if self.trusting_period >= self.unbonding_period {
+            return Err(Error::invalid_self_client(format!(
+                "trusting period {:?} must be smaller than unbonding period {:?}",
+                self.trusting_period, self.unbonding_period
+            )));
+        }
+
+        if self.proof_specs != host.proof_specs {
+            return Err(Error::invalid_self_client(
+                "proof specs do not match the host proof specs".to_string(),
+            ));
+        }
+
+        if !host.upgrade_path.is_empty() && self.upgrade_path != host.upgrade_path {
+            return Err(Error::invalid_self_client(format!(
+                "upgrade path {:?} does not match host upgrade path {:?}",
+                self.upgrade_path, host.upgrade_path
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Helper method to produce a
     /// [`tendermint_light_client::light_client::Options`] struct for use in
     /// Tendermint-specific light client verification.
@@ -271,7 +361,7 @@ mod tests {
 
     use tendermint_rpc::endpoint::abci_query::AbciQuery;
 
-    use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState};
+    use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState, HostParams};
     use crate::core::ics02_client::trust_threshold::TrustThreshold;
     use crate::core::ics23_commitment::specs::ProofSpecs;
     use crate::core::ics24_host::identifier::ChainId;
@@ -392,6 +482,99 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn client_state_validate_self_client() {
+        let host = HostParams {
+            chain_id: ChainId::new("host".to_string(), 2),
+            current_height: Height::new(2, 20),
+            unbonding_period: Duration::new(128000, 0),
+            proof_specs: ProofSpecs::default(),
+            upgrade_path: vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+        };
+
+        let valid = ClientState::new(
+            host.chain_id.clone(),
+            TrustThreshold::ONE_THIRD,
+            Duration::new(64000, 0),
+            host.unbonding_period,
+            Duration::new(3, 0),
+            Height::new(2, 10),
+            host.proof_specs.clone(),
+            host.upgrade_path.clone(),
+            AllowUpdate {
+                after_expiry: false,
+                after_misbehaviour: false,
+            },
+        )
+        .unwrap();
+
+        assert!(valid.validate_self_client(&host).is_ok());
+
+        let invalid = vec![
+            (
+                "wrong chain id",
+                ClientState {
+                    chain_id: ChainId::new("other".to_string(), 2),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "stale revision",
+                ClientState {
+                    latest_height: Height::new(1, 10),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "height not reached by the host",
+                ClientState {
+                    latest_height: host.current_height,
+                    ..valid.clone()
+                },
+            ),
+            (
+                "trust level too low",
+                ClientState {
+                    trust_level: TrustThreshold::new(1, 4).unwrap(),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "zero trust level",
+                ClientState {
+                    trust_level: TrustThreshold::ZERO,
+                    ..valid.clone()
+                },
+            ),
+            (
+                "wrong unbonding period",
+                ClientState {
+                    unbonding_period: Duration::new(64001, 0),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "wrong upgrade path",
+                ClientState {
+                    upgrade_path: vec!["".to_string()],
+                    ..valid.clone()
+                },
+            ),
+            (
+                "frozen",
+                valid.clone().with_frozen_height(Height::new(2, 5)).unwrap(),
+            ),
+        ];
+
+        for (name, client_state) in invalid {
+            assert!(
+                client_state.validate_self_client(&host).is_err(),
+                "validate_self_client unexpectedly passed for test: {}",
+                name
+            );
+        }
+    }
 }
 
 #[cfg(any(test, feature = "mocks"))]
@@ -38,6 +38,10 @@ define_error! {
             { reason: String }
             |e| { format_args!("invalid header, failed basic validation: {}", e.reason) },
 
+        InvalidSelfClient
+            { reason: String }
+            |e| { format_args!("invalid client of the host chain: {}", e.reason) },
+
         InvalidRawClientState
             { reason: String }
             |e| { format_args!("invalid raw client state: {}", e.reason) },
@@ -9,6 +9,7 @@ use crate::core::ics02_client::error::Error;
 use crate::core::ics02_client::header::{AnyHeader, Header};
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
@@ -109,7 +110,7 @@ pub trait ClientDef: Clone {
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        commitment: String,
+        commitment: PacketCommitment,
     ) -> Result<(), Error>;
 
     /// Verify a `proof` that a packet has been commited.
@@ -122,7 +123,7 @@ pub trait ClientDef: Clone {
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        ack: Vec<u8>,
+        ack: AcknowledgementCommitment,
     ) -> Result<(), Error>;
 
     /// Verify a `proof` that of the next_seq_received.
@@ -407,7 +408,7 @@ impl ClientDef for AnyClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        commitment: String,
+        commitment: PacketCommitment,
     ) -> Result<(), Error> {
         match self {
             Self::Tendermint(client) => {
@@ -455,7 +456,7 @@ impl ClientDef for AnyClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        ack: Vec<u8>,
+        ack: AcknowledgementCommitment,
     ) -> Result<(), Error> {
         match self {
             Self::Tendermint(client) => {
@@ -162,6 +162,16 @@ define_error! {
         InvalidHeightResult
             | _ | { "height cannot end up zero or negative" },
 
+        HeightRevisionMismatch
+            {
+                height: Height,
+                other: Height,
+            }
+            | e | {
+                format_args!("heights {0} and {1} belong to different revisions",
+                    e.height, e.other)
+            },
+
         InvalidAddress
             | _ | { "invalid address" },
 
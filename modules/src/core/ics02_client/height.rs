@@ -40,6 +40,12 @@ impl Height {
         self.revision_height == 0
     }
 
+    /// Returns `true` if both heights belong to the same revision of a chain.
+    pub fn is_same_revision(&self, other: &Height) -> bool {
+        self.revision_number == other.revision_number
+    }
+
+    /// Adds `delta` blocks to this height, within the same revision.
     pub fn add(&self, delta: u64) -> Height {
         Height {
             revision_number: self.revision_number,
@@ -51,6 +57,8 @@ impl Height {
         self.add(1)
     }
 
+    /// Subtracts `delta` blocks from this height, within the same revision.
+    /// Fails if the result would cross the start of the revision.
     pub fn sub(&self, delta: u64) -> Result<Height, Error> {
         if self.revision_height <= delta {
             return Err(Error::invalid_height_result());
@@ -72,6 +80,27 @@ impl Height {
             ..self
         }
     }
+
+    pub fn with_revision_number(self, revision_number: u64) -> Height {
+        Height {
+            revision_number,
+            ..self
+        }
+    }
+
+    /// Returns the number of blocks from `other` to this height.
+    /// Fails if the two heights belong to different revisions, since
+    /// heights are not comparable block-wise across a revision boundary,
+    /// or if `other` is higher than this height.
+    pub fn blocks_since(&self, other: &Height) -> Result<u64, Error> {
+        if !self.is_same_revision(other) {
+            return Err(Error::height_revision_mismatch(*self, *other));
+        }
+
+        self.revision_height
+            .checked_sub(other.revision_height)
+            .ok_or_else(Error::invalid_height_result)
+    }
 }
 
 impl Default for Height {
@@ -169,7 +198,7 @@ impl TryFrom<&str> for Height {
 
 impl From<Height> for String {
     fn from(height: Height) -> Self {
-        format!("{}-{}", height.revision_number, height.revision_number)
+        format!("{}-{}", height.revision_number, height.revision_height)
     }
 }
 
@@ -180,3 +209,37 @@ impl FromStr for Height {
         Height::try_from(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_within_revision() {
+        let height = Height::new(2, 10);
+
+        assert_eq!(height.add(5), Height::new(2, 15));
+        assert_eq!(height.sub(9).unwrap(), Height::new(2, 1));
+        assert!(height.sub(10).is_err());
+        assert_eq!(height.with_revision_number(3), Height::new(3, 10));
+    }
+
+    #[test]
+    fn blocks_since_respects_revisions() {
+        let height = Height::new(1, 10);
+
+        assert_eq!(height.blocks_since(&Height::new(1, 4)).unwrap(), 6);
+        assert_eq!(height.blocks_since(&height).unwrap(), 0);
+        assert!(height.blocks_since(&Height::new(1, 11)).is_err());
+        assert!(height.blocks_since(&Height::new(0, 4)).is_err());
+        assert!(Height::new(2, 1).blocks_since(&height).is_err());
+    }
+
+    #[test]
+    fn string_roundtrip() {
+        let height = Height::new(4, 123);
+        let s = String::from(height);
+        assert_eq!(s, "4-123");
+        assert_eq!(s.parse::<Height>().unwrap(), height);
+    }
+}
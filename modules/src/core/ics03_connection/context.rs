@@ -40,6 +40,20 @@ pub trait ConnectionReader {
     /// Returns the ConsensusState of the host (local) chain at a specific height.
     fn host_consensus_state(&self, height: Height) -> Result<AnyConsensusState, Error>;
 
+    /// Validates the client state that the counterparty chain holds of the host (local) chain,
+    /// as supplied in a `ConnOpenTry` or `ConnOpenAck` message. Must fail if the client does
+    /// not track the host chain faithfully, eg. if it has a different chain identifier,
+    /// unbonding period or proof specs, an insufficient trust level, or if it is frozen or
+    /// claims a height the host chain has not reached yet.
+    ///
+    /// The handlers only call this when the message carries a client state. The field is
+    /// optional on the wire, so a counterparty that omits it is not checked at all.
+    ///
+    /// For Tendermint host chains, see
+    /// [`ClientState::validate_self_client`](crate::clients::ics07_tendermint::client_state::ClientState::validate_self_client).
+    fn validate_self_client(&self, counterparty_client_state: &AnyClientState)
+        -> Result<(), Error>;
+
     /// Function required by ICS 03. Returns the list of all possible versions that the connection
     /// handshake protocol supports.
     fn get_compatible_versions(&self) -> Vec<Version> {
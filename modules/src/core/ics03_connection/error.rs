@@ -149,6 +149,13 @@ define_error! {
                     e.client_id)
             },
 
+        InvalidSelfClient
+            { reason: String }
+            | e | {
+                format_args!("the counterparty's client of the host chain is invalid: {0}",
+                    e.reason)
+            },
+
         ImplementationSpecific
             | _ | { "implementation specific error" },
     }
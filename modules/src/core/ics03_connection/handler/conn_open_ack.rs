@@ -35,6 +35,11 @@ pub(crate) fn process(
         return Err(Error::connection_mismatch(msg.connection_id().clone()));
     }
 
+    // Check that the counterparty's client of this chain is valid.
+    if let Some(client_state) = msg.client_state.as_ref() {
+        ctx.validate_self_client(client_state)?;
+    }
+
     // The counterparty is the local chain.
     let counterparty = Counterparty::new(
         conn_end.client_id().clone(), // The local client identifier.
@@ -97,7 +102,9 @@ mod tests {
     use crate::core::ics23_commitment::commitment::CommitmentPrefix;
     use crate::core::ics24_host::identifier::{ChainId, ClientId};
     use crate::events::IbcEvent;
+    use crate::mock::client_state::MockClientState;
     use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
     use crate::mock::host::HostType;
     use crate::timestamp::ZERO_DURATION;
 
@@ -157,13 +164,18 @@ mod tests {
             CommitmentPrefix::from(Vec::new()), // incorrect field
         ));
 
+        // A client state of the host chain claiming a height the host has not reached yet.
+        let mut msg_invalid_self_client = msg_ack.clone();
+        msg_invalid_self_client.client_state =
+            Some(MockClientState::new(MockHeader::new(latest_height.increment())).into());
+
         let tests: Vec<Test> = vec![
             Test {
                 name: "Successful processing of an Ack message".to_string(),
                 ctx: default_context
                     .clone()
                     .with_client(&client_id, proof_height)
-                    .with_connection(conn_id.clone(), default_conn_end),
+                    .with_connection(conn_id.clone(), default_conn_end.clone()),
                 msg: ConnectionMsg::ConnectionOpenAck(Box::new(msg_ack.clone())),
                 want_pass: true,
                 match_error: Box::new(|_| {
@@ -211,6 +223,23 @@ mod tests {
                     })
                 },
             },
+            Test {
+                name: "Processing fails because the counterparty's client of this chain is invalid".to_string(),
+                ctx: default_context
+                    .clone()
+                    .with_client(&client_id, proof_height)
+                    .with_connection(conn_id.clone(), default_conn_end),
+                msg: ConnectionMsg::ConnectionOpenAck(Box::new(msg_invalid_self_client)),
+                want_pass: false,
+                match_error: Box::new(|e| {
+                    match e.detail() {
+                        error::ErrorDetail::InvalidSelfClient(_) => {}
+                        _ => {
+                            panic!("Expected InvalidSelfClient error");
+                        }
+                    }
+                }),
+            },
             Test {
                 name: "Processing fails: ConsensusStateVerificationFailure due to empty counterparty prefix".to_string(),
                 ctx: default_context
@@ -67,6 +67,11 @@ pub(crate) fn process(
         }
     }?;
 
+    // Check that the counterparty's client of this chain is valid.
+    if let Some(client_state) = msg.client_state.as_ref() {
+        ctx.validate_self_client(client_state)?;
+    }
+
     // Proof verification in two steps:
     // 1. Setup: build the ConnectionEnd as we expect to find it on the other party.
     let expected_conn = ConnectionEnd::new(
@@ -129,7 +134,9 @@ mod tests {
     use crate::core::ics03_connection::msgs::ConnectionMsg;
     use crate::core::ics24_host::identifier::ChainId;
     use crate::events::IbcEvent;
+    use crate::mock::client_state::MockClientState;
     use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
     use crate::mock::host::HostType;
     use crate::Height;
 
@@ -183,6 +190,11 @@ mod tests {
             ))
             .unwrap();
 
+        // The counterparty's client of this chain claims a height this chain has not reached.
+        let mut msg_invalid_self_client = msg_conn_try.clone().with_previous_connection_id(None);
+        msg_invalid_self_client.client_state =
+            Some(MockClientState::new(MockHeader::new(host_chain_height.increment())).into());
+
         let tests: Vec<Test> = vec![
             Test {
                 name: "Processing fails because the height is too advanced".to_string(),
@@ -214,6 +226,12 @@ mod tests {
                 msg: ConnectionMsg::ConnectionOpenTry(Box::new(msg_conn_try.clone())),
                 want_pass: false,
             },
+            Test {
+                name: "Processing fails because the counterparty's client of this chain is invalid".to_string(),
+                ctx: context.clone().with_client(msg_conn_try.client_id(), Height::new(0, client_consensus_state_height)),
+                msg: ConnectionMsg::ConnectionOpenTry(Box::new(msg_invalid_self_client)),
+                want_pass: false,
+            },
             Test {
                 name: "Good parameters".to_string(),
                 ctx: context.with_client(msg_conn_try.client_id(), Height::new(0, client_consensus_state_height)),
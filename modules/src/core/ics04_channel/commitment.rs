@@ -0,0 +1,160 @@
+//! Computation of the packet and acknowledgement commitments that a
+//! host chain stores, following the encoding used by ibc-go.
+//!
+
+use core::fmt;
+
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::prelude::*;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+/// The commitment to a packet, as stored by the sending chain
+/// under the `commitments/ports/{port}/channels/{channel}/sequences/{seq}` path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PacketCommitment(Vec<u8>);
+
+impl PacketCommitment {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for PacketCommitment {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl fmt::Display for PacketCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+/// The commitment to an acknowledgement, as stored by the receiving chain
+/// under the `acks/ports/{port}/channels/{channel}/sequences/{seq}` path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct AcknowledgementCommitment(Vec<u8>);
+
+impl AcknowledgementCommitment {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for AcknowledgementCommitment {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl fmt::Display for AcknowledgementCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+/// Computes the commitment to a packet, i.e., the SHA-256 hash of the
+/// concatenation of the big-endian encoded timeout timestamp, timeout
+/// revision number and timeout revision height, followed by the SHA-256
+/// hash of the packet data. An absent timeout height is encoded as zero.
+///
+/// This matches `CommitPacket` from ibc-go.
+pub fn compute_packet_commitment(
+    packet_data: &[u8],
+    timeout_height: TimeoutHeight,
+    timeout_timestamp: Timestamp,
+) -> PacketCommitment {
+    let timeout_height = Height::from(timeout_height);
+
+    let mut input = timeout_timestamp.nanoseconds().to_be_bytes().to_vec();
+    input.extend_from_slice(&timeout_height.revision_number.to_be_bytes());
+    input.extend_from_slice(&timeout_height.revision_height.to_be_bytes());
+    input.extend_from_slice(&Sha256::digest(packet_data));
+
+    PacketCommitment(Sha256::digest(&input).to_vec())
+}
+
+/// Computes the commitment to an acknowledgement, i.e., the SHA-256 hash
+/// of the acknowledgement bytes.
+///
+/// This matches `CommitAcknowledgement` from ibc-go.
+pub fn compute_ack_commitment(ack: &[u8]) -> AcknowledgementCommitment {
+    AcknowledgementCommitment(Sha256::digest(ack).to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packet_commitment_encoding() {
+        let commitment = compute_packet_commitment(
+            b"packet data",
+            Height::new(0, 10).into(),
+            Timestamp::from_nanoseconds(1_000).unwrap(),
+        );
+
+        let mut input = 1_000u64.to_be_bytes().to_vec();
+        input.extend_from_slice(&0u64.to_be_bytes());
+        input.extend_from_slice(&10u64.to_be_bytes());
+        input.extend_from_slice(&Sha256::digest(b"packet data"));
+
+        assert_eq!(commitment.as_bytes(), Sha256::digest(&input).as_slice());
+        assert_eq!(commitment.as_bytes().len(), 32);
+    }
+
+    #[test]
+    fn packet_commitment_depends_on_all_fields() {
+        let height = TimeoutHeight::At(Height::new(1, 10));
+        let timestamp = Timestamp::from_nanoseconds(1_000).unwrap();
+        let base = compute_packet_commitment(b"data", height, timestamp);
+
+        assert_ne!(
+            base,
+            compute_packet_commitment(b"other data", height, timestamp)
+        );
+        assert_ne!(
+            base,
+            compute_packet_commitment(b"data", Height::new(2, 10).into(), timestamp)
+        );
+        assert_ne!(
+            base,
+            compute_packet_commitment(b"data", Height::new(1, 11).into(), timestamp)
+        );
+        assert_ne!(
+            base,
+            compute_packet_commitment(b"data", height, Timestamp::none())
+        );
+        assert_ne!(
+            base,
+            compute_packet_commitment(b"data", TimeoutHeight::Never, timestamp)
+        );
+    }
+
+    #[test]
+    fn ack_commitment_is_sha256_of_ack() {
+        let ack = br#"{"result":"AQ=="}"#;
+        let commitment = compute_ack_commitment(ack);
+        assert_eq!(commitment.as_bytes(), Sha256::digest(ack).as_slice());
+    }
+}
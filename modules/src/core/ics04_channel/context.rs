@@ -6,6 +6,7 @@ use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::core::ics04_channel::{error::Error, packet::Receipt};
 use crate::core::ics05_port::capabilities::Capability;
@@ -53,17 +54,17 @@ pub trait ChannelReader {
         port_channel_id: &(PortId, ChannelId),
     ) -> Result<Sequence, Error>;
 
-    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Result<String, Error>;
+    fn get_packet_commitment(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<PacketCommitment, Error>;
 
     fn get_packet_receipt(&self, key: &(PortId, ChannelId, Sequence)) -> Result<Receipt, Error>;
 
     fn get_packet_acknowledgement(
         &self,
         key: &(PortId, ChannelId, Sequence),
-    ) -> Result<String, Error>;
-
-    /// A hashing function for packet commitments
-    fn hash(&self, value: String) -> String;
+    ) -> Result<AcknowledgementCommitment, Error>;
 
     /// Returns the current height of the local chain.
     fn host_height(&self) -> Height;
@@ -123,9 +124,7 @@ pub trait ChannelKeeper {
 
                 self.store_packet_commitment(
                     (res.port_id.clone(), res.channel_id.clone(), res.seq),
-                    res.timeout_timestamp,
-                    res.timeout_height,
-                    res.data,
+                    res.commitment,
                 )?;
             }
             PacketResult::Recv(res) => {
@@ -149,7 +148,7 @@ pub trait ChannelKeeper {
             PacketResult::WriteAck(res) => {
                 self.store_packet_acknowledgement(
                     (res.port_id.clone(), res.channel_id.clone(), res.seq),
-                    res.ack,
+                    res.ack_commitment,
                 )?;
             }
             PacketResult::Ack(res) => {
@@ -186,9 +185,7 @@ pub trait ChannelKeeper {
     fn store_packet_commitment(
         &mut self,
         key: (PortId, ChannelId, Sequence),
-        timestamp: Timestamp,
-        heigh: Height,
-        data: Vec<u8>,
+        commitment: PacketCommitment,
     ) -> Result<(), Error>;
 
     fn delete_packet_commitment(&mut self, key: (PortId, ChannelId, Sequence))
@@ -203,7 +200,7 @@ pub trait ChannelKeeper {
     fn store_packet_acknowledgement(
         &mut self,
         key: (PortId, ChannelId, Sequence),
-        ack: Vec<u8>,
+        ack_commitment: AcknowledgementCommitment,
     ) -> Result<(), Error>;
 
     fn delete_packet_acknowledgement(
@@ -2,6 +2,7 @@ use super::packet::Sequence;
 use crate::core::ics02_client::error as client_error;
 use crate::core::ics03_connection::error as connection_error;
 use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
 use crate::core::ics24_host::error::ValidationError;
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::prelude::*;
@@ -210,7 +211,7 @@ define_error! {
         LowPacketHeight
             {
                 chain_height: Height,
-                timeout_height: Height
+                timeout_height: TimeoutHeight
             }
             | e | {
                 format_args!(
@@ -220,7 +221,7 @@ define_error! {
 
         PacketTimeoutHeightNotReached
             {
-                timeout_height: Height,
+                timeout_height: TimeoutHeight,
                 chain_height: Height,
             }
             | e | {
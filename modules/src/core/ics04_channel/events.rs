@@ -6,6 +6,7 @@ use tendermint::abci::Event as AbciEvent;
 
 use crate::core::ics02_client::height::Height;
 use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::packet::Packet;
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::events::{extract_attribute, Error as EventError, IbcEvent, IbcEventType, RawObject};
@@ -74,7 +75,7 @@ pub fn try_from_tx(event: &tendermint::abci::Event) -> Option<IbcEvent> {
                 IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
                     height: Default::default(),
                     packet,
-                    ack: write_ack,
+                    ack: write_ack.into(),
                 })
             })
             .ok(),
@@ -287,7 +288,7 @@ impl TryFrom<Packet> for Vec<Tag> {
         attributes.push(sequence);
         let timeout_height = Tag {
             key: PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY.parse().unwrap(),
-            value: p.timeout_height.to_string().parse().unwrap(),
+            value: Height::from(p.timeout_height).to_string().parse().unwrap(),
         };
         attributes.push(timeout_height);
         let timeout_timestamp = Tag {
@@ -704,8 +705,7 @@ impl core::fmt::Display for ReceivePacket {
 pub struct WriteAcknowledgement {
     pub height: Height,
     pub packet: Packet,
-    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
-    pub ack: Vec<u8>,
+    pub ack: Acknowledgement,
 }
 
 impl WriteAcknowledgement {
@@ -740,8 +740,8 @@ impl TryFrom<WriteAcknowledgement> for AbciEvent {
 
     fn try_from(v: WriteAcknowledgement) -> Result<Self, Self::Error> {
         let mut attributes = Vec::<Tag>::try_from(v.packet)?;
-        let val =
-            String::from_utf8(v.ack).expect("hex-encoded string should always be valid UTF-8");
+        let val = String::from_utf8(v.ack.into_vec())
+            .expect("hex-encoded string should always be valid UTF-8");
         // No actual conversion from string to `Tag::Key` or `Tag::Value`
         let ack = Tag {
             key: PKT_ACK_ATTRIBUTE_KEY.parse().unwrap(),
@@ -959,7 +959,7 @@ mod tests {
             destination_port: "b_test_port".parse().unwrap(),
             destination_channel: "b_test_channel".parse().unwrap(),
             data: "test_data".as_bytes().to_vec(),
-            timeout_height: Height::new(1, 10),
+            timeout_height: Height::new(1, 10).into(),
             timeout_timestamp: Timestamp::now(),
         };
         let mut abci_events = vec![];
@@ -971,7 +971,7 @@ mod tests {
         let write_ack = WriteAcknowledgement {
             height: Height::default(),
             packet: packet.clone(),
-            ack: "test_ack".as_bytes().to_vec(),
+            ack: "test_ack".as_bytes().to_vec().into(),
         };
         abci_events.push(AbciEvent::try_from(write_ack.clone()).unwrap());
         let ack_packet = AcknowledgePacket {
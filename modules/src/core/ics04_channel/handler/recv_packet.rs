@@ -10,7 +10,6 @@ use crate::core::ics04_channel::packet::{PacketResult, Receipt, Sequence};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
-use crate::timestamp::Expiry;
 
 #[derive(Clone, Debug)]
 pub struct RecvPacketResult {
@@ -62,18 +61,17 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
 
     let client_id = connection_end.client_id().clone();
 
-    // Check if packet height is newer than the height of the local host chain
+    // Check if the packet timed out relative to the local host chain
     let latest_height = ctx.host_height();
-    if (!packet.timeout_height.is_zero()) && (packet.timeout_height <= latest_height) {
+    if packet.timeout_height_expired(latest_height) {
         return Err(Error::low_packet_height(
             latest_height,
             packet.timeout_height,
         ));
     }
 
-    // Check if packet timestamp is newer than the local host chain timestamp
     let latest_timestamp = ctx.host_timestamp();
-    if let Expiry::Expired = latest_timestamp.check_expiry(&packet.timeout_timestamp) {
+    if packet.timeout_timestamp_expired(latest_timestamp) {
         return Err(Error::low_packet_timestamp());
     }
 
@@ -181,7 +179,7 @@ mod tests {
             destination_port: PortId::default(),
             destination_channel: ChannelId::default(),
             data: Vec::new(),
-            timeout_height: client_height,
+            timeout_height: client_height.into(),
             timeout_timestamp: Timestamp::from_nanoseconds(1).unwrap(),
         };
 
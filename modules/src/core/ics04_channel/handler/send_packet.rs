@@ -1,6 +1,7 @@
 use crate::core::ics02_client::client_state::ClientState;
 use crate::core::ics04_channel::channel::Counterparty;
 use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::commitment::{compute_packet_commitment, PacketCommitment};
 use crate::core::ics04_channel::events::SendPacket;
 use crate::core::ics04_channel::packet::{PacketResult, Sequence};
 use crate::core::ics04_channel::{context::ChannelReader, error::Error, packet::Packet};
@@ -8,8 +9,6 @@ use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
-use crate::timestamp::{Expiry, Timestamp};
-use crate::Height;
 
 #[derive(Clone, Debug)]
 pub struct SendPacketResult {
@@ -17,9 +16,7 @@ pub struct SendPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub seq_number: Sequence,
-    pub timeout_height: Height,
-    pub timeout_timestamp: Timestamp,
-    pub data: Vec<u8>,
+    pub commitment: PacketCommitment,
 }
 
 pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<PacketResult, Error> {
@@ -57,24 +54,21 @@ pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<Pac
         return Err(Error::frozen_client(connection_end.client_id().clone()));
     }
 
-    // check if packet height is newer than the height of the latest client state on the receiving chain
+    // check if the packet already timed out relative to the latest client state on the receiving chain
     let latest_height = client_state.latest_height();
-    let packet_height = packet.timeout_height;
 
-    if !packet.timeout_height.is_zero() && packet_height <= latest_height {
+    if packet.timeout_height_expired(latest_height) {
         return Err(Error::low_packet_height(
             latest_height,
             packet.timeout_height,
         ));
     }
 
-    //check if packet timestamp is newer than the timestamp of the latest consensus state of the receiving chain
     let consensus_state = ctx.client_consensus_state(&client_id, latest_height)?;
 
     let latest_timestamp = consensus_state.timestamp();
 
-    let packet_timestamp = packet.timeout_timestamp;
-    if let Expiry::Expired = latest_timestamp.check_expiry(&packet_timestamp) {
+    if packet.timeout_timestamp_expired(latest_timestamp) {
         return Err(Error::low_packet_timestamp());
     }
 
@@ -96,13 +90,15 @@ pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<Pac
         channel_id: packet.source_channel.clone(),
         seq: packet.sequence,
         seq_number: next_seq_send.increment(),
-        data: packet.clone().data,
-        timeout_height: packet.timeout_height,
-        timeout_timestamp: packet.timeout_timestamp,
+        commitment: compute_packet_commitment(
+            &packet.data,
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        ),
     });
 
     output.emit(IbcEvent::SendPacket(SendPacket {
-        height: packet_height,
+        height: ctx.host_height(),
         packet,
     }));
 
@@ -122,6 +118,7 @@ mod tests {
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::version::get_compatible_versions;
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::ChannelReader;
     use crate::core::ics04_channel::handler::send_packet::send_packet;
     use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::core::ics04_channel::packet::Packet;
@@ -241,6 +238,7 @@ mod tests {
                     // TODO: The object in the output is a PacketResult what can we check on it?
                     for e in proto_output.events.iter() {
                         assert!(matches!(e, &IbcEvent::SendPacket(_)));
+                        assert_eq!(e.height(), ChannelReader::host_height(&test.ctx));
                     }
                 }
                 Err(e) => {
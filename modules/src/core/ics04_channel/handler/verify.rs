@@ -2,8 +2,10 @@ use crate::core::ics02_client::client_state::ClientState;
 use crate::core::ics02_client::{client_def::AnyClient, client_def::ClientDef};
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{compute_ack_commitment, compute_packet_commitment};
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::packet::{Packet, Sequence};
 use crate::core::ics24_host::identifier::ClientId;
 use crate::prelude::*;
@@ -64,11 +66,11 @@ pub fn verify_packet_recv_proofs(
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data
+    let commitment = compute_packet_commitment(
+        &packet.data,
+        packet.timeout_height,
+        packet.timeout_timestamp,
     );
-    let commitment = ctx.hash(input);
 
     // Verify the proof for the packet against the chain store.
     client_def
@@ -90,7 +92,7 @@ pub fn verify_packet_recv_proofs(
 pub fn verify_packet_acknowledgement_proofs(
     ctx: &dyn ChannelReader,
     packet: &Packet,
-    acknowledgement: Vec<u8>,
+    acknowledgement: &Acknowledgement,
     client_id: ClientId,
     proofs: &Proofs,
 ) -> Result<(), Error> {
@@ -112,7 +114,7 @@ pub fn verify_packet_acknowledgement_proofs(
             &packet.source_port,
             &packet.source_channel,
             &packet.sequence,
-            acknowledgement,
+            compute_ack_commitment(acknowledgement.as_bytes()),
         )
         .map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
 
@@ -1,5 +1,7 @@
 use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::commitment::{compute_ack_commitment, AcknowledgementCommitment};
 use crate::core::ics04_channel::events::WriteAcknowledgement;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::packet::{Packet, PacketResult, Sequence};
 use crate::core::ics04_channel::{context::ChannelReader, error::Error};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
@@ -14,13 +16,13 @@ pub struct WriteAckPacketResult {
     pub port_id: PortId,
     pub channel_id: ChannelId,
     pub seq: Sequence,
-    pub ack: Vec<u8>,
+    pub ack_commitment: AcknowledgementCommitment,
 }
 
 pub fn process(
     ctx: &dyn ChannelReader,
     packet: Packet,
-    ack: Vec<u8>,
+    ack: Acknowledgement,
 ) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
@@ -60,7 +62,7 @@ pub fn process(
         port_id: packet.source_port.clone(),
         channel_id: packet.source_channel.clone(),
         seq: packet.sequence,
-        ack: ack.clone(),
+        ack_commitment: compute_ack_commitment(ack.as_bytes()),
     });
 
     output.log("success: packet write acknowledgement");
@@ -100,7 +102,7 @@ mod tests {
             name: String,
             ctx: MockContext,
             packet: Packet,
-            ack: Vec<u8>,
+            ack: Acknowledgement,
             want_pass: bool,
         }
 
@@ -112,8 +114,8 @@ mod tests {
         packet.sequence = 1.into();
         packet.data = vec![0];
 
-        let ack = vec![0];
-        let ack_null = Vec::new();
+        let ack = Acknowledgement::from(vec![0]);
+        let ack_null = Acknowledgement::default();
 
         let dest_channel_end = ChannelEnd::new(
             State::Open,
@@ -2,6 +2,7 @@
 //! applications and the chains those applications are built upon.
 
 pub mod channel;
+pub mod commitment;
 pub mod context;
 pub mod error;
 pub mod events;
@@ -9,6 +10,7 @@ pub mod events;
 pub mod handler;
 pub mod msgs;
 pub mod packet;
+pub mod timeout;
 
 mod version;
 pub use version::Version;
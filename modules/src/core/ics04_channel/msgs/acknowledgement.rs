@@ -1,5 +1,7 @@
 use crate::prelude::*;
 
+use serde_derive::{Deserialize, Serialize};
+use subtle_encoding::base64;
 use tendermint_proto::Protobuf;
 
 use ibc_proto::ibc::core::channel::v1::MsgAcknowledgement as RawMsgAcknowledgement;
@@ -12,13 +14,114 @@ use crate::tx_msg::Msg;
 
 pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgAcknowledgement";
 
+/// An acknowledgement written by the receiving application of a packet.
+///
+/// The bytes are opaque to the core IBC handlers, but most applications
+/// (e.g., ICS20) follow the standard JSON envelope of either
+/// `{"result": <base64>}` or `{"error": <string>}`, which can be
+/// inspected via [`Acknowledgement::response`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Acknowledgement(
+    #[serde(
+        serialize_with = "crate::serializers::ser_hex_upper",
+        deserialize_with = "crate::serializers::de_hex_upper"
+    )]
+    Vec<u8>,
+);
+
+impl Acknowledgement {
+    /// Builds a successful acknowledgement using the standard JSON envelope.
+    pub fn success(result: &[u8]) -> Self {
+        let result = String::from_utf8(base64::encode(result))
+            .expect("base64-encoded string should always be valid UTF-8");
+
+        RawAcknowledgementResponse::Result(result).into()
+    }
+
+    /// Builds an error acknowledgement using the standard JSON envelope.
+    pub fn error(error: impl ToString) -> Self {
+        RawAcknowledgementResponse::Error(error.to_string()).into()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Parses the acknowledgement as a standard JSON envelope.
+    /// Returns `None` if the application uses a custom format.
+    pub fn response(&self) -> Option<AcknowledgementResponse> {
+        let raw: RawAcknowledgementResponse = serde_json::from_slice(&self.0).ok()?;
+
+        match raw {
+            RawAcknowledgementResponse::Result(result) => base64::decode(result)
+                .ok()
+                .map(AcknowledgementResponse::Success),
+            RawAcknowledgementResponse::Error(error) => Some(AcknowledgementResponse::Error(error)),
+        }
+    }
+
+    /// Returns `true` if the acknowledgement is a standard error acknowledgement.
+    /// Acknowledgements in a custom format are never considered errors.
+    pub fn is_error(&self) -> bool {
+        matches!(self.response(), Some(AcknowledgementResponse::Error(_)))
+    }
+}
+
+impl AsRef<[u8]> for Acknowledgement {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Acknowledgement {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Acknowledgement> for Vec<u8> {
+    fn from(ack: Acknowledgement) -> Self {
+        ack.0
+    }
+}
+
+impl From<RawAcknowledgementResponse> for Acknowledgement {
+    fn from(raw: RawAcknowledgementResponse) -> Self {
+        Self(serde_json::to_vec(&raw).expect("acknowledgement envelope should always serialize"))
+    }
+}
+
+/// The content of an acknowledgement following the standard JSON envelope.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AcknowledgementResponse {
+    /// The packet was successfully processed, with the given (decoded) result.
+    Success(Vec<u8>),
+    /// The packet could not be processed by the receiving application.
+    Error(String),
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RawAcknowledgementResponse {
+    Result(String),
+    Error(String),
+}
+
 ///
 /// Message definition for packet acknowledgements.
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct MsgAcknowledgement {
     pub packet: Packet,
-    pub acknowledgement: Vec<u8>, // TODO(romac): Introduce a newtype for this
+    pub acknowledgement: Acknowledgement,
     pub proofs: Proofs,
     pub signer: Signer,
 }
@@ -26,7 +129,7 @@ pub struct MsgAcknowledgement {
 impl MsgAcknowledgement {
     pub fn new(
         packet: Packet,
-        acknowledgement: Vec<u8>,
+        acknowledgement: Acknowledgement,
         proofs: Proofs,
         signer: Signer,
     ) -> MsgAcknowledgement {
@@ -38,7 +141,7 @@ impl MsgAcknowledgement {
         }
     }
 
-    pub fn acknowledgement(&self) -> &Vec<u8> {
+    pub fn acknowledgement(&self) -> &Acknowledgement {
         &self.acknowledgement
     }
 
@@ -66,6 +169,10 @@ impl TryFrom<RawMsgAcknowledgement> for MsgAcknowledgement {
     type Error = Error;
 
     fn try_from(raw_msg: RawMsgAcknowledgement) -> Result<Self, Self::Error> {
+        if raw_msg.acknowledgement.is_empty() {
+            return Err(Error::invalid_acknowledgement());
+        }
+
         let proofs = Proofs::new(
             raw_msg.proof_acked.into(),
             None,
@@ -83,7 +190,7 @@ impl TryFrom<RawMsgAcknowledgement> for MsgAcknowledgement {
                 .packet
                 .ok_or_else(Error::missing_packet)?
                 .try_into()?,
-            acknowledgement: raw_msg.acknowledgement,
+            acknowledgement: raw_msg.acknowledgement.into(),
             signer: raw_msg.signer.into(),
             proofs,
         })
@@ -94,7 +201,7 @@ impl From<MsgAcknowledgement> for RawMsgAcknowledgement {
     fn from(domain_msg: MsgAcknowledgement) -> Self {
         RawMsgAcknowledgement {
             packet: Some(domain_msg.packet.into()),
-            acknowledgement: domain_msg.acknowledgement,
+            acknowledgement: domain_msg.acknowledgement.into(),
             signer: domain_msg.signer.to_string(),
             proof_height: Some(domain_msg.proofs.height().into()),
             proof_acked: domain_msg.proofs.object_proof().clone().into(),
@@ -136,7 +243,9 @@ mod test {
 
     use crate::core::ics04_channel::error::Error;
     use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
-    use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+    use crate::core::ics04_channel::msgs::acknowledgement::{
+        Acknowledgement, AcknowledgementResponse, MsgAcknowledgement,
+    };
 
     #[test]
     fn msg_acknowledgment_try_from_raw() {
@@ -183,6 +292,14 @@ mod test {
                 name: "Empty proof acked".to_string(),
                 raw: RawMsgAcknowledgement {
                     proof_acked: Vec::new(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Empty acknowledgement".to_string(),
+                raw: RawMsgAcknowledgement {
+                    acknowledgement: Vec::new(),
                     ..default_raw_msg
                 },
                 want_pass: false,
@@ -202,4 +319,43 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn acknowledgement_envelope() {
+        let success = Acknowledgement::success(&[1]);
+        assert_eq!(success.as_bytes(), br#"{"result":"AQ=="}"#);
+        assert_eq!(
+            success.response(),
+            Some(AcknowledgementResponse::Success(vec![1]))
+        );
+        assert!(!success.is_error());
+
+        let error = Acknowledgement::error("insufficient funds");
+        assert_eq!(error.as_bytes(), br#"{"error":"insufficient funds"}"#);
+        assert_eq!(
+            error.response(),
+            Some(AcknowledgementResponse::Error(
+                "insufficient funds".to_string()
+            ))
+        );
+        assert!(error.is_error());
+
+        let custom = Acknowledgement::from(vec![0x0a, 0x01]);
+        assert_eq!(custom.response(), None);
+        assert!(!custom.is_error());
+
+        let bad_base64 = Acknowledgement::from(br#"{"result":"!!"}"#.to_vec());
+        assert_eq!(bad_base64.response(), None);
+    }
+
+    #[test]
+    fn acknowledgement_serde_roundtrip() {
+        let ack = Acknowledgement::from(vec![0x0a, 0xff]);
+
+        let json = serde_json::to_string(&ack).unwrap();
+        assert_eq!(json, r#""0AFF""#);
+
+        let decoded: Acknowledgement = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, ack);
+    }
 }
@@ -7,9 +7,10 @@ use serde_derive::{Deserialize, Serialize};
 use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
 
 use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::events::{extract_attribute, Error as EventError, RawObject};
-use crate::timestamp::{Expiry::Expired, Timestamp};
+use crate::timestamp::Timestamp;
 use crate::Height;
 
 use super::handler::{
@@ -106,28 +107,45 @@ pub struct Packet {
     pub destination_channel: ChannelId,
     #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
     pub data: Vec<u8>,
-    pub timeout_height: Height,
+    pub timeout_height: TimeoutHeight,
     pub timeout_timestamp: Timestamp,
 }
 
 impl Packet {
-    /// Checks whether a packet from a
-    /// [`SendPacket`](crate::core::ics04_channel::events::SendPacket)
-    /// event is timed-out relative to the current state of the
+    /// Checks whether the packet is timed-out relative to the given
+    /// height `host_height` and timestamp `host_timestamp` of the
     /// destination chain.
     ///
-    /// Checks both for time-out relative to the destination chain's
-    /// current timestamp `dst_chain_ts` as well as relative to
-    /// the height `dst_chain_height`.
+    /// Both timeouts are inclusive: a packet can no longer be received
+    /// once the destination chain has reached its timeout height or its
+    /// timeout timestamp. This is the check performed by the `RecvPacket`
+    /// handler against the host chain, by the `Timeout` handler against
+    /// the counterparty state at the proof height, and by the relayer
+    /// against the latest state of the destination chain.
     ///
     /// Note: a timed-out packet should result in a
     /// [`MsgTimeout`](crate::core::ics04_channel::msgs::timeout::MsgTimeout),
     /// instead of the common-case where it results in
     /// [`MsgRecvPacket`](crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket).
-    pub fn timed_out(&self, dst_chain_ts: &Timestamp, dst_chain_height: Height) -> bool {
-        (self.timeout_height != Height::zero() && self.timeout_height < dst_chain_height)
-            || (self.timeout_timestamp != Timestamp::none()
-                && dst_chain_ts.check_expiry(&self.timeout_timestamp) == Expired)
+    pub fn timed_out(&self, host_height: Height, host_timestamp: Timestamp) -> bool {
+        self.timeout_height_expired(host_height) || self.timeout_timestamp_expired(host_timestamp)
+    }
+
+    /// Checks whether the timeout height of the packet has been reached
+    /// by a chain at height `host_height`.
+    pub fn timeout_height_expired(&self, host_height: Height) -> bool {
+        self.timeout_height.has_expired(host_height)
+    }
+
+    /// Checks whether the timeout timestamp of the packet has been reached
+    /// by a chain at timestamp `host_timestamp`. A packet without a timeout
+    /// timestamp never expires, and neither does any packet when the host
+    /// timestamp is unknown.
+    pub fn timeout_timestamp_expired(&self, host_timestamp: Timestamp) -> bool {
+        let timeout = self.timeout_timestamp.nanoseconds();
+        let host = host_timestamp.nanoseconds();
+
+        timeout != 0 && host != 0 && host >= timeout
     }
 }
 
@@ -155,12 +173,12 @@ impl TryFrom<RawPacket> for Packet {
         if Sequence::from(raw_pkt.sequence).is_zero() {
             return Err(Error::zero_packet_sequence());
         }
-        let packet_timeout_height: Height = raw_pkt
+        let packet_timeout_height: TimeoutHeight = raw_pkt
             .timeout_height
             .ok_or_else(Error::missing_height)?
             .into();
 
-        if packet_timeout_height.is_zero() && raw_pkt.timeout_timestamp == 0 {
+        if !packet_timeout_height.is_set() && raw_pkt.timeout_timestamp == 0 {
             return Err(Error::zero_packet_timeout());
         }
         if raw_pkt.data.is_empty() {
@@ -279,6 +297,9 @@ mod tests {
 
     use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
 
     #[test]
     fn packet_try_from_raw() {
@@ -426,4 +447,114 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    /// Small domains over which the timeout checks are exhaustively exercised.
+    fn timeout_heights() -> Vec<TimeoutHeight> {
+        let mut heights = vec![TimeoutHeight::Never];
+        for revision_number in 0..3 {
+            for revision_height in 1..4 {
+                heights.push(TimeoutHeight::At(Height::new(
+                    revision_number,
+                    revision_height,
+                )));
+            }
+        }
+        heights
+    }
+
+    fn host_heights() -> Vec<Height> {
+        (0..3)
+            .flat_map(|number| (0..5).map(move |height| Height::new(number, height)))
+            .collect()
+    }
+
+    fn timestamps() -> Vec<Timestamp> {
+        (0..4)
+            .map(|nanos| Timestamp::from_nanoseconds(nanos * 10).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn timed_out_matches_specification() {
+        for timeout_height in timeout_heights() {
+            for timeout_timestamp in timestamps() {
+                let packet = Packet {
+                    timeout_height,
+                    timeout_timestamp,
+                    ..Default::default()
+                };
+
+                for host_height in host_heights() {
+                    for host_timestamp in timestamps() {
+                        let height_expired = match timeout_height {
+                            TimeoutHeight::At(height) => host_height >= height,
+                            TimeoutHeight::Never => false,
+                        };
+                        let timestamp_expired = timeout_timestamp.nanoseconds() != 0
+                            && host_timestamp.nanoseconds() != 0
+                            && host_timestamp.nanoseconds() >= timeout_timestamp.nanoseconds();
+
+                        assert_eq!(
+                            packet.timed_out(host_height, host_timestamp),
+                            height_expired || timestamp_expired,
+                            "packet with timeout ({}, {}) at host ({}, {})",
+                            timeout_height,
+                            timeout_timestamp,
+                            host_height,
+                            host_timestamp,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn timed_out_is_monotonic() {
+        // Once a packet timed out, it stays timed out as the host chain progresses.
+        for timeout_height in timeout_heights() {
+            for timeout_timestamp in timestamps() {
+                let packet = Packet {
+                    timeout_height,
+                    timeout_timestamp,
+                    ..Default::default()
+                };
+
+                for host_height in host_heights() {
+                    for host_timestamp in timestamps() {
+                        if !packet.timed_out(host_height, host_timestamp) {
+                            continue;
+                        }
+
+                        for later_height in host_heights().into_iter().filter(|h| *h >= host_height)
+                        {
+                            for later_timestamp in timestamps()
+                                .into_iter()
+                                .filter(|t| t.nanoseconds() >= host_timestamp.nanoseconds())
+                            {
+                                assert!(packet.timed_out(later_height, later_timestamp));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn timed_out_at_exact_timeout() {
+        let packet = Packet {
+            timeout_height: Height::new(1, 10).into(),
+            timeout_timestamp: Timestamp::from_nanoseconds(100).unwrap(),
+            ..Default::default()
+        };
+
+        let before = Timestamp::from_nanoseconds(99).unwrap();
+        let at = Timestamp::from_nanoseconds(100).unwrap();
+
+        assert!(!packet.timed_out(Height::new(1, 9), before));
+        assert!(packet.timed_out(Height::new(1, 10), before));
+        assert!(packet.timed_out(Height::new(1, 9), at));
+        assert!(packet.timed_out(Height::new(2, 1), before));
+    }
 }
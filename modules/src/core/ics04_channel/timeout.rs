@@ -0,0 +1,185 @@
+//! Definition of the timeout height of a packet.
+//!
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+use crate::core::ics02_client::height::{Height, HeightError};
+use crate::prelude::*;
+
+/// Indicates a consensus height on the destination chain at and after
+/// which the packet will no longer be processed, and will instead count
+/// as having timed-out.
+///
+/// In the protocol, a height of zero stands for the absence of a timeout
+/// height. This type makes that case explicit, and converts from and to
+/// a zero `Height` at the boundaries (protobuf, events and commitments).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "Height", into = "Height")]
+pub enum TimeoutHeight {
+    Never,
+    At(Height),
+}
+
+impl TimeoutHeight {
+    pub fn no_timeout() -> Self {
+        Self::Never
+    }
+
+    /// Returns `true` if a timeout height is set.
+    pub fn is_set(&self) -> bool {
+        matches!(self, Self::At(_))
+    }
+
+    /// Returns the timeout height, if any.
+    pub fn height(&self) -> Option<Height> {
+        match self {
+            Self::At(height) => Some(*height),
+            Self::Never => None,
+        }
+    }
+
+    /// Checks whether the timeout height has been reached by a chain
+    /// at the given `height`. The timeout height is inclusive, i.e.,
+    /// a packet with timeout height `h` can no longer be received at
+    /// height `h` on the destination chain.
+    pub fn has_expired(&self, height: Height) -> bool {
+        match self {
+            Self::At(timeout_height) => height >= *timeout_height,
+            Self::Never => false,
+        }
+    }
+}
+
+impl Default for TimeoutHeight {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+impl From<Height> for TimeoutHeight {
+    fn from(height: Height) -> Self {
+        // Only `0-0` stands for the absence of a timeout height, a height
+        // of zero in a non-zero revision is a valid timeout height.
+        if height == Height::zero() {
+            Self::Never
+        } else {
+            Self::At(height)
+        }
+    }
+}
+
+impl From<TimeoutHeight> for Height {
+    fn from(timeout_height: TimeoutHeight) -> Self {
+        match timeout_height {
+            TimeoutHeight::At(height) => height,
+            TimeoutHeight::Never => Height::zero(),
+        }
+    }
+}
+
+impl From<RawHeight> for TimeoutHeight {
+    fn from(raw_height: RawHeight) -> Self {
+        Height::from(raw_height).into()
+    }
+}
+
+impl From<TimeoutHeight> for RawHeight {
+    fn from(timeout_height: TimeoutHeight) -> Self {
+        Height::from(timeout_height).into()
+    }
+}
+
+impl FromStr for TimeoutHeight {
+    type Err = HeightError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Height>().map(Into::into)
+    }
+}
+
+impl Display for TimeoutHeight {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::At(height) => write!(f, "{}", height),
+            Self::Never => write!(f, "no timeout"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_height_is_no_timeout() {
+        assert_eq!(TimeoutHeight::from(Height::zero()), TimeoutHeight::Never);
+        assert_eq!(
+            TimeoutHeight::from(Height::new(1, 0)),
+            TimeoutHeight::At(Height::new(1, 0))
+        );
+        assert_eq!(Height::from(TimeoutHeight::Never), Height::zero());
+        assert_eq!(
+            "0-0".parse::<TimeoutHeight>().unwrap(),
+            TimeoutHeight::Never
+        );
+        assert_eq!(
+            "1-10".parse::<TimeoutHeight>().unwrap(),
+            TimeoutHeight::At(Height::new(1, 10))
+        );
+    }
+
+    #[test]
+    fn no_timeout_never_expires() {
+        for revision_number in 0..4 {
+            for revision_height in 0..4 {
+                let height = Height::new(revision_number, revision_height);
+                assert!(!TimeoutHeight::Never.has_expired(height));
+            }
+        }
+    }
+
+    #[test]
+    fn timeout_height_expiry_is_inclusive() {
+        // Exhaustively check all pairs of small heights, including
+        // heights across revision boundaries.
+        let heights = (0..4)
+            .flat_map(|number| (1..5).map(move |height| Height::new(number, height)))
+            .collect::<Vec<_>>();
+
+        for timeout in &heights {
+            let timeout_height = TimeoutHeight::At(*timeout);
+
+            for height in &heights {
+                assert_eq!(
+                    timeout_height.has_expired(*height),
+                    height >= timeout,
+                    "timeout height {} at chain height {}",
+                    timeout,
+                    height
+                );
+            }
+
+            assert!(timeout_height.has_expired(*timeout));
+            assert!(timeout_height.has_expired(timeout.increment()));
+            assert!(!timeout_height.has_expired(timeout.with_revision_height(0)));
+        }
+    }
+
+    #[test]
+    fn roundtrip_through_height() {
+        for revision_number in 0..4 {
+            for revision_height in 0..4 {
+                let height = Height::new(revision_number, revision_height);
+                let timeout_height = TimeoutHeight::from(height);
+                let raw = RawHeight::from(timeout_height);
+                assert_eq!(TimeoutHeight::from(raw), timeout_height);
+                assert_eq!(Height::from(timeout_height), height);
+            }
+        }
+    }
+}
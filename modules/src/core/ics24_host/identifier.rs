@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-use core::convert::{From, Infallible};
+use core::convert::From;
 use core::fmt::{self, Display, Formatter};
 use core::str::FromStr;
 use serde::{Deserialize, Serialize};
@@ -10,11 +10,18 @@ use crate::core::ics24_host::error::ValidationError;
 
 use super::validate::*;
 
-/// This type is subject to future changes.
+/// The identifier of a chain, optionally carrying a revision number.
 ///
-/// TODO: ChainId validation is not standardized yet.
-///       `is_epoch_format` will most likely be replaced by validate_chain_id()-style function.
-///       See: <https://github.com/informalsystems/ibc-rs/pull/304#discussion_r503917283>.
+/// Chain identifiers in the format `{chain name}-{revision number}`, where the
+/// revision number is non-zero and has no leading zero, are said to be in
+/// _epoch format_ (or _revision format_). For such identifiers, the revision
+/// number is the `revision_number` of every `Height` of the chain, and it is
+/// incremented whenever the chain undergoes an upgrade that resets its height
+/// (eg. `foo-1` becomes `foo-2`). Any other identifier has revision number 0.
+///
+/// Strictly validated identifiers are obtained through the [`FromStr`]
+/// implementation, whereas [`ChainId::from_string`] accepts any identifier
+/// reported by a chain as-is.
 ///
 /// Also, contrast with tendermint-rs `ChainId` type.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -42,6 +49,10 @@ impl ChainId {
         }
     }
 
+    /// Creates a `ChainId` from the given string without validating it.
+    ///
+    /// This should only be used for chain identifiers obtained from a chain,
+    /// for which validation is the responsibility of the chain itself.
     pub fn from_string(id: &str) -> Self {
         let version = if Self::is_epoch_format(id) {
             Self::chain_version(id)
@@ -66,6 +77,69 @@ impl ChainId {
         self.version
     }
 
+    /// Get the name of the chain, ie. the identifier without its revision
+    /// number, if the identifier is in epoch format.
+    /// ```
+    /// use ibc::core::ics24_host::identifier::ChainId;
+    ///
+    /// assert_eq!(ChainId::from_string("cosmos-hub-97").name(), "cosmos-hub");
+    /// assert_eq!(ChainId::from_string("chainA-0").name(), "chainA-0");
+    /// assert_eq!(ChainId::from_string("chainA").name(), "chainA");
+    /// ```
+    pub fn name(&self) -> &str {
+        if Self::is_epoch_format(&self.id) {
+            self.id
+                .rsplit_once('-')
+                .map(|(name, _)| name)
+                .unwrap_or(&self.id)
+        } else {
+            &self.id
+        }
+    }
+
+    /// Returns the identifier of this chain at the given revision number.
+    ///
+    /// Fails if the resulting identifier is not a valid chain identifier,
+    /// or if the revision number is zero, which cannot be expressed in
+    /// epoch format.
+    /// ```
+    /// use ibc::core::ics24_host::identifier::ChainId;
+    ///
+    /// let id = ChainId::from_string("ibc-1");
+    /// assert_eq!(id.with_version(5).unwrap(), ChainId::from_string("ibc-5"));
+    /// assert!(id.with_version(0).is_err());
+    /// ```
+    pub fn with_version(&self, version: u64) -> Result<Self, ValidationError> {
+        if version == 0 {
+            return Err(ValidationError::chain_id_invalid_format(format!(
+                "{}-{}",
+                self.name(),
+                version
+            )));
+        }
+
+        let upgraded = Self::new(self.name().to_string(), version);
+        validate_chain_identifier(upgraded.as_str())?;
+
+        Ok(upgraded)
+    }
+
+    /// Returns the identifier the chain assumes after an upgrade which
+    /// increments its revision number.
+    /// ```
+    /// use ibc::core::ics24_host::identifier::ChainId;
+    ///
+    /// let id = ChainId::from_string("foo-1").increment_version().unwrap();
+    /// assert_eq!(id.as_str(), "foo-2");
+    /// assert_eq!(id.version(), 2);
+    ///
+    /// let id = ChainId::from_string("foo").increment_version().unwrap();
+    /// assert_eq!(id.as_str(), "foo-1");
+    /// ```
+    pub fn increment_version(&self) -> Result<Self, ValidationError> {
+        self.with_version(self.version + 1)
+    }
+
     /// Extract the version from the given chain identifier.
     /// ```
     /// use ibc::core::ics24_host::identifier::ChainId;
@@ -74,17 +148,16 @@ impl ChainId {
     /// assert_eq!(ChainId::chain_version("ibc-10"), 10);
     /// assert_eq!(ChainId::chain_version("cosmos-hub-97"), 97);
     /// assert_eq!(ChainId::chain_version("testnet-helloworld-2"), 2);
+    /// assert_eq!(ChainId::chain_version("ibc-99999999999999999999"), 0);
     /// ```
     pub fn chain_version(chain_id: &str) -> u64 {
         if !ChainId::is_epoch_format(chain_id) {
             return 0;
         }
 
-        let split: Vec<_> = chain_id.split('-').collect();
-        split
-            .last()
-            .expect("get revision number from chain_id")
-            .parse()
+        chain_id
+            .rsplit_once('-')
+            .and_then(|(_, version)| version.parse().ok())
             .unwrap_or(0)
     }
 
@@ -102,10 +175,19 @@ impl ChainId {
     }
 }
 
+/// Parses and validates a chain identifier.
+/// ```
+/// use ibc::core::ics24_host::identifier::ChainId;
+///
+/// assert!("ibc-0".parse::<ChainId>().is_ok());
+/// assert!("ibc/0".parse::<ChainId>().is_err());
+/// assert!("".parse::<ChainId>().is_err());
+/// ```
 impl FromStr for ChainId {
-    type Err = Infallible;
+    type Err = ValidationError;
 
     fn from_str(id: &str) -> Result<Self, Self::Err> {
+        validate_chain_identifier(id)?;
         Ok(Self::from_string(id))
     }
 }
@@ -124,13 +206,13 @@ impl From<ChainId> for tendermint::chain::Id {
 
 impl From<tendermint::chain::Id> for ChainId {
     fn from(id: tendermint::chain::Id) -> Self {
-        ChainId::from_str(id.as_str()).unwrap()
+        ChainId::from_string(id.as_str())
     }
 }
 
 impl Default for ChainId {
     fn default() -> Self {
-        "defaultChainId".to_string().parse().unwrap()
+        Self::from_string("defaultChainId")
     }
 }
 
@@ -43,6 +43,15 @@ pub fn validate_identifier(id: &str, min: usize, max: usize) -> Result<(), Error
     Ok(())
 }
 
+/// Default validator function for Chain identifiers.
+///
+/// A valid identifier must be between 1-50 characters, the maximum length
+/// of a Tendermint chain identifier, and only contain alphanumeric
+/// characters or one of the special characters listed in [`validate_identifier`].
+pub fn validate_chain_identifier(id: &str) -> Result<(), Error> {
+    validate_identifier(id, 1, 50)
+}
+
 /// Default validator function for Client identifiers.
 ///
 /// A valid identifier must be between 9-64 characters and only contain lowercase
@@ -78,8 +87,8 @@ pub fn validate_channel_identifier(id: &str) -> Result<(), Error> {
 #[cfg(test)]
 mod tests {
     use crate::core::ics24_host::validate::{
-        validate_channel_identifier, validate_client_identifier, validate_connection_identifier,
-        validate_identifier, validate_port_identifier,
+        validate_chain_identifier, validate_channel_identifier, validate_client_identifier,
+        validate_connection_identifier, validate_identifier, validate_port_identifier,
     };
     use test_log::test;
 
@@ -147,6 +156,22 @@ mod tests {
         assert!(id.is_err())
     }
 
+    #[test]
+    fn parse_chain_id() {
+        assert!(validate_chain_identifier("ibc-0").is_ok());
+        assert!(validate_chain_identifier("cosmoshub-4").is_ok());
+        assert!(validate_chain_identifier("").is_err());
+        assert!(validate_chain_identifier("chain/1").is_err());
+        assert!(validate_chain_identifier("chain 1").is_err());
+    }
+
+    #[test]
+    fn parse_chain_id_max() {
+        // invalid max chain id (test string length is 51)
+        let id = validate_chain_identifier("u7mhybw9ipnbcbrmzx3a3k0xbmbbjfkhbs4d0ueg1y8vvwpqdt1");
+        assert!(id.is_err())
+    }
+
     #[test]
     fn parse_invalid_id_chars() {
         // invalid id chars
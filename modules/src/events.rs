@@ -17,6 +17,7 @@ use crate::core::ics03_connection::events::Attributes as ConnectionAttributes;
 use crate::core::ics04_channel::error as channel_error;
 use crate::core::ics04_channel::events as ChannelEvents;
 use crate::core::ics04_channel::events::Attributes as ChannelAttributes;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::packet::Packet;
 use crate::core::ics24_host::error::ValidationError;
 use crate::timestamp::ParseTimestampError;
@@ -455,7 +456,7 @@ impl IbcEvent {
         }
     }
 
-    pub fn ack(&self) -> Option<&[u8]> {
+    pub fn ack(&self) -> Option<&Acknowledgement> {
         match self {
             IbcEvent::WriteAcknowledgement(ev) => Some(&ev.ack),
             _ => None,
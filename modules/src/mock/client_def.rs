@@ -7,6 +7,7 @@ use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::Error;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
@@ -120,7 +121,7 @@ impl ClientDef for MockClient {
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-        _data: String,
+        _commitment: PacketCommitment,
     ) -> Result<(), Error> {
         Ok(())
     }
@@ -133,7 +134,7 @@ impl ClientDef for MockClient {
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-        _data: Vec<u8>,
+        _ack: AcknowledgementCommitment,
     ) -> Result<(), Error> {
         Ok(())
     }
@@ -4,16 +4,17 @@ use crate::prelude::*;
 
 use alloc::collections::btree_map::BTreeMap;
 use core::cmp::min;
+use core::time::Duration;
 
 use tracing::debug;
 
 use prost_types::Any;
-use sha2::Digest;
 
 use crate::applications::ics20_fungible_token_transfer::context::Ics20Context;
 use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+use crate::clients::ics07_tendermint::client_state::HostParams as TmHostParams;
 use crate::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
-use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
 use crate::core::ics02_client::error::Error as Ics02Error;
@@ -22,6 +23,7 @@ use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
 use crate::core::ics03_connection::error::Error as Ics03Error;
 use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::Error as Ics04Error;
 use crate::core::ics04_channel::packet::{Receipt, Sequence};
@@ -29,6 +31,7 @@ use crate::core::ics05_port::capabilities::Capability;
 use crate::core::ics05_port::context::PortReader;
 use crate::core::ics05_port::error::Error as Ics05Error;
 use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics23_commitment::specs::ProofSpecs;
 use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use crate::core::ics26_routing::context::Ics26Context;
 use crate::core::ics26_routing::handler::{deliver, dispatch};
@@ -43,6 +46,10 @@ use crate::signer::Signer;
 use crate::timestamp::Timestamp;
 use crate::Height;
 
+/// The unbonding period of the host chain underlying a context mock. Matches the
+/// unbonding period of the dummy Tendermint client states used in tests.
+pub const MOCK_HOST_UNBONDING_PERIOD: Duration = Duration::from_secs(128000);
+
 /// A context implementing the dependencies necessary for testing any IBC module.
 #[derive(Clone, Debug)]
 pub struct MockContext {
@@ -99,13 +106,13 @@ pub struct MockContext {
     /// Tracks the sequence number for the next packet to be acknowledged.
     next_sequence_ack: BTreeMap<(PortId, ChannelId), Sequence>,
 
-    packet_acknowledgement: BTreeMap<(PortId, ChannelId, Sequence), String>,
+    packet_acknowledgement: BTreeMap<(PortId, ChannelId, Sequence), AcknowledgementCommitment>,
 
     /// Maps ports to their capabilities
     port_capabilities: BTreeMap<PortId, Capability>,
 
     /// Constant-size commitments to packets data fields
-    packet_commitment: BTreeMap<(PortId, ChannelId, Sequence), String>,
+    packet_commitment: BTreeMap<(PortId, ChannelId, Sequence), PacketCommitment>,
 
     // Used by unordered channel
     packet_receipt: BTreeMap<(PortId, ChannelId, Sequence), Receipt>,
@@ -407,7 +414,7 @@ impl MockContext {
         port_id: PortId,
         chan_id: ChannelId,
         seq: Sequence,
-        data: String,
+        data: PacketCommitment,
     ) -> Self {
         let mut packet_commitment = self.packet_commitment.clone();
         packet_commitment.insert((port_id, chan_id, seq), data);
@@ -489,6 +496,53 @@ impl MockContext {
         Ok(())
     }
 
+    /// Returns the identifiers of the clients hosted by this context.
+    pub fn client_ids(&self) -> Vec<ClientId> {
+        self.clients.keys().cloned().collect()
+    }
+
+    /// Returns the identifier of the connection of the given client, if any.
+    pub fn client_connection(&self, client_id: &ClientId) -> Option<&ConnectionId> {
+        self.client_connections.get(client_id)
+    }
+
+    /// Returns the identifiers of the connections hosted by this context.
+    pub fn connection_ids(&self) -> Vec<ConnectionId> {
+        self.connections.keys().cloned().collect()
+    }
+
+    /// Returns the port and channel identifiers of the channels hosted by this context.
+    pub fn channel_ids(&self) -> Vec<(PortId, ChannelId)> {
+        self.channels.keys().cloned().collect()
+    }
+
+    /// Returns the sequences of the packets sent on the given channel whose commitment is stored.
+    pub fn packet_commitment_sequences(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Vec<Sequence> {
+        self.packet_commitment
+            .keys()
+            .filter(|(p, c, _)| p == port_id && c == channel_id)
+            .map(|(_, _, sequence)| *sequence)
+            .collect()
+    }
+
+    /// Returns the sequences of the packets received on the given channel whose acknowledgement
+    /// is stored.
+    pub fn packet_acknowledgement_sequences(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Vec<Sequence> {
+        self.packet_acknowledgement
+            .keys()
+            .filter(|(p, c, _)| p == port_id && c == channel_id)
+            .map(|(_, _, sequence)| *sequence)
+            .collect()
+    }
+
     pub fn add_port(&mut self, port_id: PortId) {
         self.port_capabilities.insert(port_id, Capability::new());
     }
@@ -625,7 +679,7 @@ impl ChannelReader for MockContext {
     fn get_packet_commitment(
         &self,
         key: &(PortId, ChannelId, Sequence),
-    ) -> Result<String, Ics04Error> {
+    ) -> Result<PacketCommitment, Ics04Error> {
         match self.packet_commitment.get(key) {
             Some(commitment) => Ok(commitment.clone()),
             None => Err(Ics04Error::packet_commitment_not_found(key.2)),
@@ -645,18 +699,13 @@ impl ChannelReader for MockContext {
     fn get_packet_acknowledgement(
         &self,
         key: &(PortId, ChannelId, Sequence),
-    ) -> Result<String, Ics04Error> {
+    ) -> Result<AcknowledgementCommitment, Ics04Error> {
         match self.packet_acknowledgement.get(key) {
             Some(ack) => Ok(ack.clone()),
             None => Err(Ics04Error::packet_acknowledgement_not_found(key.2)),
         }
     }
 
-    fn hash(&self, input: String) -> String {
-        let r = sha2::Sha256::digest(input.as_bytes());
-        format!("{:x}", r)
-    }
-
     fn host_height(&self) -> Height {
         self.latest_height
     }
@@ -674,24 +723,18 @@ impl ChannelKeeper for MockContext {
     fn store_packet_commitment(
         &mut self,
         key: (PortId, ChannelId, Sequence),
-        timeout_timestamp: Timestamp,
-        timeout_height: Height,
-        data: Vec<u8>,
+        commitment: PacketCommitment,
     ) -> Result<(), Ics04Error> {
-        let input = format!("{:?},{:?},{:?}", timeout_timestamp, timeout_height, data);
-        self.packet_commitment
-            .insert(key, ChannelReader::hash(self, input));
+        self.packet_commitment.insert(key, commitment);
         Ok(())
     }
 
     fn store_packet_acknowledgement(
         &mut self,
         key: (PortId, ChannelId, Sequence),
-        ack: Vec<u8>,
+        ack_commitment: AcknowledgementCommitment,
     ) -> Result<(), Ics04Error> {
-        let input = format!("{:?}", ack);
-        self.packet_acknowledgement
-            .insert(key, ChannelReader::hash(self, input));
+        self.packet_acknowledgement.insert(key, ack_commitment);
         Ok(())
     }
 
@@ -816,6 +859,46 @@ impl ConnectionReader for MockContext {
         }
     }
 
+    fn validate_self_client(
+        &self,
+        counterparty_client_state: &AnyClientState,
+    ) -> Result<(), Ics03Error> {
+        match counterparty_client_state {
+            AnyClientState::Tendermint(client_state) => {
+                let host = TmHostParams {
+                    chain_id: self.host_chain_id.clone(),
+                    current_height: self.latest_height,
+                    unbonding_period: MOCK_HOST_UNBONDING_PERIOD,
+                    proof_specs: ProofSpecs::default(),
+                    upgrade_path: Vec::new(),
+                };
+
+                client_state
+                    .validate_self_client(&host)
+                    .map_err(|e| Ics03Error::invalid_self_client(e.to_string()))
+            }
+            AnyClientState::Mock(client_state) => {
+                if client_state.is_frozen() {
+                    return Err(Ics03Error::invalid_self_client(
+                        "client is frozen".to_string(),
+                    ));
+                }
+
+                let latest_height = client_state.latest_height();
+                if !latest_height.is_same_revision(&self.latest_height)
+                    || latest_height >= self.latest_height
+                {
+                    return Err(Ics03Error::invalid_self_client(format!(
+                        "latest height {} is not a past height of the host, which is at height {}",
+                        latest_height, self.latest_height
+                    )));
+                }
+
+                Ok(())
+            }
+        }
+    }
+
     fn connection_counter(&self) -> Result<u64, Ics03Error> {
         Ok(self.connection_ids_counter)
     }
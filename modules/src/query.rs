@@ -2,6 +2,7 @@ use tendermint::abci::transaction::Hash;
 
 use crate::core::ics02_client::client_consensus::QueryClientEventRequest;
 use crate::core::ics04_channel::channel::QueryPacketEventDataRequest;
+use crate::Height;
 
 /// Used for queries and not yet standardized in channel's query.proto
 #[derive(Clone, Debug)]
@@ -14,6 +15,20 @@ pub enum QueryTxRequest {
 #[derive(Clone, Debug)]
 pub enum QueryBlockRequest {
     Packet(QueryPacketEventDataRequest),
+    PacketRange(QueryBlockRangeRequest),
+}
+
+/// Used to scan every block from `from_height` to `to_height` (inclusive) for the packet
+/// events matching `packet`, without relying on the transaction index of the node.
+/// The `height` of the packet request is not taken into account.
+///
+/// The events emitted by the begin blocks and the transactions are returned first,
+/// followed by those emitted by the end blocks, each in increasing height order.
+#[derive(Clone, Debug)]
+pub struct QueryBlockRangeRequest {
+    pub packet: QueryPacketEventDataRequest,
+    pub from_height: Height,
+    pub to_height: Height,
 }
 
 #[derive(Clone, Debug)]
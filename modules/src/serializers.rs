@@ -1,3 +1,6 @@
+use crate::prelude::*;
+
+use serde::de::{Deserialize, Deserializer, Error as _};
 use serde::ser::{Serialize, Serializer};
 use subtle_encoding::{Encoding, Hex};
 
@@ -9,3 +12,11 @@ where
     let hex = Hex::upper_case().encode_to_string(data).unwrap();
     hex.serialize(serializer)
 }
+
+pub fn de_hex_upper<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hex = String::deserialize(deserializer)?;
+    Hex::upper_case().decode(hex).map_err(D::Error::custom)
+}
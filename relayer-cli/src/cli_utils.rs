@@ -7,9 +7,8 @@ use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer::chain::counterparty::channel_connection_client;
 use ibc_relayer::{
     chain::{
+        backend::spawn_chain_backend,
         handle::{ChainHandle, ProdChainHandle},
-        runtime::ChainRuntime,
-        CosmosSdkChain,
     },
     config::Config,
 };
@@ -66,8 +65,7 @@ pub fn spawn_chain_runtime_generic<Chain: ChainHandle>(
         .ok_or_else(|| Error::missing_config(chain_id.clone()))?;
 
     let rt = Arc::new(TokioRuntime::new().unwrap());
-    let handle =
-        ChainRuntime::<CosmosSdkChain>::spawn::<Chain>(chain_config, rt).map_err(Error::relayer)?;
+    let handle = spawn_chain_backend::<Chain>(chain_config, rt).map_err(Error::relayer)?;
 
     Ok(handle)
 }
@@ -15,11 +15,12 @@ use crate::DEFAULT_CONFIG_PATH;
 use ibc_relayer::config::Config;
 
 use self::{
-    config::ConfigCmd, create::CreateCmds, health::HealthCheckCmd, keys::KeysCmd,
+    clear::ClearCmds, config::ConfigCmd, create::CreateCmds, health::HealthCheckCmd, keys::KeysCmd,
     listen::ListenCmd, misbehaviour::MisbehaviourCmd, query::QueryCmd, start::StartCmd, tx::TxCmd,
     update::UpdateCmds, upgrade::UpgradeCmds, version::VersionCmd,
 };
 
+mod clear;
 mod config;
 mod create;
 mod health;
@@ -77,6 +78,13 @@ pub enum CliCmd {
     #[clap(subcommand, about = "Create and send IBC transactions")]
     Tx(TxCmd),
 
+    /// The `clear` subcommand
+    #[clap(
+        subcommand,
+        about = "Clear objects, such as outstanding packets, on chains"
+    )]
+    Clear(ClearCmds),
+
     /// The `listen` subcommand
     #[clap(about = "Listen to and display IBC events emitted by a chain")]
     Listen(ListenCmd),
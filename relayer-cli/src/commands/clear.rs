@@ -0,0 +1,83 @@
+//! `clear` subcommand
+
+use abscissa_core::{Clap, Command, Runnable};
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::events::IbcEvent;
+use ibc::Height;
+use ibc_relayer::link::{Link, LinkParameters};
+
+use crate::cli_utils::ChainHandlePair;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+#[derive(Command, Debug, Clap, Runnable)]
+pub enum ClearCmds {
+    /// Subcommand for clearing the `packets` of a channel within a range of blocks
+    #[clap(
+        about = "Relay the packets sent, and the acknowledgments written, on a channel within a range of blocks of the source chain"
+    )]
+    Packets(ClearPacketsCmd),
+}
+
+#[derive(Clone, Command, Debug, Clap)]
+pub struct ClearPacketsCmd {
+    #[clap(required = true, about = "identifier of the destination chain")]
+    dst_chain_id: ChainId,
+
+    #[clap(required = true, about = "identifier of the source chain")]
+    src_chain_id: ChainId,
+
+    #[clap(required = true, about = "identifier of the source port")]
+    src_port_id: PortId,
+
+    #[clap(required = true, about = "identifier of the source channel")]
+    src_channel_id: ChannelId,
+
+    #[clap(
+        long,
+        required = true,
+        about = "height of the first block of the source chain to scan"
+    )]
+    from_height: u64,
+
+    #[clap(
+        long,
+        required = true,
+        about = "height of the last block of the source chain to scan"
+    )]
+    to_height: u64,
+}
+
+impl Runnable for ClearPacketsCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chains = match ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id) {
+            Ok(chains) => chains,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
+        let from_height = Height::new(self.src_chain_id.version(), self.from_height);
+        let to_height = Height::new(self.src_chain_id.version(), self.to_height);
+
+        let opts = LinkParameters {
+            src_port_id: self.src_port_id.clone(),
+            src_channel_id: self.src_channel_id.clone(),
+        };
+        let mut link = match Link::new_from_opts(chains.src, chains.dst, opts, false) {
+            Ok(link) => link,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
+        let res: Result<Vec<IbcEvent>, Error> = link
+            .build_and_send_packet_messages_in_height_range(from_height, to_height)
+            .map_err(Error::link);
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
@@ -9,7 +9,7 @@ use abscissa_core::{Clap, Command, Runnable};
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{HDPath, KeyEntry, KeyRing, Store},
+    keyring::{HDPath, KeyEntry, KeyRing},
 };
 
 use crate::application::app_config;
@@ -98,7 +98,7 @@ pub fn add_key(
     file: &Path,
     hd_path: &HDPath,
 ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut keyring = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)?;
 
     let key_contents = fs::read_to_string(file).map_err(|_| "error reading the key file")?;
     let key = keyring.key_from_seed_file(&key_contents, hd_path)?;
@@ -5,7 +5,7 @@ use abscissa_core::{Clap, Command, Runnable};
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{KeyEntry, KeyRing, Store},
+    keyring::{KeyEntry, KeyRing},
 };
 
 use crate::conclude::Output;
@@ -63,7 +63,7 @@ pub struct KeysListOptions {
 pub fn list_keys(
     config: ChainConfig,
 ) -> Result<Vec<(String, KeyEntry)>, Box<dyn std::error::Error>> {
-    let keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let keyring = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)?;
     let keys = keyring.keys()?;
     Ok(keys)
 }
@@ -5,7 +5,7 @@ use abscissa_core::{Clap, Command, Runnable};
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{HDPath, KeyEntry, KeyRing, Store},
+    keyring::{HDPath, KeyEntry, KeyRing},
 };
 
 use crate::application::app_config;
@@ -99,7 +99,7 @@ pub fn restore_key(
     hdpath: &HDPath,
     config: &ChainConfig,
 ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut keyring = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)?;
     let key_entry = keyring.key_from_mnemonic(mnemonic, hdpath, &config.address_type)?;
 
     keyring.add_key(key_name, key_entry.clone())?;
@@ -10,9 +10,8 @@ use tendermint::abci::transaction::Hash;
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc::query::{QueryTxHash, QueryTxRequest};
 
+use ibc_relayer::chain::backend::spawn_chain_backend;
 use ibc_relayer::chain::handle::{ChainHandle, ProdChainHandle};
-use ibc_relayer::chain::runtime::ChainRuntime;
-use ibc_relayer::chain::CosmosSdkChain;
 
 use crate::conclude::{exit_with_unrecoverable_error, Output};
 use crate::error::Error;
@@ -44,9 +43,8 @@ impl Runnable for QueryTxEventsCmd {
         };
 
         let rt = Arc::new(TokioRuntime::new().unwrap());
-        let chain =
-            ChainRuntime::<CosmosSdkChain>::spawn::<ProdChainHandle>(chain_config.clone(), rt)
-                .unwrap_or_else(exit_with_unrecoverable_error);
+        let chain = spawn_chain_backend::<ProdChainHandle>(chain_config.clone(), rt)
+            .unwrap_or_else(exit_with_unrecoverable_error);
 
         let res = Hash::from_str(self.hash.as_str())
             .map_err(|e| Error::invalid_hash(self.hash.clone(), e))
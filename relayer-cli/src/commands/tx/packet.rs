@@ -1,8 +1,12 @@
+use core::ops::RangeInclusive;
+
 use abscissa_core::{Clap, Command, Runnable};
 
+use ibc::core::ics04_channel::packet::Sequence;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::events::IbcEvent;
 use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::util::seq_range::parse_seq_range;
 
 use crate::cli_utils::ChainHandlePair;
 use crate::conclude::Output;
@@ -22,6 +26,16 @@ pub struct TxRawPacketRecvCmd {
 
     #[clap(required = true, about = "identifier of the source channel")]
     src_channel_id: ChannelId,
+
+    #[clap(
+        short = 's',
+        long,
+        use_delimiter = true,
+        parse(try_from_str = parse_seq_range),
+        about = "sequences of the packets to relay, as a comma-separated list of single \
+                 sequences or inclusive ranges, eg. '1,5..10' (default: all pending packets)"
+    )]
+    packet_sequences: Vec<RangeInclusive<Sequence>>,
 }
 
 impl Runnable for TxRawPacketRecvCmd {
@@ -43,7 +57,7 @@ impl Runnable for TxRawPacketRecvCmd {
         };
 
         let res: Result<Vec<IbcEvent>, Error> = link
-            .build_and_send_recv_packet_messages()
+            .build_and_send_recv_packet_messages(&self.packet_sequences)
             .map_err(Error::link);
 
         match res {
@@ -66,6 +80,16 @@ pub struct TxRawPacketAckCmd {
 
     #[clap(required = true, about = "identifier of the source channel")]
     src_channel_id: ChannelId,
+
+    #[clap(
+        short = 's',
+        long,
+        use_delimiter = true,
+        parse(try_from_str = parse_seq_range),
+        about = "sequences of the packets whose acknowledgments to relay, as a comma-separated list \
+                 of single sequences or inclusive ranges, eg. '1,5..10' (default: all pending acknowledgments)"
+    )]
+    packet_sequences: Vec<RangeInclusive<Sequence>>,
 }
 
 impl Runnable for TxRawPacketAckCmd {
@@ -87,7 +111,7 @@ impl Runnable for TxRawPacketAckCmd {
         };
 
         let res: Result<Vec<IbcEvent>, Error> = link
-            .build_and_send_ack_packet_messages()
+            .build_and_send_ack_packet_messages(&self.packet_sequences)
             .map_err(Error::link);
 
         match res {
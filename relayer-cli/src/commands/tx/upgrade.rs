@@ -47,6 +47,13 @@ pub struct TxIbcUpgradeChainCmd {
     )]
     new_chain_id: Option<ChainId>,
 
+    #[clap(
+        short = 'r',
+        long,
+        about = "increment the revision number of the upgrading chain identifier, eg. from 'foo-1' to 'foo-2' (optional)"
+    )]
+    increment_revision: bool,
+
     #[clap(
         short = 'u',
         long,
@@ -87,16 +94,33 @@ impl TxIbcUpgradeChainCmd {
             )
         })?;
 
+        if self.increment_revision && self.new_chain_id.is_some() {
+            return Err(
+                "the options '--new-chain-id' and '--increment-revision' are mutually exclusive"
+                    .to_string(),
+            );
+        }
+
+        let upgraded_chain_id = if self.increment_revision {
+            self.dst_chain_id.increment_version().map_err(|e| {
+                format!(
+                    "cannot increment the revision number of chain '{}': {}",
+                    self.dst_chain_id, e
+                )
+            })?
+        } else {
+            self.new_chain_id
+                .clone()
+                .unwrap_or_else(|| self.dst_chain_id.clone())
+        };
+
         let opts = UpgradePlanOptions {
             dst_chain_config: dst_chain_config.clone(),
             src_chain_config: src_chain_config.clone(),
             src_client_id: self.src_client_id.clone(),
             amount: self.amount,
             height_offset: self.height_offset,
-            upgraded_chain_id: self
-                .new_chain_id
-                .clone()
-                .unwrap_or_else(|| self.dst_chain_id.clone()),
+            upgraded_chain_id,
             upgraded_unbonding_period: self.new_unbonding.map(Duration::from_secs),
             upgrade_plan_name: self
                 .upgrade_name
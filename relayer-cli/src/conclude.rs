@@ -69,11 +69,7 @@ pub fn exit_with(out: Output) {
     let status = out.status;
 
     // Handle the output message
-    if json() {
-        println!("{}", serde_json::to_string(&out.into_json()).unwrap());
-    } else {
-        println!("{}: {}", out.status, out.result);
-    }
+    out.print();
 
     // The return code
     if status == Status::Error {
@@ -212,6 +208,16 @@ impl Output {
         exit_with(self);
     }
 
+    /// Prints the current output to `stdout`, as JSON if the JSON output mode is enabled,
+    /// without exiting from the process.
+    pub fn print(self) {
+        if json() {
+            println!("{}", serde_json::to_string(&self.into_json()).unwrap());
+        } else {
+            println!("{}: {}", self.status, self.result);
+        }
+    }
+
     /// Convert this output value to a JSON value
     pub fn into_json(self) -> serde_json::Value {
         let mut map = serde_json::Map::new();
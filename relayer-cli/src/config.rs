@@ -9,7 +9,14 @@ use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc_relayer::config::{Config, ModeConfig};
+use ibc::core::ics24_host::validate::validate_identifier;
+use ibc_relayer::chain::backend::{
+    is_chain_backend_registered, registered_chain_types, COSMOS_SDK_CHAIN_TYPE,
+};
+use ibc_relayer::config::{
+    ChainConfig, ChannelsSpec, Config, DynamicGasPrice, FilterPattern, ModeConfig, PacketFilter,
+    Wildcard,
+};
 use tendermint_light_client::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
 
@@ -71,6 +78,57 @@ define_error! {
                 format!("config file specifies an invalid `gas_adjustment` ({0}) for the chain with id {1}, caused by: {2}",
                     e.gas_adjustment, e.chain_id, e.reason)
             },
+
+        InvalidDynamicGasPrice
+            {
+                chain_id: ChainId,
+                reason: String
+            }
+            |e| {
+                format!("config file specifies an invalid `dynamic_gas_price` for the chain with id {0}, caused by: {1}",
+                    e.chain_id, e.reason)
+            },
+
+        UnknownChainType
+            {
+                chain_type: String,
+                chain_id: ChainId,
+                known_types: String
+            }
+            |e| {
+                format!("config file specifies an unknown `type` ('{0}') for the chain with id {1}, expected one of: {2}",
+                    e.chain_type, e.chain_id, e.known_types)
+            },
+
+        UnexpectedBackendConfig
+            {
+                chain_id: ChainId,
+            }
+            |e| {
+                format!("config file specifies a `backend` section for the chain with id {0}, which its `cosmos` backend does not support",
+                    e.chain_id)
+            },
+
+        InvalidFeeBudget
+            {
+                chain_id: ChainId,
+                reason: String
+            }
+            |e| {
+                format!("config file specifies an invalid `fee_budget` for the chain with id {0}, caused by: {1}",
+                    e.chain_id, e.reason)
+            },
+
+        InvalidChannelPattern
+            {
+                pattern: String,
+                chain_id: ChainId,
+                reason: String
+            }
+            |e| {
+                format!("config file specifies an invalid port or channel pattern ('{0}') for the chain with id {1}, caused by: {2}",
+                    e.pattern, e.chain_id, e.reason)
+            },
     }
 }
 
@@ -90,10 +148,21 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
             return Err(Diagnostic::Error(Error::duplicate_chains(c.id.clone())));
         }
 
+        validate_chain_type(c)?;
+
         validate_trust_threshold(&c.id, c.trust_threshold)?;
 
         // Validate gas-related settings
         validate_gas_settings(&c.id, c.gas_adjustment)?;
+
+        if let Some(dynamic_gas_price) = &c.dynamic_gas_price {
+            validate_dynamic_gas_price(&c.id, dynamic_gas_price)?;
+        }
+
+        validate_fee_budget(c)?;
+
+        // Validate the wildcard patterns of the channel filters
+        validate_channel_patterns(c)?;
     }
 
     // Check for invalid mode config
@@ -118,6 +187,26 @@ fn validate_mode(mode: &ModeConfig) -> Result<(), Diagnostic<Error>> {
     Ok(())
 }
 
+/// Check that a backend is registered for the type of the chain,
+/// and that a `cosmos` chain has no backend-specific configuration.
+fn validate_chain_type(config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    if !is_chain_backend_registered(&config.chain_type) {
+        return Err(Diagnostic::Error(Error::unknown_chain_type(
+            config.chain_type.clone(),
+            config.id.clone(),
+            registered_chain_types().join(", "),
+        )));
+    }
+
+    if config.chain_type == COSMOS_SDK_CHAIN_TYPE && !config.backend.is_empty() {
+        return Err(Diagnostic::Error(Error::unexpected_backend_config(
+            config.id.clone(),
+        )));
+    }
+
+    Ok(())
+}
+
 /// Check that the trust threshold is:
 ///
 /// a) non-zero
@@ -169,3 +258,118 @@ fn validate_gas_settings(
         _ => Ok(()),
     }
 }
+
+/// Check that the multiplier of the dynamic gas price is at least 1, that its
+/// maximum is positive, and that its maximum age is at least its refresh interval.
+fn validate_dynamic_gas_price(
+    id: &ChainId,
+    dynamic_gas_price: &DynamicGasPrice,
+) -> Result<(), Diagnostic<Error>> {
+    if !(1.0..).contains(&dynamic_gas_price.multiplier) {
+        return Err(Diagnostic::Error(Error::invalid_dynamic_gas_price(
+            id.clone(),
+            "multiplier must be greater than or equal to 1.0".to_string(),
+        )));
+    }
+
+    if dynamic_gas_price.max.is_nan() || dynamic_gas_price.max <= 0.0 {
+        return Err(Diagnostic::Error(Error::invalid_dynamic_gas_price(
+            id.clone(),
+            "max must be greater than 0.0".to_string(),
+        )));
+    }
+
+    if dynamic_gas_price.max_age < dynamic_gas_price.refresh_interval {
+        return Err(Diagnostic::Error(Error::invalid_dynamic_gas_price(
+            id.clone(),
+            "max_age must be greater than or equal to refresh_interval".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that the fee budget of a chain, if any, specifies a maximum for the denominations
+/// of the gas prices of all its fee policies, as no fee may be paid in other denominations.
+fn validate_fee_budget(chain_config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let fee_budget = match &chain_config.fee_budget {
+        Some(fee_budget) => fee_budget,
+        None => return Ok(()),
+    };
+
+    let unbudgeted = chain_config.fee_policies.iter().find(|policy| {
+        let denom = &policy.gas_price.denom;
+        denom != &chain_config.gas_price.denom && !fee_budget.max_fees_per_denom.contains_key(denom)
+    });
+
+    match unbudgeted {
+        Some(policy) => Err(Diagnostic::Error(Error::invalid_fee_budget(
+            chain_config.id.clone(),
+            format!(
+                "no maximum is specified in `max_fees_per_denom` for the denomination '{}' of a fee policy",
+                policy.gas_price.denom
+            ),
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Check that the wildcard patterns of all the channel filters of a chain,
+/// once stripped of their wildcards, only contain characters which are valid
+/// in port and channel identifiers, and are not longer than these identifiers.
+fn validate_channel_patterns(chain_config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let packet_filter_spec = match &chain_config.packet_filter {
+        PacketFilter::Allow(spec) | PacketFilter::Deny(spec) => Some(spec),
+        PacketFilter::AllowAll => None,
+    };
+
+    let specs = packet_filter_spec
+        .into_iter()
+        .chain(chain_config.fee_policies.iter().map(|p| &p.channels))
+        .chain(chain_config.packet_rate_limits.iter().map(|l| &l.channels))
+        .chain(chain_config.transfer_filters.iter().map(|f| &f.channels));
+
+    for spec in specs {
+        validate_channels_spec(&chain_config.id, spec)?;
+    }
+
+    Ok(())
+}
+
+fn validate_channels_spec(id: &ChainId, spec: &ChannelsSpec) -> Result<(), Diagnostic<Error>> {
+    for (port, channel) in spec.iter() {
+        if let FilterPattern::Wildcard(wildcard) = port {
+            validate_wildcard(id, wildcard, 128)?;
+        }
+
+        if let FilterPattern::Wildcard(wildcard) = channel {
+            validate_wildcard(id, wildcard, 64)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_wildcard(
+    id: &ChainId,
+    wildcard: &Wildcard,
+    max_len: usize,
+) -> Result<(), Diagnostic<Error>> {
+    let literal: String = wildcard
+        .as_str()
+        .chars()
+        .filter(|c| !matches!(c, '*' | '?'))
+        .collect();
+
+    if literal.is_empty() {
+        return Ok(());
+    }
+
+    validate_identifier(&literal, 1, max_len).map_err(|e| {
+        Diagnostic::Error(Error::invalid_channel_pattern(
+            wildcard.to_string(),
+            id.clone(),
+            e.to_string(),
+        ))
+    })
+}
@@ -3,9 +3,13 @@ use std::process;
 
 use abscissa_core::{Clap, Command, Configurable, FrameworkError, Runnable};
 use clap::IntoApp;
+use ibc_relayer::chain::dry_run;
 use ibc_relayer::config::Config;
+use ibc_relayer::keyring::{self, Store};
 
 use crate::commands::CliCmd;
+use crate::conclude::Output;
+use crate::prelude::app_config;
 
 /// Entry point for Hermes CLI.
 #[derive(Command, Debug, Clap)]
@@ -19,6 +23,14 @@ pub struct EntryPoint {
     #[clap(short = 'j', long, about = "enable JSON output")]
     pub json: bool,
 
+    /// Toggle the dry-run mode, in which transactions are simulated instead of submitted
+    #[clap(
+        long,
+        global = true,
+        about = "simulate and output the transactions instead of submitting them (start and tx commands only)"
+    )]
+    pub dry_run: bool,
+
     /// Subcommand to execute.
     ///
     /// The `command` option will delegate option parsing to the command type,
@@ -30,7 +42,17 @@ pub struct EntryPoint {
 impl Runnable for EntryPoint {
     fn run(&self) {
         match &self.command {
-            Some(cmd) => cmd.run(),
+            Some(cmd) => {
+                if self.dry_run {
+                    enable_dry_run(cmd);
+                }
+
+                if !matches!(cmd, CliCmd::Version(_) | CliCmd::Config(_)) {
+                    read_keyring_passphrase();
+                }
+
+                cmd.run()
+            }
             None => {
                 EntryPoint::into_app().print_help().unwrap();
                 process::exit(0);
@@ -39,6 +61,32 @@ impl Runnable for EntryPoint {
     }
 }
 
+/// Enable the dry-run mode, if supported by the given command, which outputs
+/// each simulated transaction as it would output the result of a command.
+fn enable_dry_run(cmd: &CliCmd) {
+    if !matches!(cmd, CliCmd::Start(_) | CliCmd::Tx(_)) {
+        Output::error("the --dry-run option is only supported by the start and tx commands").exit();
+    }
+
+    dry_run::enable(|tx| Output::success(tx).print());
+}
+
+/// Read the passphrase of the encrypted key stores once, before any chain runtime
+/// opens its key store.
+fn read_keyring_passphrase() {
+    let config = app_config();
+
+    let chain_ids = config
+        .chains
+        .iter()
+        .filter(|chain| chain.key_store_type == Store::File && chain.remote_signer.is_none())
+        .map(|chain| &chain.id);
+
+    if let Err(e) = keyring::read_passphrase(chain_ids) {
+        Output::error(format!("failed to read the key store passphrase: {}", e)).exit();
+    }
+}
+
 impl Configurable<Config> for EntryPoint {
     /// Path to the command's configuration file
     fn config_path(&self) -> Option<PathBuf> {
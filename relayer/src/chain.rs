@@ -44,14 +44,19 @@ use crate::error::Error;
 use crate::event::monitor::TxMonitorCmd;
 use crate::keyring::{KeyEntry, KeyRing};
 use crate::light_client::LightClient;
-use crate::{config::ChainConfig, event::monitor::EventReceiver};
+use crate::{
+    config::{ChainConfig, FeePolicy},
+    event::monitor::EventReceiver,
+};
 
+pub mod backend;
 pub mod cosmos;
 pub mod counterparty;
+pub mod dry_run;
 pub mod handle;
 pub mod runtime;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 pub mod mock;
 
 /// The result of a health check.
@@ -68,6 +73,15 @@ pub struct StatusResponse {
     pub timestamp: Timestamp,
 }
 
+/// The responses of the transactions broadcast for some messages, which are not committed
+/// yet, along with an [`IbcEvent::ChainError`] for each of the messages dropped from their
+/// transaction because they failed its simulation.
+#[derive(Clone, Debug, Default)]
+pub struct CheckTxResponses {
+    pub responses: Vec<TxResponse>,
+    pub dropped_events: Vec<IbcEvent>,
+}
+
 /// Generic query response type
 /// TODO - will slowly move to GRPC protobuf specs for queries
 #[derive(Clone, Debug, PartialEq)]
@@ -101,9 +115,12 @@ pub trait ChainEndpoint: Sized {
     fn init_light_client(&self) -> Result<Self::LightClient, Error>;
 
     /// Initializes and returns the event monitor (if any) associated with this chain.
+    /// If a height is given, the monitor first collects the events of the blocks
+    /// committed after that height.
     fn init_event_monitor(
         &self,
         rt: Arc<TokioRuntime>,
+        resume_from: Option<ICSHeight>,
     ) -> Result<(EventReceiver, TxMonitorCmd), Error>;
 
     /// Returns the chain's identifier
@@ -123,9 +140,12 @@ pub trait ChainEndpoint: Sized {
 
     /// Sends one or more transactions with `msgs` to chain and
     // synchronously wait for it to be committed.
+    /// The fees of the transactions are paid according to the given fee policy,
+    /// or according to the chain's configuration if none is given.
     fn send_messages_and_wait_commit(
         &mut self,
         proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
     ) -> Result<Vec<IbcEvent>, Error>;
 
     /// Sends one or more transactions with `msgs` to chain.
@@ -133,7 +153,47 @@ pub trait ChainEndpoint: Sized {
     fn send_messages_and_wait_check_tx(
         &mut self,
         proto_msgs: Vec<Any>,
-    ) -> Result<Vec<TxResponse>, Error>;
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<CheckTxResponses, Error>;
+
+    /// Sends the messages of each submission as `send_messages_and_wait_check_tx` does,
+    /// returning the result of each submission in the same order.
+    /// Chains signing with several keys may broadcast the transactions of submissions
+    /// signed with different keys concurrently.
+    fn send_messages_concurrently_and_wait_check_tx(
+        &mut self,
+        submissions: Vec<(Vec<Any>, Option<FeePolicy>)>,
+    ) -> Vec<Result<CheckTxResponses, Error>> {
+        submissions
+            .into_iter()
+            .map(|(proto_msgs, fee_policy)| {
+                self.send_messages_and_wait_check_tx(proto_msgs, fee_policy.as_ref())
+            })
+            .collect()
+    }
+
+    /// Sends a transaction carrying each of the given batches of messages as is, all of
+    /// them signed with the same key, returning the result of each transaction in the same
+    /// order. Fails without sending any transaction if the messages cannot be signed.
+    fn send_transactions_and_wait_check_tx(
+        &mut self,
+        txs: Vec<Vec<Any>>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<Vec<Result<CheckTxResponses, Error>>, Error> {
+        Ok(txs
+            .into_iter()
+            .map(|proto_msgs| self.send_messages_and_wait_check_tx(proto_msgs, fee_policy))
+            .collect())
+    }
+
+    /// Builds and simulates the transactions which `send_messages_and_wait_commit` would
+    /// send with `msgs`, without broadcasting them, and returns the events they would emit.
+    /// Used in place of `send_messages_and_wait_commit` when the [`dry_run`] mode is enabled.
+    fn simulate_messages(
+        &mut self,
+        proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<Vec<IbcEvent>, Error>;
 
     fn get_signer(&mut self) -> Result<Signer, Error>;
 
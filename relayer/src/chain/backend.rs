@@ -0,0 +1,201 @@
+//! Registry of the chain backends, ie. the [`ChainEndpoint`] implementations
+//! chain runtimes can be spawned with, selected by the `type` of the
+//! configuration of each chain.
+//!
+//! The [`CosmosSdkChain`] backend is registered under the `cosmos` type, which
+//! is the default type of a chain. Crates implementing [`ChainEndpoint`] for
+//! other kinds of chains can register their backend under another type with
+//! [`register_chain_backend`], before spawning any chain runtime, eg.:
+//!
+//! ```ignore
+//! ibc_relayer::chain::backend::register_chain_backend::<SubstrateChain>("substrate");
+//! ```
+//!
+//! The configuration specific to such a backend is given in the `[chains.backend]`
+//! table of each chain, which the backend deserializes with
+//! [`BackendConfig::parse`](crate::config::BackendConfig::parse).
+//!
+//! With the `mock` feature enabled, the `MockChain` backend is also registered,
+//! under the `mock` type.
+
+use alloc::collections::BTreeMap as HashMap;
+use alloc::sync::Arc;
+use std::sync::RwLock;
+
+use crossbeam_channel as channel;
+use once_cell::sync::Lazy;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::chain::handle::{ChainHandle, ChainRequest, ProdChainHandle};
+use crate::chain::runtime::ChainRuntime;
+use crate::chain::{ChainEndpoint, CosmosSdkChain};
+use crate::config::ChainConfig;
+use crate::error::Error;
+
+/// The type of the chains relayed with the [`CosmosSdkChain`] backend
+pub const COSMOS_SDK_CHAIN_TYPE: &str = "cosmos";
+
+/// The type of the chains relayed with the `MockChain` backend
+pub const MOCK_CHAIN_TYPE: &str = "mock";
+
+/// Spawns a chain runtime, and returns the identifier of its chain along
+/// with the sender of the requests to the runtime
+type SpawnRuntime =
+    fn(ChainConfig, Arc<TokioRuntime>) -> Result<(ChainId, channel::Sender<ChainRequest>), Error>;
+
+/// A registry of chain backends, by type of chain.
+///
+/// The process-wide registry used to spawn the chain runtimes is managed with the
+/// functions of this module, while separate instances of the registry can be used
+/// where the registered backends must not be shared, eg. in tests.
+#[derive(Clone, Debug)]
+pub struct ChainBackends {
+    backends: HashMap<String, SpawnRuntime>,
+}
+
+impl Default for ChainBackends {
+    /// A registry of the built-in backends
+    fn default() -> Self {
+        let mut backends = Self {
+            backends: HashMap::new(),
+        };
+
+        backends.register::<CosmosSdkChain>(COSMOS_SDK_CHAIN_TYPE);
+
+        #[cfg(any(test, feature = "mock"))]
+        backends.register::<crate::chain::mock::MockChain>(MOCK_CHAIN_TYPE);
+
+        backends
+    }
+}
+
+impl ChainBackends {
+    /// Register the given [`ChainEndpoint`] implementation as the backend of the chains
+    /// of the given type, replacing the backend previously registered for that type, if any.
+    pub fn register<Chain>(&mut self, chain_type: &str)
+    where
+        Chain: ChainEndpoint + Send + 'static,
+    {
+        self.backends
+            .insert(chain_type.to_string(), spawn_runtime::<Chain>);
+    }
+
+    /// Returns true if a backend is registered for the chains of the given type.
+    pub fn is_registered(&self, chain_type: &str) -> bool {
+        self.backends.contains_key(chain_type)
+    }
+
+    /// The types of chains for which a backend is registered.
+    pub fn chain_types(&self) -> Vec<String> {
+        self.backends.keys().cloned().collect()
+    }
+
+    /// Spawns a chain runtime with the backend registered for the type of the given chain,
+    /// and returns a handle to the runtime.
+    pub fn spawn<Handle: ChainHandle>(
+        &self,
+        config: ChainConfig,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<Handle, Error> {
+        let spawn = self.backends.get(&config.chain_type).ok_or_else(|| {
+            Error::unknown_chain_type(config.id.clone(), config.chain_type.clone())
+        })?;
+
+        let (chain_id, sender) = spawn(config, rt)?;
+
+        Ok(Handle::new(chain_id, sender))
+    }
+}
+
+static CHAIN_BACKENDS: Lazy<RwLock<ChainBackends>> =
+    Lazy::new(|| RwLock::new(ChainBackends::default()));
+
+/// Register the given [`ChainEndpoint`] implementation as the backend of the chains
+/// of the given type, replacing the backend previously registered for that type, if any.
+pub fn register_chain_backend<Chain>(chain_type: &str)
+where
+    Chain: ChainEndpoint + Send + 'static,
+{
+    CHAIN_BACKENDS
+        .write()
+        .expect("poisoned lock")
+        .register::<Chain>(chain_type);
+}
+
+/// Returns true if a backend is registered for the chains of the given type.
+pub fn is_chain_backend_registered(chain_type: &str) -> bool {
+    CHAIN_BACKENDS
+        .read()
+        .expect("poisoned lock")
+        .is_registered(chain_type)
+}
+
+/// The types of chains for which a backend is registered.
+pub fn registered_chain_types() -> Vec<String> {
+    CHAIN_BACKENDS.read().expect("poisoned lock").chain_types()
+}
+
+/// Spawns a chain runtime with the backend registered for the type of the given chain,
+/// and returns a handle to the runtime.
+pub fn spawn_chain_backend<Handle: ChainHandle>(
+    config: ChainConfig,
+    rt: Arc<TokioRuntime>,
+) -> Result<Handle, Error> {
+    // Release the lock before spawning, in case the backend registers other backends
+    let backends = CHAIN_BACKENDS.read().expect("poisoned lock").clone();
+
+    backends.spawn(config, rt)
+}
+
+fn spawn_runtime<Chain>(
+    config: ChainConfig,
+    rt: Arc<TokioRuntime>,
+) -> Result<(ChainId, channel::Sender<ChainRequest>), Error>
+where
+    Chain: ChainEndpoint + Send + 'static,
+{
+    let handle: ProdChainHandle = ChainRuntime::<Chain>::spawn(config, rt)?;
+
+    Ok((handle.id(), handle.sender().clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::chain::mock::MockChain;
+
+    #[test]
+    fn register_chain_backends() {
+        let mut backends = ChainBackends::default();
+
+        assert!(backends.is_registered(COSMOS_SDK_CHAIN_TYPE));
+        assert!(backends.is_registered(MOCK_CHAIN_TYPE));
+        assert!(!backends.is_registered("test-backend"));
+
+        backends.register::<MockChain>("test-backend");
+
+        assert!(backends.is_registered("test-backend"));
+        assert!(backends.chain_types().contains(&"test-backend".to_string()));
+
+        // The registries do not share their backends
+        assert!(!ChainBackends::default().is_registered("test-backend"));
+        assert!(!is_chain_backend_registered("test-backend"));
+    }
+
+    #[test]
+    fn spawn_unknown_chain_type() {
+        let backends = ChainBackends::default();
+
+        let mut config = get_basic_chain_config("chain-a");
+        config.chain_type = "unknown".to_string();
+
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let result = backends.spawn::<ProdChainHandle>(config, rt);
+
+        assert!(result.is_err());
+    }
+}
@@ -8,14 +8,14 @@ use core::{
 };
 use num_bigint::BigInt;
 use num_rational::BigRational;
-use std::{fmt, thread, time::Instant};
+use std::{collections::HashMap, fmt, thread, time::Instant};
 
 use bech32::{ToBase32, Variant};
 use bitcoin::hashes::hex::ToHex;
 use itertools::Itertools;
 use prost::Message;
 use prost_types::Any;
-use tendermint::abci::{Event, Path as TendermintABCIPath};
+use tendermint::abci::{tag::Tag, Event, Path as TendermintABCIPath};
 use tendermint::account::Id as AccountId;
 use tendermint::block::Height;
 use tendermint::consensus::Params;
@@ -40,6 +40,7 @@ use ibc::core::ics02_client::client_consensus::{
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics02_client::client_type::ClientType;
 use ibc::core::ics02_client::events as ClientEvents;
+use ibc::core::ics02_client::msgs::update_client;
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd};
 use ibc::core::ics04_channel;
 use ibc::core::ics04_channel::channel::{
@@ -57,7 +58,10 @@ use ibc::events::{from_tx_response_event, IbcEvent};
 use ibc::query::{QueryTxHash, QueryTxRequest};
 use ibc::signer::Signer;
 use ibc::Height as ICSHeight;
-use ibc::{downcast, query::QueryBlockRequest};
+use ibc::{
+    downcast,
+    query::{QueryBlockRangeRequest, QueryBlockRequest},
+};
 use ibc_proto::cosmos::auth::v1beta1::{BaseAccount, EthAccount, QueryAccountRequest};
 use ibc_proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient;
 use ibc_proto::cosmos::base::tendermint::v1beta1::GetNodeInfoRequest;
@@ -81,23 +85,40 @@ use ibc_proto::ibc::core::connection::v1::{
 use ibc_proto::ibc::core::port::v1::QueryAppVersionRequest;
 
 use crate::event::monitor::{EventMonitor, EventReceiver};
-use crate::keyring::{KeyEntry, KeyRing};
+use crate::event::poller::EventPoller;
+use crate::keyring::{
+    address_from_public_key, KeyEntry, KeyRing, LocalSigner, RemoteSigner, Store, TxSigner,
+};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::LightClient;
 use crate::light_client::Verified;
+use crate::telemetry;
 use crate::{
-    chain::handle::requests::AppVersion, chain::QueryResponse, chain::StatusResponse,
+    chain::dry_run::{self, DryRunTx},
+    chain::handle::requests::AppVersion,
+    chain::QueryResponse,
+    chain::{CheckTxResponses, StatusResponse},
     event::monitor::TxMonitorCmd,
 };
 use crate::{config::types::Memo, error::Error};
 use crate::{
-    config::{AddressType, ChainConfig, GasPrice},
-    sdk_error::sdk_error_from_tx_sync_error_code,
+    config::{AddressType, ChainConfig, DynamicGasPrice, EventSource, FeePolicy, GasPrice},
+    sdk_error::{is_incorrect_account_sequence_code, sdk_error_from_tx_sync_error_code},
 };
 
 use super::{ChainEndpoint, HealthCheck};
+use endpoints::{EndpointPool, RequestOutcome};
+use fee_budget::FeeBudgetTracker;
+use gas_estimate::ResendGasEstimates;
+use gas_price::GasPriceCache;
+use key_pool::{with_signer, KeyPool};
 
 mod compatibility;
+pub mod endpoints;
+mod fee_budget;
+mod gas_estimate;
+mod gas_price;
+pub mod key_pool;
 pub mod version;
 
 /// Default gas limit when submitting a transaction.
@@ -113,7 +134,7 @@ const DEFAULT_FEE_GRANTER: &str = "";
 pub const GENESIS_MAX_BYTES_MAX_FRACTION: f64 = 0.9;
 
 mod retry_strategy {
-    use crate::util::retry::Fixed;
+    use crate::util::retry::{ConstantGrowth, Fixed};
     use core::time::Duration;
 
     pub fn wait_for_block_commits(max_total_wait: Duration) -> impl Iterator<Item = Duration> {
@@ -121,16 +142,37 @@ mod retry_strategy {
         let count: usize = (max_total_wait.as_millis() / backoff_millis as u128) as usize;
         Fixed::from_millis(backoff_millis).take(count)
     }
+
+    /// Delays before retrying a transaction which failed with an account sequence mismatch,
+    /// leaving time for the transactions already in the mempool to be committed.
+    pub fn account_sequence_mismatch() -> impl Iterator<Item = Duration> {
+        const MAX_RETRIES: usize = 3;
+        const MAX_DELAY: Duration = Duration::from_secs(2);
+
+        ConstantGrowth::new(Duration::from_millis(300), Duration::from_millis(300))
+            .clamp(MAX_DELAY, MAX_RETRIES)
+    }
 }
 
 pub struct CosmosSdkChain {
     config: ChainConfig,
-    rpc_client: HttpClient,
-    grpc_addr: Uri,
+    /// The endpoints of the chain, the active one of which is used for all requests
+    endpoints: EndpointPool,
     rt: Arc<TokioRuntime>,
     keybase: KeyRing,
-    /// A cached copy of the account information
-    account: Option<BaseAccount>,
+    /// The keys used in turn to sign transactions
+    key_pool: KeyPool,
+    /// Remote signers holding the keys of the pool in place of the key store, if configured
+    remote_signers: HashMap<String, RemoteSigner>,
+    /// A cached copy of the account information of each key of the pool
+    accounts: HashMap<String, BaseAccount>,
+    /// The last gas price obtained from the chain, if a dynamic gas price is configured
+    dynamic_gas_price: GasPriceCache,
+    /// The gas estimates of recently simulated batches, reused when the same messages
+    /// are sent again, if enabled
+    resend_gas_estimates: Option<ResendGasEstimates>,
+    /// The fees spent within the window of the fee budget, if one is configured
+    fee_budget: Option<FeeBudgetTracker>,
 }
 
 impl CosmosSdkChain {
@@ -185,11 +227,11 @@ impl CosmosSdkChain {
 
         // Check on the configured max_tx_size against the consensus parameters at latest height
         let result = self
-            .block_on(self.rpc_client.consensus_params(latest_height))
+            .request(self.rpc_client().consensus_params(latest_height))
             .map_err(|e| {
                 Error::config_validation_json_rpc(
                     self.id().clone(),
-                    self.config.rpc_addr.to_string(),
+                    self.rpc_addr().to_string(),
                     "/consensus_params".to_string(),
                     e,
                 )
@@ -233,9 +275,9 @@ impl CosmosSdkChain {
         crate::time!("unbonding_period");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -244,7 +286,7 @@ impl CosmosSdkChain {
             tonic::Request::new(ibc_proto::cosmos::staking::v1beta1::QueryParamsRequest {});
 
         let response = self
-            .block_on(client.params(request))
+            .request(client.params(request))
             .map_err(Error::grpc_status)?;
 
         let res = response
@@ -258,7 +300,15 @@ impl CosmosSdkChain {
     }
 
     fn rpc_client(&self) -> &HttpClient {
-        &self.rpc_client
+        &self.endpoints.active().rpc_client
+    }
+
+    fn rpc_addr(&self) -> &Url {
+        &self.endpoints.active().config.rpc_addr
+    }
+
+    fn grpc_addr(&self) -> &Uri {
+        &self.endpoints.active().grpc_addr
     }
 
     pub fn config(&self) -> &ChainConfig {
@@ -271,8 +321,8 @@ impl CosmosSdkChain {
         crate::time!("query_consensus_params");
 
         Ok(self
-            .block_on(self.rpc_client().genesis())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?
+            .request(self.rpc_client().genesis())
+            .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?
             .consensus_params)
     }
 
@@ -282,39 +332,141 @@ impl CosmosSdkChain {
         self.rt.block_on(f)
     }
 
-    fn send_tx(&mut self, proto_msgs: Vec<Any>) -> Result<Response, Error> {
+    /// Run a request to the active endpoint to completion on the Tokio runtime,
+    /// recording a failure of the endpoint if it could not serve the request.
+    fn request<F>(&self, f: F) -> F::Output
+    where
+        F: Future,
+        F::Output: RequestOutcome,
+    {
+        let outcome = self.block_on(f);
+        self.endpoints.record_outcome(&outcome);
+        outcome
+    }
+
+    /// Select the key to sign the given messages with, and set the signer
+    /// of the messages to the account of that key if it is not the primary key.
+    fn dispatch_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<(String, Vec<Any>), Error> {
+        let key_name = self.key_pool.next_key(&proto_msgs).to_string();
+
+        if key_name == self.key_pool.primary() {
+            return Ok((key_name, proto_msgs));
+        }
+
+        let signer = self.signer_account(&key_name)?;
+        let proto_msgs = proto_msgs
+            .into_iter()
+            .map(|msg| with_signer(msg, &signer))
+            .collect::<Result<_, _>>()?;
+
+        Ok((key_name, proto_msgs))
+    }
+
+    fn send_tx(
+        &mut self,
+        key_name: &str,
+        proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<Response, Error> {
         crate::time!("send_tx");
 
-        let account_seq = self.account_sequence()?;
+        let tx = self.sign_tx(key_name, proto_msgs, fee_policy)?;
 
-        debug!(
-            "[{}] send_tx: sending {} messages using account sequence {}",
+        let response = self.request(broadcast_tx_sync(
+            self.rpc_client(),
+            self.rpc_addr(),
+            tx.bytes,
+        ))?;
+
+        match response.code {
+            tendermint::abci::Code::Ok => {
+                if let Some(fee_budget) = self.fee_budget.as_mut() {
+                    fee_budget.record(&tx.fee);
+                }
+
+                // A success means the account s.n. was increased
+                self.incr_account_sequence(key_name)?;
+                debug!("[{}] send_tx: broadcast_tx_sync: {:?}", self.id(), response);
+            }
+            tendermint::abci::Code::Err(code) => {
+                // Avoid increasing the account s.n. if CheckTx failed
+                self.log_check_tx_error(&response, code);
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn log_check_tx_error(&self, response: &Response, code: u32) {
+        error!(
+            "[{}] send_tx: broadcast_tx_sync: {:?}: diagnostic: {:?}",
             self.id(),
-            proto_msgs.len(),
-            account_seq,
+            response,
+            sdk_error_from_tx_sync_error_code(code)
         );
+    }
 
-        let signer_info = self.signer(account_seq)?;
-        let max_fee = self.max_fee();
+    /// Builds and signs a transaction carrying the given messages with the current
+    /// sequence of the account of the given key, without broadcasting it.
+    fn sign_tx(
+        &mut self,
+        key_name: &str,
+        proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<SignedTx, Error> {
+        self.refresh_gas_price();
+
+        let account_seq = self.account_sequence(key_name)?;
 
         debug!(
-            "[{}] send_tx: max fee, for use in tx simulation: {}",
+            "[{}] send_tx: sending {} messages with key {} using account sequence {}",
             self.id(),
-            PrettyFee(&max_fee)
+            proto_msgs.len(),
+            key_name,
+            account_seq,
         );
 
-        let (body, body_buf) = tx_body_and_bytes(proto_msgs, self.tx_memo())?;
+        let signer_info = self.signer(key_name, account_seq)?;
 
-        let (auth_info, auth_buf) = auth_info_and_bytes(signer_info.clone(), max_fee)?;
-        let signed_doc = self.signed_doc(body_buf.clone(), auth_buf, account_seq)?;
+        let resend_gas = self
+            .resend_gas_estimates
+            .as_ref()
+            .and_then(|estimates| estimates.get(&proto_msgs));
 
-        let simulate_tx = Tx {
-            body: Some(body),
-            auth_info: Some(auth_info),
-            signatures: vec![signed_doc],
-        };
+        let (body, body_buf) = tx_body_and_bytes(proto_msgs.clone(), self.tx_memo())?;
 
-        let estimated_gas = self.estimate_gas(simulate_tx)?;
+        let estimated_gas = match resend_gas {
+            Some(gas) => {
+                debug!(
+                    "[{}] send_tx: reusing gas estimate {} of the same messages sent recently, skipping tx simulation",
+                    self.id(),
+                    gas
+                );
+
+                gas
+            }
+            None => {
+                let max_fee = self.max_fee(fee_policy);
+
+                debug!(
+                    "[{}] send_tx: max fee, for use in tx simulation: {}",
+                    self.id(),
+                    PrettyFee(&max_fee)
+                );
+
+                let (auth_info, auth_buf) = auth_info_and_bytes(signer_info.clone(), max_fee)?;
+                let signed_doc =
+                    self.signed_doc(key_name, body_buf.clone(), auth_buf, account_seq)?;
+
+                let simulate_tx = Tx {
+                    body: Some(body),
+                    auth_info: Some(auth_info),
+                    signatures: vec![signed_doc],
+                };
+
+                self.estimate_gas(simulate_tx)?
+            }
+        };
 
         if estimated_gas > self.max_gas() {
             debug!(estimated = ?estimated_gas, max = ?self.max_gas(), "[{}] send_tx: estimated gas is higher than max gas", self.id());
@@ -326,7 +478,7 @@ impl CosmosSdkChain {
             ));
         }
 
-        let adjusted_fee = self.fee_with_gas(estimated_gas);
+        let adjusted_fee = self.fee_with_gas(estimated_gas, fee_policy);
 
         debug!(
             "[{}] send_tx: using {} gas, fee {}",
@@ -335,10 +487,17 @@ impl CosmosSdkChain {
             PrettyFee(&adjusted_fee)
         );
 
+        self.check_fee_budget(&adjusted_fee)?;
+        let fee = adjusted_fee.amount.clone();
+
         let (_auth_adjusted, auth_buf_adjusted) = auth_info_and_bytes(signer_info, adjusted_fee)?;
-        let account_number = self.account_number()?;
-        let signed_doc =
-            self.signed_doc(body_buf.clone(), auth_buf_adjusted.clone(), account_number)?;
+        let account_number = self.account_number(key_name)?;
+        let signed_doc = self.signed_doc(
+            key_name,
+            body_buf.clone(),
+            auth_buf_adjusted.clone(),
+            account_number,
+        )?;
 
         let tx_raw = TxRaw {
             body_bytes: body_buf,
@@ -350,31 +509,204 @@ impl CosmosSdkChain {
         prost::Message::encode(&tx_raw, &mut tx_bytes)
             .map_err(|e| Error::protobuf_encode(String::from("Transaction"), e))?;
 
-        let response = self.block_on(broadcast_tx_sync(
-            self.rpc_client(),
-            &self.config.rpc_addr,
-            tx_bytes,
-        ))?;
+        Ok(SignedTx {
+            proto_msgs,
+            bytes: tx_bytes,
+            fee,
+            dropped_events: vec![],
+        })
+    }
 
-        match response.code {
-            tendermint::abci::Code::Ok => {
-                // A success means the account s.n. was increased
-                self.incr_account_sequence()?;
-                debug!("[{}] send_tx: broadcast_tx_sync: {:?}", self.id(), response);
+    /// Signs the transactions carrying all the given messages with a single key of the pool,
+    /// using consecutive sequences of its account, without broadcasting them.
+    /// Returns the name of the key along with the signed transactions, whose fees are
+    /// reserved against the fee budget until they are broadcast.
+    fn sign_messages(
+        &mut self,
+        proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<(String, Vec<SignedTx>), Error> {
+        let (key_name, proto_msgs) = self.dispatch_msgs(proto_msgs)?;
+        let sequence = self.account_sequence(&key_name)?;
+
+        let mut txs: Vec<SignedTx> = vec![];
+        for msg_batch in self.batch_messages(proto_msgs)? {
+            let signed = self
+                .sign_batch(&key_name, msg_batch, fee_policy)
+                .and_then(|tx| {
+                    self.incr_account_sequence(&key_name)?;
+                    Ok(tx)
+                });
+
+            match signed {
+                Ok(tx) => {
+                    // The fee is only spent once the tx is accepted, but it is reserved
+                    // so that the txs signed next are checked against what remains
+                    if let Some(fee_budget) = self.fee_budget.as_mut() {
+                        fee_budget.reserve(&tx.fee);
+                    }
+
+                    txs.push(tx);
+                }
+                Err(e) => {
+                    // None of the txs are broadcast, their sequences and fees can be used again
+                    self.account(&key_name)?.sequence = sequence;
+
+                    if let Some(fee_budget) = self.fee_budget.as_mut() {
+                        for tx in &txs {
+                            fee_budget.release(&tx.fee);
+                        }
+                    }
+
+                    return Err(e);
+                }
             }
-            tendermint::abci::Code::Err(code) => {
-                // Avoid increasing the account s.n. if CheckTx failed
-                // Log the error
-                error!(
-                    "[{}] send_tx: broadcast_tx_sync: {:?}: diagnostic: {:?}",
-                    self.id(),
-                    response,
-                    sdk_error_from_tx_sync_error_code(code)
-                );
+        }
+
+        Ok((key_name, txs))
+    }
+
+    /// Sends a transaction carrying the given messages, and retries it if it is rejected
+    /// because of an account sequence mismatch, e.g. because another process signed a
+    /// transaction with the same key, or because a transaction was evicted from the mempool.
+    /// Before each retry, the account is queried again to obtain its current sequence.
+    fn send_tx_with_account_sequence_retry(
+        &mut self,
+        key_name: &str,
+        proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<Response, Error> {
+        let mut delays = retry_strategy::account_sequence_mismatch();
+
+        loop {
+            let result = self.send_tx(key_name, proto_msgs.clone(), fee_policy);
+
+            let mismatch = match &result {
+                Ok(response) => match response.code {
+                    tendermint::abci::Code::Err(code) => is_incorrect_account_sequence_code(code),
+                    tendermint::abci::Code::Ok => false,
+                },
+                Err(e) => is_account_sequence_mismatch(e),
+            };
+
+            if !mismatch {
+                return result;
+            }
+
+            match delays.next() {
+                Some(delay) => {
+                    warn!(
+                        "[{}] send_tx: account sequence mismatch for key {}, retrying in {:?} with the sequence of the account on chain",
+                        self.id(),
+                        key_name,
+                        delay
+                    );
+
+                    thread::sleep(delay);
+                    self.refresh_account(key_name)?;
+                }
+                None => {
+                    error!(
+                        "[{}] send_tx: account sequence mismatch for key {}, giving up after retrying",
+                        self.id(),
+                        key_name,
+                    );
+
+                    // Query the account again before the next transaction
+                    self.accounts.remove(key_name);
+
+                    return result;
+                }
             }
         }
+    }
 
-        Ok(response)
+    /// Sends a transaction carrying the given messages.
+    ///
+    /// If the simulation of the transaction fails while executing one of the messages,
+    /// that message is dropped from the batch and the remaining messages are sent again.
+    /// Client updates are never dropped, since the messages following them depend on them.
+    ///
+    /// Returns the response of the transaction along with the number of messages it carries,
+    /// and with a [`IbcEvent::ChainError`] for each message dropped from the batch.
+    fn send_batch(
+        &mut self,
+        key_name: &str,
+        mut proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<(Response, usize, Vec<IbcEvent>), Error> {
+        let mut dropped = vec![];
+
+        loop {
+            let result =
+                self.send_tx_with_account_sequence_retry(key_name, proto_msgs.clone(), fee_policy);
+
+            match result {
+                Ok(response) => return Ok((response, proto_msgs.len(), dropped)),
+                Err(e) => match self.drop_failed_message(&mut proto_msgs, &e) {
+                    Some(event) => dropped.push(event),
+                    None => return Err(e),
+                },
+            }
+        }
+    }
+
+    /// Signs a transaction carrying the given messages without broadcasting it,
+    /// dropping the messages which fail the tx simulation as `send_batch` does.
+    /// The signed tx carries a [`IbcEvent::ChainError`] for each message dropped.
+    fn sign_batch(
+        &mut self,
+        key_name: &str,
+        mut proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<SignedTx, Error> {
+        let mut dropped = vec![];
+
+        loop {
+            match self.sign_tx(key_name, proto_msgs.clone(), fee_policy) {
+                Ok(tx) => {
+                    return Ok(SignedTx {
+                        dropped_events: dropped,
+                        ..tx
+                    })
+                }
+                Err(e) => match self.drop_failed_message(&mut proto_msgs, &e) {
+                    Some(event) => dropped.push(event),
+                    None => return Err(e),
+                },
+            }
+        }
+    }
+
+    /// Removes from the batch the message whose execution failed the tx simulation with
+    /// the given error, unless it is the only message of the batch or a client update.
+    /// Returns an error event describing the message removed, if any.
+    fn drop_failed_message(&self, proto_msgs: &mut Vec<Any>, e: &Error) -> Option<IbcEvent> {
+        let index = match failed_message_index(e) {
+            Some(index) if index < proto_msgs.len() && proto_msgs.len() > 1 => index,
+            _ => return None,
+        };
+
+        if proto_msgs[index].type_url == update_client::TYPE_URL {
+            return None;
+        }
+
+        let dropped = proto_msgs.remove(index);
+
+        warn!(
+            "[{}] send_batch: dropping message {} of type {} from the batch, which failed the tx simulation: {}",
+            self.id(),
+            index,
+            dropped.type_url,
+            e.detail()
+        );
+
+        Some(IbcEvent::ChainError(format!(
+            "simulation on chain {} failed for message of type {}, which was dropped from its tx: {}",
+            self.id(),
+            dropped.type_url,
+            e.detail()
+        )))
     }
 
     /// Try to simulate the given tx in order to estimate how much gas will be needed to submit it.
@@ -385,7 +717,16 @@ impl CosmosSdkChain {
     ///
     /// If the batch is split in two TX-es, the second one will fail the simulation in `deliverTx` check.
     /// In this case we use the `default_gas` param.
-    fn estimate_gas(&self, tx: Tx) -> Result<u64, Error> {
+    ///
+    /// The gas used by a successful simulation is recorded, to be reused if the same messages are
+    /// sent again, if enabled.
+    fn estimate_gas(&mut self, tx: Tx) -> Result<u64, Error> {
+        let msgs = tx
+            .body
+            .as_ref()
+            .map(|body| body.messages.clone())
+            .unwrap_or_default();
+
         let simulated_gas = self.send_tx_simulate(tx).map(|sr| sr.gas_info);
 
         match simulated_gas {
@@ -396,6 +737,10 @@ impl CosmosSdkChain {
                     gas_info.gas_used
                 );
 
+                if let Some(estimates) = self.resend_gas_estimates.as_mut() {
+                    estimates.record(&msgs, gas_info.gas_used);
+                }
+
                 Ok(gas_info.gas_used)
             }
 
@@ -453,9 +798,122 @@ impl CosmosSdkChain {
             .unwrap_or(DEFAULT_FEE_GRANTER)
     }
 
-    /// The gas price
+    /// Fails if paying the given fee would exceed what remains of the fee budget
+    /// for its current window, if a fee budget is configured.
+    fn check_fee_budget(&mut self, fee: &Fee) -> Result<(), Error> {
+        let exceeded = match self
+            .fee_budget
+            .as_mut()
+            .map(|budget| budget.check(&fee.amount))
+        {
+            Some(Err(exceeded)) => exceeded,
+            _ => return Ok(()),
+        };
+
+        warn!(
+            "[{}] send_tx: fee budget exhausted, not submitting a transaction with fee {}{} ({}{} remaining)",
+            self.id(),
+            exceeded.fee,
+            exceeded.denom,
+            exceeded.remaining,
+            exceeded.denom
+        );
+
+        telemetry!(fee_budget_exhausted, self.id());
+
+        Err(Error::fee_budget_exhausted(
+            self.id().clone(),
+            exceeded.denom,
+            exceeded.fee,
+            exceeded.remaining,
+        ))
+    }
+
+    /// The gas price: the last gas price obtained from the chain if a dynamic
+    /// gas price is configured and it is not older than its maximum age,
+    /// otherwise the configured static gas price.
     fn gas_price(&self) -> &GasPrice {
-        &self.config.gas_price
+        self.config
+            .dynamic_gas_price
+            .as_ref()
+            .and_then(|dynamic| {
+                self.dynamic_gas_price
+                    .price(dynamic.max_age, Instant::now())
+            })
+            .unwrap_or(&self.config.gas_price)
+    }
+
+    /// Refresh the dynamic gas price, if one is configured and the last attempt
+    /// at querying it was made more than the refresh interval ago.
+    /// If the query fails, the previous gas price remains in use until it
+    /// reaches its maximum age, and the query is attempted again after the
+    /// refresh interval.
+    fn refresh_gas_price(&mut self) {
+        let dynamic = match &self.config.dynamic_gas_price {
+            Some(dynamic) => dynamic,
+            None => return,
+        };
+
+        if !self
+            .dynamic_gas_price
+            .needs_refresh(dynamic.refresh_interval, Instant::now())
+        {
+            return;
+        }
+
+        match self.query_gas_price(dynamic) {
+            Ok(queried_price) => {
+                let price = GasPrice::new(
+                    dynamic.effective_price(queried_price),
+                    self.config.gas_price.denom.clone(),
+                );
+
+                debug!(
+                    "[{}] refreshed dynamic gas price: queried {}, using {}",
+                    self.id(),
+                    queried_price,
+                    price
+                );
+
+                telemetry!(gas_price, self.id(), &price.denom, price.price);
+
+                self.dynamic_gas_price.record_success(price, Instant::now());
+            }
+            Err(e) => {
+                self.dynamic_gas_price.record_failure(Instant::now());
+
+                warn!(
+                    "[{}] failed to refresh the dynamic gas price, using {}: {}",
+                    self.id(),
+                    self.gas_price(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Query the current gas price of the chain, before applying the multiplier and cap.
+    fn query_gas_price(&self, dynamic: &DynamicGasPrice) -> Result<f64, Error> {
+        crate::time!("query_gas_price");
+
+        let path = TendermintABCIPath::from_str(dynamic.query_path())
+            .map_err(|e| Error::dynamic_gas_price(e.to_string()))?;
+
+        let denom = &self.config.gas_price.denom;
+        let request = gas_price::encode_request(dynamic.query, denom);
+
+        let response = self
+            .request(
+                self.rpc_client()
+                    .abci_query(Some(path), request, None, false),
+            )
+            .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
+
+        if !response.code.is_ok() {
+            return Err(Error::abci_query(response));
+        }
+
+        gas_price::decode_response(dynamic.query, denom, &response.value)
     }
 
     /// The gas price adjustment
@@ -481,13 +939,29 @@ impl CosmosSdkChain {
     }
 
     /// The maximum fee the relayer pays for a transaction
-    fn max_fee_in_coins(&self) -> Coin {
-        calculate_fee(self.max_gas(), self.gas_price())
+    fn max_fee_in_coins(&self, fee_policy: Option<&FeePolicy>) -> Coin {
+        calculate_fee(self.max_gas(), &self.fee_gas_price(fee_policy))
     }
 
     /// The fee in coins based on gas amount
-    fn fee_from_gas_in_coins(&self, gas: u64) -> Coin {
-        calculate_fee(gas, self.gas_price())
+    fn fee_from_gas_in_coins(&self, gas: u64, fee_policy: Option<&FeePolicy>) -> Coin {
+        calculate_fee(gas, &self.fee_gas_price(fee_policy))
+    }
+
+    /// The gas price of the given fee policy if any, combined with that of the chain,
+    /// otherwise the gas price of the chain
+    fn fee_gas_price(&self, fee_policy: Option<&FeePolicy>) -> GasPrice {
+        match fee_policy {
+            Some(policy) => policy.effective_gas_price(self.gas_price()),
+            None => self.gas_price().clone(),
+        }
+    }
+
+    /// The fee granter of the given fee policy if it specifies one, otherwise that of the chain
+    fn fee_policy_granter<'a>(&'a self, fee_policy: Option<&'a FeePolicy>) -> &'a str {
+        fee_policy
+            .and_then(|policy| policy.fee_granter.as_deref())
+            .unwrap_or_else(|| self.fee_granter())
     }
 
     /// The maximum number of messages included in a transaction
@@ -500,6 +974,85 @@ impl CosmosSdkChain {
         self.config.max_tx_size.into()
     }
 
+    /// Splits the given messages in batches, such that each batch does not exceed the
+    /// configured maximum number of messages per transaction and maximum transaction size.
+    fn batch_messages(&self, proto_msgs: Vec<Any>) -> Result<Vec<Vec<Any>>, Error> {
+        let mut batches = vec![];
+
+        let mut size = 0;
+        let mut msg_batch = vec![];
+        for msg in proto_msgs {
+            let mut buf = Vec::new();
+            prost::Message::encode(&msg, &mut buf)
+                .map_err(|e| Error::protobuf_encode(String::from("Message"), e))?;
+            size += buf.len();
+            msg_batch.push(msg);
+            if msg_batch.len() >= self.max_msg_num() || size >= self.max_tx_size() {
+                batches.push(core::mem::take(&mut msg_batch));
+                size = 0;
+            }
+        }
+        if !msg_batch.is_empty() {
+            batches.push(msg_batch);
+        }
+
+        Ok(batches)
+    }
+
+    /// Simulates a transaction carrying the given batch of messages, logs it along with the
+    /// gas it would use, and returns the IBC events it would emit at the given height.
+    fn simulate_batch(
+        &mut self,
+        key_name: &str,
+        proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+        height: ICSHeight,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        let account_seq = self.account_sequence(key_name)?;
+        let signer_info = self.signer(key_name, account_seq)?;
+        let account_number = self.account_number(key_name)?;
+
+        let (body, body_buf) = tx_body_and_bytes(proto_msgs.clone(), self.tx_memo())?;
+        let (auth_info, auth_buf) = auth_info_and_bytes(signer_info, self.max_fee(fee_policy))?;
+        let signed_doc = self.signed_doc(key_name, body_buf, auth_buf, account_number)?;
+
+        let simulate_tx = Tx {
+            body: Some(body),
+            auth_info: Some(auth_info),
+            signatures: vec![signed_doc],
+        };
+
+        let (estimated_gas, events) = match self.send_tx_simulate(simulate_tx) {
+            Ok(response) => {
+                let events = response
+                    .result
+                    .map(|result| result.events)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(abci_event)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .iter()
+                    .filter_map(|event| from_tx_response_event(height, event))
+                    .collect();
+
+                (response.gas_info.map(|gas_info| gas_info.gas_used), events)
+            }
+            Err(e) => {
+                warn!(
+                    "[{}] dry run: tx simulation failed: {}",
+                    self.id(),
+                    e.detail()
+                );
+
+                (None, vec![])
+            }
+        };
+
+        dry_run::report(DryRunTx::new(self.id().clone(), &proto_msgs, estimated_gas));
+
+        Ok(events)
+    }
+
     fn query(&self, data: Path, height: ICSHeight, prove: bool) -> Result<QueryResponse, Error> {
         crate::time!("query");
 
@@ -513,7 +1066,7 @@ impl CosmosSdkChain {
             return Err(Error::private_store());
         }
 
-        let response = self.block_on(abci_query(self, path, data.to_string(), height, prove))?;
+        let response = self.request(abci_query(self, path, data.to_string(), height, prove))?;
 
         // TODO - Verify response proof, if requested.
         if prove {}
@@ -537,7 +1090,7 @@ impl CosmosSdkChain {
         // SAFETY: Creating a Path from a constant; this should never fail
         let path = TendermintABCIPath::from_str(SDK_UPGRADE_QUERY_PATH)
             .expect("Turning SDK upgrade query path constant into a Tendermint ABCI path");
-        let response: QueryResponse = self.block_on(abci_query(
+        let response: QueryResponse = self.request(abci_query(
             self,
             path,
             Path::Upgrade(data).to_string(),
@@ -567,70 +1120,96 @@ impl CosmosSdkChain {
         };
 
         let mut client = self
-            .block_on(ServiceClient::connect(self.grpc_addr.clone()))
+            .request(ServiceClient::connect(self.grpc_addr().clone()))
             .map_err(Error::grpc_transport)?;
 
         let request = tonic::Request::new(req);
         let response = self
-            .block_on(client.simulate(request))
+            .request(client.simulate(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
         Ok(response)
     }
 
-    fn key(&self) -> Result<KeyEntry, Error> {
-        self.keybase()
-            .get_key(&self.config.key_name)
-            .map_err(Error::key_base)
+    /// The signer of the transactions signed with the given key: the remote
+    /// signer if one is configured, otherwise the key store.
+    fn tx_signer<'a>(&'a self, key_name: &'a str) -> Box<dyn TxSigner + 'a> {
+        match self.remote_signers.get(key_name) {
+            Some(remote_signer) => Box::new(remote_signer),
+            None => Box::new(LocalSigner::new(
+                &self.keybase,
+                key_name,
+                &self.config.address_type,
+            )),
+        }
     }
 
-    fn key_bytes(&self, key: &KeyEntry) -> Result<Vec<u8>, Error> {
+    fn key_bytes(&self, key_name: &str) -> Result<Vec<u8>, Error> {
+        let public_key = self
+            .tx_signer(key_name)
+            .public_key()
+            .map_err(Error::key_base)?;
+
         let mut pk_buf = Vec::new();
-        prost::Message::encode(&key.public_key.public_key.to_bytes(), &mut pk_buf)
+        prost::Message::encode(&public_key.to_bytes(), &mut pk_buf)
             .map_err(|e| Error::protobuf_encode(String::from("Key bytes"), e))?;
         Ok(pk_buf)
     }
 
-    fn key_and_bytes(&self) -> Result<(KeyEntry, Vec<u8>), Error> {
-        let key = self.key()?;
-        let key_bytes = self.key_bytes(&key)?;
-        Ok((key, key_bytes))
+    /// The bech32-encoded account of the given key
+    fn signer_account(&self, key_name: &str) -> Result<String, Error> {
+        let public_key = self
+            .tx_signer(key_name)
+            .public_key()
+            .map_err(Error::key_base)?;
+        let address = address_from_public_key(&public_key, &self.config.address_type);
+
+        encode_to_bech32(&address.to_hex(), &self.config.account_prefix)
     }
 
-    fn account(&mut self) -> Result<&mut BaseAccount, Error> {
-        if self.account == None {
-            let account = self.block_on(query_account(self, self.key()?.account))?;
+    fn account(&mut self, key_name: &str) -> Result<&mut BaseAccount, Error> {
+        if !self.accounts.contains_key(key_name) {
+            let account = self.request(query_account(self, self.signer_account(key_name)?))?;
             debug!(
                 sequence = %account.sequence,
                 number = %account.account_number,
-                "[{}] send_tx: retrieved account",
-                self.id()
+                "[{}] send_tx: retrieved account for key {}",
+                self.id(),
+                key_name,
             );
-            self.account = Some(account);
+            self.accounts.insert(key_name.to_string(), account);
         }
 
         Ok(self
-            .account
-            .as_mut()
+            .accounts
+            .get_mut(key_name)
             .expect("account was supposedly just cached"))
     }
 
-    fn account_number(&mut self) -> Result<u64, Error> {
-        Ok(self.account()?.account_number)
+    /// Query the account of the given key again, replacing the cached copy
+    /// whose sequence may have fallen out of sync with the chain.
+    fn refresh_account(&mut self, key_name: &str) -> Result<(), Error> {
+        self.accounts.remove(key_name);
+        self.account(key_name)?;
+        Ok(())
     }
 
-    fn account_sequence(&mut self) -> Result<u64, Error> {
-        Ok(self.account()?.sequence)
+    fn account_number(&mut self, key_name: &str) -> Result<u64, Error> {
+        Ok(self.account(key_name)?.account_number)
     }
 
-    fn incr_account_sequence(&mut self) -> Result<(), Error> {
-        self.account()?.sequence += 1;
+    fn account_sequence(&mut self, key_name: &str) -> Result<u64, Error> {
+        Ok(self.account(key_name)?.sequence)
+    }
+
+    fn incr_account_sequence(&mut self, key_name: &str) -> Result<(), Error> {
+        self.account(key_name)?.sequence += 1;
         Ok(())
     }
 
-    fn signer(&self, sequence: u64) -> Result<SignerInfo, Error> {
-        let (_key, pk_buf) = self.key_and_bytes()?;
+    fn signer(&self, key_name: &str, sequence: u64) -> Result<SignerInfo, Error> {
+        let pk_buf = self.key_bytes(key_name)?;
         let pk_type = match &self.config.address_type {
             AddressType::Cosmos => "/cosmos.crypto.secp256k1.PubKey".to_string(),
             AddressType::Ethermint { pk_type } => pk_type.clone(),
@@ -652,28 +1231,29 @@ impl CosmosSdkChain {
         Ok(signer_info)
     }
 
-    fn max_fee(&self) -> Fee {
+    fn max_fee(&self, fee_policy: Option<&FeePolicy>) -> Fee {
         Fee {
-            amount: vec![self.max_fee_in_coins()],
+            amount: vec![self.max_fee_in_coins(fee_policy)],
             gas_limit: self.max_gas(),
             payer: "".to_string(),
-            granter: self.fee_granter().to_string(),
+            granter: self.fee_policy_granter(fee_policy).to_string(),
         }
     }
 
-    fn fee_with_gas(&self, gas_limit: u64) -> Fee {
+    fn fee_with_gas(&self, gas_limit: u64, fee_policy: Option<&FeePolicy>) -> Fee {
         let adjusted_gas_limit = self.apply_adjustment_to_gas(gas_limit);
 
         Fee {
-            amount: vec![self.fee_from_gas_in_coins(adjusted_gas_limit)],
+            amount: vec![self.fee_from_gas_in_coins(adjusted_gas_limit, fee_policy)],
             gas_limit: adjusted_gas_limit,
             payer: "".to_string(),
-            granter: self.fee_granter().to_string(),
+            granter: self.fee_policy_granter(fee_policy).to_string(),
         }
     }
 
     fn signed_doc(
         &self,
+        key_name: &str,
         body_bytes: Vec<u8>,
         auth_info_bytes: Vec<u8>,
         account_number: u64,
@@ -692,12 +1272,8 @@ impl CosmosSdkChain {
 
         // Sign doc
         let signed = self
-            .keybase
-            .sign_msg(
-                &self.config.key_name,
-                signdoc_buf,
-                &self.config.address_type,
-            )
+            .tx_signer(key_name)
+            .sign(&signdoc_buf)
             .map_err(Error::key_base)?;
 
         Ok(signed)
@@ -794,12 +1370,14 @@ impl CosmosSdkChain {
     /// Query the chain status via an RPC query
     fn status(&self) -> Result<status::Response, Error> {
         let status = self
-            .block_on(self.rpc_client().status())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .request(self.rpc_client().status())
+            .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
 
         if status.sync_info.catching_up {
+            self.endpoints.record_failure();
+
             return Err(Error::chain_not_caught_up(
-                self.config.rpc_addr.to_string(),
+                self.rpc_addr().to_string(),
                 self.config().id.clone(),
             ));
         }
@@ -835,25 +1413,56 @@ impl ChainEndpoint for CosmosSdkChain {
     type LightClient = TmLightClient;
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let rpc_client = HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+        let endpoints = EndpointPool::new(&config, rt.clone())?;
+
+        // Initialize key store and load key. With a remote signer, the relayer holds no key
+        // material: the key store is left empty and in memory, and the public keys and
+        // addresses of the keys are obtained from the signer instead.
+        let key_store_type = match config.remote_signer {
+            Some(_) => Store::Memory,
+            None => config.key_store_type,
+        };
 
-        // Initialize key store and load key
-        let keybase = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)
+        let keybase = KeyRing::new(key_store_type, &config.account_prefix, &config.id)
             .map_err(Error::key_base)?;
 
-        let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
-            .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
+        let key_pool = KeyPool::new(config.key_name.clone(), config.extra_key_names.clone());
+
+        let mut remote_signers = HashMap::new();
+        if let Some(remote_signer) = &config.remote_signer {
+            for key_name in key_pool.key_names() {
+                let signer = RemoteSigner::new(
+                    remote_signer,
+                    config.id.clone(),
+                    key_name.clone(),
+                    &config.address_type,
+                )
+                .map_err(Error::key_base)?;
+
+                remote_signers.insert(key_name.clone(), signer);
+            }
+        }
 
         // Retrieve the version specification of this chain
 
+        let resend_gas_estimates = config.resend_gas_estimate_ttl.map(ResendGasEstimates::new);
+
+        let fee_budget = config
+            .fee_budget
+            .as_ref()
+            .map(|budget| FeeBudgetTracker::new(budget, &config.gas_price.denom));
+
         let chain = Self {
             config,
-            rpc_client,
-            grpc_addr,
+            endpoints,
             rt,
             keybase,
-            account: None,
+            key_pool,
+            remote_signers,
+            accounts: HashMap::new(),
+            dynamic_gas_price: GasPriceCache::default(),
+            resend_gas_estimates,
+            fee_budget,
         };
 
         Ok(chain)
@@ -865,12 +1474,12 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("init_light_client");
 
         let peer_id: PeerId = self
-            .rt
-            .block_on(self.rpc_client.status())
+            .request(self.rpc_client().status())
             .map(|s| s.node_info.id)
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
 
-        let light_client = TmLightClient::from_config(&self.config, peer_id)?;
+        let light_client =
+            TmLightClient::from_config(&self.config, self.endpoints.clone(), peer_id)?;
 
         Ok(light_client)
     }
@@ -878,21 +1487,48 @@ impl ChainEndpoint for CosmosSdkChain {
     fn init_event_monitor(
         &self,
         rt: Arc<TokioRuntime>,
+        resume_from: Option<ICSHeight>,
     ) -> Result<(EventReceiver, TxMonitorCmd), Error> {
         crate::time!("init_event_monitor");
 
-        let (mut event_monitor, event_receiver, monitor_tx) = EventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            rt,
-        )
-        .map_err(Error::event_monitor)?;
+        match self.config.event_source {
+            EventSource::Push => {
+                let (mut event_monitor, event_receiver, monitor_tx) = EventMonitor::new(
+                    self.config.id.clone(),
+                    self.endpoints.active().config.websocket_addr.clone(),
+                    rt,
+                )
+                .map_err(Error::event_monitor)?;
 
-        event_monitor.subscribe().map_err(Error::event_monitor)?;
+                event_monitor.set_endpoints(self.endpoints.clone());
+                if let Some(height) = resume_from {
+                    event_monitor.resume_from(height);
+                }
+                event_monitor.subscribe().map_err(Error::event_monitor)?;
 
-        thread::spawn(move || event_monitor.run());
+                thread::spawn(move || event_monitor.run());
 
-        Ok((event_receiver, monitor_tx))
+                Ok((event_receiver, monitor_tx))
+            }
+            EventSource::Pull { interval } => {
+                let (mut event_poller, event_receiver, monitor_tx) = EventPoller::new(
+                    self.config.id.clone(),
+                    self.rpc_addr().clone(),
+                    interval,
+                    rt,
+                )
+                .map_err(Error::event_monitor)?;
+
+                event_poller.set_endpoints(self.endpoints.clone());
+                if let Some(height) = resume_from {
+                    event_poller.resume_from(height);
+                }
+
+                thread::spawn(move || event_poller.run());
+
+                Ok((event_receiver, monitor_tx))
+            }
+        }
     }
 
     fn shutdown(self) -> Result<(), Error> {
@@ -953,6 +1589,7 @@ impl ChainEndpoint for CosmosSdkChain {
     fn send_messages_and_wait_commit(
         &mut self,
         proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
     ) -> Result<Vec<IbcEvent>, Error> {
         crate::time!("send_messages_and_wait_commit");
         debug!(
@@ -963,37 +1600,20 @@ impl ChainEndpoint for CosmosSdkChain {
         if proto_msgs.is_empty() {
             return Ok(vec![]);
         }
+
+        let (key_name, proto_msgs) = self.dispatch_msgs(proto_msgs)?;
         let mut tx_sync_results = vec![];
+        let mut dropped_events = vec![];
 
-        let mut n = 0;
-        let mut size = 0;
-        let mut msg_batch = vec![];
-        for msg in proto_msgs.iter() {
-            msg_batch.push(msg.clone());
-            let mut buf = Vec::new();
-            prost::Message::encode(msg, &mut buf)
-                .map_err(|e| Error::protobuf_encode(String::from("Message"), e))?;
-            n += 1;
-            size += buf.len();
-            if n >= self.max_msg_num() || size >= self.max_tx_size() {
-                let events_per_tx = vec![IbcEvent::default(); msg_batch.len()];
-                let tx_sync_result = self.send_tx(msg_batch)?;
-                tx_sync_results.push(TxSyncResult {
-                    response: tx_sync_result,
-                    events: events_per_tx,
-                });
-                n = 0;
-                size = 0;
-                msg_batch = vec![];
-            }
-        }
-        if !msg_batch.is_empty() {
-            let events_per_tx = vec![IbcEvent::default(); msg_batch.len()];
-            let tx_sync_result = self.send_tx(msg_batch)?;
+        for msg_batch in self.batch_messages(proto_msgs)? {
+            let (tx_sync_result, msg_count, dropped) =
+                self.send_batch(&key_name, msg_batch, fee_policy)?;
+            let events_per_tx = vec![IbcEvent::default(); msg_count];
             tx_sync_results.push(TxSyncResult {
                 response: tx_sync_result,
                 events: events_per_tx,
             });
+            dropped_events.extend(dropped);
         }
 
         let tx_sync_results = self.wait_for_block_commits(tx_sync_results)?;
@@ -1002,6 +1622,7 @@ impl ChainEndpoint for CosmosSdkChain {
             .into_iter()
             .map(|el| el.events)
             .flatten()
+            .chain(dropped_events)
             .collect();
 
         Ok(events)
@@ -1010,7 +1631,8 @@ impl ChainEndpoint for CosmosSdkChain {
     fn send_messages_and_wait_check_tx(
         &mut self,
         proto_msgs: Vec<Any>,
-    ) -> Result<Vec<Response>, Error> {
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<CheckTxResponses, Error> {
         crate::time!("send_messages_and_wait_check_tx");
         debug!(
             "send_messages_and_wait_check_tx with {} messages",
@@ -1018,46 +1640,225 @@ impl ChainEndpoint for CosmosSdkChain {
         );
 
         if proto_msgs.is_empty() {
-            return Ok(vec![]);
+            return Ok(CheckTxResponses::default());
         }
-        let mut responses = vec![];
 
-        let mut n = 0;
-        let mut size = 0;
-        let mut msg_batch = vec![];
-        for msg in proto_msgs.iter() {
-            msg_batch.push(msg.clone());
-            let mut buf = Vec::new();
-            prost::Message::encode(msg, &mut buf)
-                .map_err(|e| Error::protobuf_encode(String::from("Messages"), e))?;
-            n += 1;
-            size += buf.len();
-            if n >= self.max_msg_num() || size >= self.max_tx_size() {
-                // Send the tx and enqueue the resulting response
-                responses.push(self.send_tx(msg_batch)?);
-                n = 0;
-                size = 0;
-                msg_batch = vec![];
+        let (key_name, proto_msgs) = self.dispatch_msgs(proto_msgs)?;
+        let mut check_tx = CheckTxResponses::default();
+
+        for msg_batch in self.batch_messages(proto_msgs)? {
+            // Send the tx and enqueue the resulting response
+            let (response, _, dropped) = self.send_batch(&key_name, msg_batch, fee_policy)?;
+            check_tx.responses.push(response);
+            check_tx.dropped_events.extend(dropped);
+        }
+
+        Ok(check_tx)
+    }
+
+    /// Sends a transaction carrying each of the given batches of messages, signed with the
+    /// same key of the pool, such that they are broadcast in order by the same account.
+    fn send_transactions_and_wait_check_tx(
+        &mut self,
+        txs: Vec<Vec<Any>>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<Vec<Result<CheckTxResponses, Error>>, Error> {
+        crate::time!("send_transactions_and_wait_check_tx");
+        debug!("send_transactions_and_wait_check_tx with {} txs", txs.len());
+
+        let tx_lens: Vec<usize> = txs.iter().map(Vec::len).collect();
+        let (key_name, proto_msgs) = self.dispatch_msgs(txs.into_iter().flatten().collect())?;
+
+        let mut proto_msgs = proto_msgs.into_iter();
+        let mut results = Vec::with_capacity(tx_lens.len());
+
+        for tx_len in tx_lens {
+            let msg_batch = proto_msgs.by_ref().take(tx_len).collect();
+            let result =
+                self.send_batch(&key_name, msg_batch, fee_policy)
+                    .map(|(response, _, dropped)| CheckTxResponses {
+                        responses: vec![response],
+                        dropped_events: dropped,
+                    });
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    /// Signs the transactions of every submission in turn, each submission with a single key
+    /// of the pool, then broadcasts the transactions signed by different keys concurrently.
+    /// The transactions signed by a key are broadcast in order, and once one of them is not
+    /// accepted, the following ones are signed again with the sequence of the account on
+    /// chain and sent one at a time.
+    fn send_messages_concurrently_and_wait_check_tx(
+        &mut self,
+        submissions: Vec<(Vec<Any>, Option<FeePolicy>)>,
+    ) -> Vec<Result<CheckTxResponses, Error>> {
+        crate::time!("send_messages_concurrently_and_wait_check_tx");
+
+        if self.key_pool.key_names().len() == 1 {
+            return submissions
+                .into_iter()
+                .map(|(proto_msgs, fee_policy)| {
+                    self.send_messages_and_wait_check_tx(proto_msgs, fee_policy.as_ref())
+                })
+                .collect();
+        }
+
+        debug!(
+            "send_messages_concurrently_and_wait_check_tx with {} submissions",
+            submissions.len()
+        );
+
+        let mut results = Vec::with_capacity(submissions.len());
+        let mut fee_policies = Vec::with_capacity(submissions.len());
+
+        // The signed txs of each key, along with the index of the submission they belong to
+        let mut queues: Vec<(String, Vec<(usize, SignedTx)>)> = vec![];
+
+        for (index, (proto_msgs, fee_policy)) in submissions.into_iter().enumerate() {
+            match self.sign_messages(proto_msgs, fee_policy.as_ref()) {
+                Ok((key_name, txs)) => {
+                    let dropped_events = txs
+                        .iter()
+                        .flat_map(|tx| tx.dropped_events.clone())
+                        .collect();
+
+                    let txs = txs.into_iter().map(|tx| (index, tx));
+
+                    match queues.iter_mut().find(|(name, _)| *name == key_name) {
+                        Some((_, queue)) => queue.extend(txs),
+                        None => queues.push((key_name, txs.collect())),
+                    }
+
+                    results.push(Ok(CheckTxResponses {
+                        responses: vec![],
+                        dropped_events,
+                    }));
+                }
+                Err(e) => results.push(Err(e)),
             }
+
+            fee_policies.push(fee_policy);
         }
-        if !msg_batch.is_empty() {
-            responses.push(self.send_tx(msg_batch)?);
+
+        let broadcasts = queues.iter().map(|(_, queue)| {
+            let txs = queue.iter().map(|(_, tx)| tx.bytes.clone()).collect();
+            broadcast_txs_in_order(self.rpc_client(), self.rpc_addr(), txs)
+        });
+
+        let responses = self.block_on(futures::future::join_all(broadcasts));
+
+        for ((key_name, queue), responses) in queues.into_iter().zip(responses) {
+            let broadcast_count = responses.len();
+            let mut queue = queue.into_iter();
+
+            for ((index, tx), response) in queue.by_ref().take(broadcast_count).zip(responses) {
+                self.endpoints.record_outcome(&response);
+
+                if let Some(fee_budget) = self.fee_budget.as_mut() {
+                    fee_budget.release(&tx.fee);
+                }
+
+                match response {
+                    Ok(response) => {
+                        match response.code {
+                            tendermint::abci::Code::Ok => {
+                                if let Some(fee_budget) = self.fee_budget.as_mut() {
+                                    fee_budget.record(&tx.fee);
+                                }
+                            }
+                            tendermint::abci::Code::Err(code) => {
+                                self.log_check_tx_error(&response, code);
+                            }
+                        }
+
+                        if let Ok(check_tx) = results[index].as_mut() {
+                            check_tx.responses.push(response);
+                        }
+                    }
+                    Err(e) => results[index] = Err(e),
+                }
+            }
+
+            let remaining = queue.collect::<Vec<_>>();
+
+            // The remaining txs are signed again when they are sent, and their fees
+            // are only spent once they are accepted
+            if let Some(fee_budget) = self.fee_budget.as_mut() {
+                for (_, tx) in &remaining {
+                    fee_budget.release(&tx.fee);
+                }
+            }
+
+            if remaining.is_empty() {
+                continue;
+            }
+
+            // The cached sequence of the account accounts for txs which were not accepted,
+            // query the account again before signing the remaining txs
+            self.accounts.remove(&key_name);
+
+            for (index, tx) in remaining {
+                if results[index].is_err() {
+                    continue;
+                }
+
+                let result =
+                    self.send_batch(&key_name, tx.proto_msgs, fee_policies[index].as_ref());
+                match result {
+                    Ok((response, _, dropped)) => {
+                        if let Ok(check_tx) = results[index].as_mut() {
+                            check_tx.responses.push(response);
+                            check_tx.dropped_events.extend(dropped);
+                        }
+                    }
+                    Err(e) => results[index] = Err(e),
+                }
+            }
+        }
+
+        results
+    }
+
+    fn simulate_messages(
+        &mut self,
+        proto_msgs: Vec<Any>,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        crate::time!("simulate_messages");
+        debug!("simulate_messages with {} messages", proto_msgs.len());
+
+        if proto_msgs.is_empty() {
+            return Ok(vec![]);
         }
 
-        Ok(responses)
+        let (key_name, proto_msgs) = self.dispatch_msgs(proto_msgs)?;
+
+        // The events of the simulated txs are reported at the height the txs would be included at
+        let height = self.query_latest_height()?.increment();
+
+        let mut events = vec![];
+        for msg_batch in self.batch_messages(proto_msgs)? {
+            events.extend(self.simulate_batch(&key_name, msg_batch, fee_policy, height)?);
+        }
+
+        Ok(events)
     }
 
     /// Get the account for the signer
     fn get_signer(&mut self) -> Result<Signer, Error> {
         crate::time!("get_signer");
 
-        // Get the key from key seed file
-        let key = self
-            .keybase()
-            .get_key(&self.config.key_name)
+        let public_key = self
+            .tx_signer(&self.config.key_name)
+            .public_key()
             .map_err(|e| Error::key_not_found(self.config.key_name.clone(), e))?;
 
-        let bech32 = encode_to_bech32(&key.address.to_hex(), &self.config.account_prefix)?;
+        let address = address_from_public_key(&public_key, &self.config.address_type);
+        let bech32 = encode_to_bech32(&address.to_hex(), &self.config.account_prefix)?;
         Ok(Signer::new(bech32))
     }
 
@@ -1120,16 +1921,16 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_clients");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
 
         let request = tonic::Request::new(request);
         let response = self
-            .block_on(client.client_states(request))
+            .request(client.client_states(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1218,16 +2019,16 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_consensus_states");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
 
         let request = tonic::Request::new(request);
         let response = self
-            .block_on(client.consensus_states(request))
+            .request(client.consensus_states(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1262,16 +2063,16 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_connections");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
 
         let request = tonic::Request::new(request);
 
-        let response = match self.block_on(client.client_connections(request)) {
+        let response = match self.request(client.client_connections(request)) {
             Ok(res) => res.into_inner(),
             Err(e) if e.code() == tonic::Code::NotFound => return Ok(vec![]),
             Err(e) => return Err(Error::grpc_status(e)),
@@ -1296,9 +2097,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_connections");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1306,7 +2107,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let response = self
-            .block_on(client.connections(request))
+            .request(client.connections(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1336,7 +2137,7 @@ impl ChainEndpoint for CosmosSdkChain {
             use tonic::{metadata::MetadataValue, IntoRequest};
 
             let mut client =
-                connection::query_client::QueryClient::connect(chain.grpc_addr.clone())
+                connection::query_client::QueryClient::connect(chain.grpc_addr().clone())
                     .await
                     .map_err(Error::grpc_transport)?;
 
@@ -1376,7 +2177,7 @@ impl ChainEndpoint for CosmosSdkChain {
             }
         }
 
-        self.block_on(async { do_query_connection(self, connection_id, height).await })
+        self.request(async { do_query_connection(self, connection_id, height).await })
     }
 
     fn query_connection_channels(
@@ -1386,9 +2187,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_connection_channels");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1396,7 +2197,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let response = self
-            .block_on(client.connection_channels(request))
+            .request(client.connection_channels(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1418,9 +2219,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_connections");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1428,7 +2229,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let response = self
-            .block_on(client.channels(request))
+            .request(client.channels(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1463,9 +2264,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_channel_client_state");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1473,7 +2274,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let response = self
-            .block_on(client.channel_client_state(request))
+            .request(client.channel_client_state(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1492,9 +2293,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_packet_commitments");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1502,7 +2303,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let response = self
-            .block_on(client.packet_commitments(request))
+            .request(client.packet_commitments(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1525,9 +2326,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_unreceived_packets");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1535,7 +2336,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let mut response = self
-            .block_on(client.unreceived_packets(request))
+            .request(client.unreceived_packets(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1551,9 +2352,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_packet_acknowledgements");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1561,7 +2362,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let response = self
-            .block_on(client.packet_acknowledgements(request))
+            .request(client.packet_acknowledgements(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1583,9 +2384,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_unreceived_acknowledgements");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1593,7 +2394,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let mut response = self
-            .block_on(client.unreceived_acks(request))
+            .request(client.unreceived_acks(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1608,9 +2409,9 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_next_sequence_receive");
 
         let mut client = self
-            .block_on(
+            .request(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr().clone(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1618,7 +2419,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let request = tonic::Request::new(request);
 
         let response = self
-            .block_on(client.next_sequence_receive(request))
+            .request(client.next_sequence_receive(request))
             .map_err(Error::grpc_status)?
             .into_inner();
 
@@ -1648,14 +2449,14 @@ impl ChainEndpoint for CosmosSdkChain {
                 for seq in &request.sequences {
                     // query first (and only) Tx that includes the event specified in the query request
                     let response = self
-                        .block_on(self.rpc_client.tx_search(
+                        .request(self.rpc_client().tx_search(
                             packet_query(&request, *seq),
                             false,
                             1,
                             1, // get only the first Tx matching the query
                             Order::Ascending,
                         ))
-                        .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                        .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
 
                     assert!(
                         response.txs.len() <= 1,
@@ -1688,14 +2489,14 @@ impl ChainEndpoint for CosmosSdkChain {
                 // failed on chain. Therefore only one Tx is of interest and current API returns
                 // the first one.
                 let mut response = self
-                    .block_on(self.rpc_client.tx_search(
+                    .request(self.rpc_client().tx_search(
                         header_query(&request),
                         false,
                         1,
                         1, // get only the first Tx matching the query
                         Order::Ascending,
                     ))
-                    .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                    .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
 
                 if response.txs.is_empty() {
                     return Ok(vec![]);
@@ -1715,14 +2516,14 @@ impl ChainEndpoint for CosmosSdkChain {
 
             QueryTxRequest::Transaction(tx) => {
                 let mut response = self
-                    .block_on(self.rpc_client.tx_search(
+                    .request(self.rpc_client().tx_search(
                         tx_hash_query(&tx),
                         false,
                         1,
                         1, // get only the first Tx matching the query
                         Order::Ascending,
                     ))
-                    .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                    .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
 
                 if response.txs.is_empty() {
                     Ok(vec![])
@@ -1749,13 +2550,13 @@ impl ChainEndpoint for CosmosSdkChain {
 
                 for seq in &request.sequences {
                     let response = self
-                        .block_on(self.rpc_client.block_search(
+                        .request(self.rpc_client().block_search(
                             packet_query(&request, *seq),
                             1,
                             1, // there should only be a single match for this query
                             Order::Ascending,
                         ))
-                        .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                        .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
 
                     assert!(
                         response.blocks.len() <= 1,
@@ -1771,8 +2572,8 @@ impl ChainEndpoint for CosmosSdkChain {
                         }
 
                         let response = self
-                            .block_on(self.rpc_client.block_results(block.header.height))
-                            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                            .request(self.rpc_client().block_results(block.header.height))
+                            .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
 
                         begin_block_events.append(
                             &mut response
@@ -1795,6 +2596,51 @@ impl ChainEndpoint for CosmosSdkChain {
                 }
                 Ok((begin_block_events, end_block_events))
             }
+            QueryBlockRequest::PacketRange(request) => {
+                crate::time!("query_blocks: scan blocks for packet events");
+
+                let mut begin_block_events: Vec<IbcEvent> = vec![];
+                let mut end_block_events: Vec<IbcEvent> = vec![];
+
+                let from_height = request.from_height.revision_height;
+                let to_height = request.to_height.revision_height;
+
+                for revision_height in from_height..=to_height {
+                    let height = ICSHeight::new(self.id().version(), revision_height);
+                    let tm_height =
+                        Height::try_from(revision_height).map_err(Error::invalid_height)?;
+
+                    let response = self
+                        .request(self.rpc_client().block_results(tm_height))
+                        .map_err(|e| Error::rpc(self.rpc_addr().clone(), e))?;
+
+                    let matching_events = |events: Vec<Event>| -> Vec<IbcEvent> {
+                        events
+                            .into_iter()
+                            .filter_map(|ev| filter_matching_range_event(height, ev, &request))
+                            .collect()
+                    };
+
+                    // Only the transactions which were successfully delivered emitted events
+                    let tx_events = response
+                        .txs_results
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|tx_result| tx_result.code.is_ok())
+                        .flat_map(|tx_result| tx_result.events)
+                        .collect();
+
+                    begin_block_events.append(&mut matching_events(
+                        response.begin_block_events.unwrap_or_default(),
+                    ));
+                    begin_block_events.append(&mut matching_events(tx_events));
+                    end_block_events.append(&mut matching_events(
+                        response.end_block_events.unwrap_or_default(),
+                    ));
+                }
+
+                Ok((begin_block_events, end_block_events))
+            }
         }
     }
 
@@ -1980,11 +2826,11 @@ impl ChainEndpoint for CosmosSdkChain {
         use ibc_proto::ibc::core::port::v1::query_client::QueryClient;
 
         let mut client = self
-            .block_on(QueryClient::connect(self.grpc_addr.clone()))
+            .request(QueryClient::connect(self.grpc_addr().clone()))
             .map_err(Error::grpc_transport)?;
 
         let tonic_req: QueryAppVersionRequest = request.into();
-        let response = self.block_on(client.app_version(tonic_req));
+        let response = self.request(client.app_version(tonic_req));
         let resp_version = response
             .map_err(Error::grpc_status)?
             .into_inner()
@@ -2118,6 +2964,47 @@ fn all_ibc_events_from_tx_search_response(chain_id: &ChainId, response: ResultTx
     result
 }
 
+/// Converts an event reported by a tx simulation into the type of the events
+/// reported by the RPC endpoint, from which the IBC events are parsed.
+fn abci_event(event: tendermint_proto::abci::Event) -> Result<Event, Error> {
+    fn parse_attribute<T>(event_type: &str, bytes: Vec<u8>) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let invalid_attribute =
+            |reason: String| Error::invalid_event_attribute(event_type.to_string(), reason);
+
+        String::from_utf8(bytes)
+            .map_err(|e| invalid_attribute(e.to_string()))?
+            .parse()
+            .map_err(|e: T::Err| invalid_attribute(e.to_string()))
+    }
+
+    let attributes = event
+        .attributes
+        .into_iter()
+        .map(|attribute| {
+            Ok(Tag {
+                key: parse_attribute(&event.r#type, attribute.key)?,
+                value: parse_attribute(&event.r#type, attribute.value)?,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Event {
+        type_str: event.r#type,
+        attributes,
+    })
+}
+
+fn matches_packet_channel(request: &QueryPacketEventDataRequest, packet: &Packet) -> bool {
+    packet.source_port == request.source_port_id
+        && packet.source_channel == request.source_channel_id
+        && packet.destination_port == request.destination_port_id
+        && packet.destination_channel == request.destination_channel_id
+}
+
 fn filter_matching_event(
     event: Event,
     request: &QueryPacketEventDataRequest,
@@ -2128,11 +3015,7 @@ fn filter_matching_event(
         seq: Sequence,
         packet: &Packet,
     ) -> bool {
-        packet.source_port == request.source_port_id
-            && packet.source_channel == request.source_channel_id
-            && packet.destination_port == request.destination_port_id
-            && packet.destination_channel == request.destination_channel_id
-            && packet.sequence == seq
+        matches_packet_channel(request, packet) && packet.sequence == seq
     }
 
     if event.type_str != request.event_id.as_str() {
@@ -2153,6 +3036,42 @@ fn filter_matching_event(
     }
 }
 
+// Extracts the packet event emitted at `height`, within the range of heights of the request
+// (inclusive), which matches the packet request for any of its sequences, or for any sequence
+// at all if the packet request does not specify them.
+fn filter_matching_range_event(
+    height: ICSHeight,
+    event: Event,
+    range_request: &QueryBlockRangeRequest,
+) -> Option<IbcEvent> {
+    let request = &range_request.packet;
+
+    if height < range_request.from_height || height > range_request.to_height {
+        return None;
+    }
+
+    if event.type_str != request.event_id.as_str() {
+        return None;
+    }
+
+    let mut ibc_event = ChannelEvents::try_from_tx(&event)?;
+    let packet = match ibc_event {
+        IbcEvent::SendPacket(ref send_ev) => &send_ev.packet,
+        IbcEvent::WriteAcknowledgement(ref ack_ev) => &ack_ev.packet,
+        _ => return None,
+    };
+
+    let matches = matches_packet_channel(request, packet)
+        && (request.sequences.is_empty() || request.sequences.contains(&packet.sequence));
+
+    if !matches {
+        return None;
+    }
+
+    ibc_event.set_height(height);
+    Some(ibc_event)
+}
+
 /// Perform a generic `abci_query`, and return the corresponding deserialized response data.
 async fn abci_query(
     chain: &CosmosSdkChain,
@@ -2172,7 +3091,7 @@ async fn abci_query(
         .rpc_client()
         .abci_query(Some(path), data.into_bytes(), height, prove)
         .await
-        .map_err(|e| Error::rpc(chain.config.rpc_addr.clone(), e))?;
+        .map_err(|e| Error::rpc(chain.rpc_addr().clone(), e))?;
 
     if !response.code.is_ok() {
         // Fail with response log.
@@ -2213,10 +3132,33 @@ pub async fn broadcast_tx_sync(
     Ok(response)
 }
 
+/// Broadcasts the given txs in order, stopping after the first tx which is not accepted,
+/// since the txs following it were signed with account sequences which are not valid anymore.
+async fn broadcast_txs_in_order(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    txs: Vec<Vec<u8>>,
+) -> Vec<Result<Response, Error>> {
+    let mut responses = Vec::with_capacity(txs.len());
+
+    for tx_bytes in txs {
+        let response = broadcast_tx_sync(rpc_client, rpc_address, tx_bytes).await;
+        let accepted = matches!(&response, Ok(r) if r.code == tendermint::abci::Code::Ok);
+
+        responses.push(response);
+
+        if !accepted {
+            break;
+        }
+    }
+
+    responses
+}
+
 /// Uses the GRPC client to retrieve the account sequence
 async fn query_account(chain: &CosmosSdkChain, address: String) -> Result<BaseAccount, Error> {
     let mut client = ibc_proto::cosmos::auth::v1beta1::query_client::QueryClient::connect(
-        chain.grpc_addr.clone(),
+        chain.grpc_addr().clone(),
     )
     .await
     .map_err(Error::grpc_transport)?;
@@ -2275,6 +3217,19 @@ pub struct TxSyncResult {
     events: Vec<IbcEvent>,
 }
 
+/// A transaction signed with the current sequence of the account of its key,
+/// ready to be broadcast.
+struct SignedTx {
+    // the messages carried by the tx, once those failing the simulation are dropped
+    proto_msgs: Vec<Any>,
+    // the encoded `TxRaw`
+    bytes: Vec<u8>,
+    // the fee paid for the tx, accounted for by the fee budget
+    fee: Vec<Coin>,
+    // an error event for each message dropped from the tx, as it failed the simulation
+    dropped_events: Vec<IbcEvent>,
+}
+
 pub fn auth_info_and_bytes(
     signer_info: SignerInfo,
     fee: Fee,
@@ -2310,11 +3265,11 @@ pub fn tx_body_and_bytes(proto_msgs: Vec<Any>, memo: &Memo) -> Result<(TxBody, V
 
 async fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
     let chain_id = chain.id();
-    let grpc_address = chain.grpc_addr.to_string();
-    let rpc_address = chain.config.rpc_addr.to_string();
+    let grpc_address = chain.grpc_addr().to_string();
+    let rpc_address = chain.rpc_addr().to_string();
 
     // Checkup on the self-reported health endpoint
-    chain.rpc_client.health().await.map_err(|e| {
+    chain.rpc_client().health().await.map_err(|e| {
         Error::health_check_json_rpc(
             chain_id.clone(),
             rpc_address.clone(),
@@ -2325,7 +3280,7 @@ async fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
 
     // Checkup on transaction indexing
     chain
-        .rpc_client
+        .rpc_client()
         .tx_search(
             Query::from(EventType::NewBlock),
             false,
@@ -2343,7 +3298,7 @@ async fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
             )
         })?;
 
-    let version_specs = fetch_version_specs(&chain.config.id, &chain.grpc_addr).await?;
+    let version_specs = fetch_version_specs(&chain.config.id, chain.grpc_addr()).await?;
 
     // Checkup on the underlying SDK & IBC-go versions
     if let Err(diagnostic) = compatibility::run_diagnostic(&version_specs) {
@@ -2412,6 +3367,28 @@ fn can_recover_from_simulation_failure(e: &Error) -> bool {
     }
 }
 
+/// Determine whether the given error yielded by `tx_simulate`
+/// is due to the account sequence of the tx not matching that of the account.
+fn is_account_sequence_mismatch(e: &Error) -> bool {
+    use crate::error::ErrorDetail::*;
+
+    match e.detail() {
+        GrpcStatus(detail) => detail.is_account_sequence_mismatch(),
+        _ => false,
+    }
+}
+
+/// The index of the message which failed the execution of the simulated tx,
+/// if the given error yielded by `tx_simulate` reports one.
+fn failed_message_index(e: &Error) -> Option<usize> {
+    use crate::error::ErrorDetail::*;
+
+    match e.detail() {
+        GrpcStatus(detail) => detail.failed_message_index(),
+        _ => None,
+    }
+}
+
 struct PrettyFee<'a>(&'a Fee);
 
 impl fmt::Display for PrettyFee<'_> {
@@ -2462,20 +3439,27 @@ fn calculate_client_state_drift(
 
 #[cfg(test)]
 mod tests {
+    use tendermint::abci::Event as AbciEvent;
+
     use ibc::{
         core::{
             ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState},
             ics02_client::client_type::ClientType,
+            ics04_channel::channel::QueryPacketEventDataRequest,
+            ics04_channel::events::{SendPacket, WriteAcknowledgement},
+            ics04_channel::packet::{Packet, Sequence},
             ics24_host::identifier::ClientId,
         },
+        events::{IbcEvent, WithBlockDataType},
         mock::client_state::MockClientState,
         mock::header::MockHeader,
+        query::QueryBlockRangeRequest,
         Height,
     };
 
     use crate::{chain::cosmos::client_id_suffix, config::GasPrice};
 
-    use super::calculate_fee;
+    use super::{abci_event, calculate_fee, filter_matching_range_event};
 
     #[test]
     fn mul_ceil() {
@@ -2541,4 +3525,132 @@ mod tests {
             7
         );
     }
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: Sequence::from(sequence),
+            source_port: "transfer".parse().unwrap(),
+            source_channel: "channel-0".parse().unwrap(),
+            destination_port: "transfer".parse().unwrap(),
+            destination_channel: "channel-1".parse().unwrap(),
+            ..Packet::default()
+        }
+    }
+
+    fn send_packet_event(sequence: u64) -> AbciEvent {
+        AbciEvent::try_from(SendPacket {
+            height: Height::default(),
+            packet: packet(sequence),
+        })
+        .unwrap()
+    }
+
+    fn range_request(sequences: Vec<u64>, from: u64, to: u64) -> QueryBlockRangeRequest {
+        QueryBlockRangeRequest {
+            packet: QueryPacketEventDataRequest {
+                event_id: WithBlockDataType::SendPacket,
+                source_port_id: "transfer".parse().unwrap(),
+                source_channel_id: "channel-0".parse().unwrap(),
+                destination_port_id: "transfer".parse().unwrap(),
+                destination_channel_id: "channel-1".parse().unwrap(),
+                sequences: sequences.into_iter().map(Sequence::from).collect(),
+                height: Height::new(0, 30),
+            },
+            from_height: Height::new(0, from),
+            to_height: Height::new(0, to),
+        }
+    }
+
+    #[test]
+    fn range_bounds_are_inclusive() {
+        let request = range_request(vec![], 10, 20);
+
+        for height in [10, 15, 20] {
+            let event =
+                filter_matching_range_event(Height::new(0, height), send_packet_event(1), &request);
+
+            match event {
+                Some(IbcEvent::SendPacket(send_packet)) => {
+                    assert_eq!(send_packet.height, Height::new(0, height));
+                    assert_eq!(send_packet.packet.sequence, Sequence::from(1));
+                }
+                other => panic!("expected a SendPacket event, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn events_outside_range_are_dropped() {
+        let request = range_request(vec![], 10, 20);
+
+        for height in [9, 21] {
+            assert_eq!(
+                filter_matching_range_event(Height::new(0, height), send_packet_event(1), &request),
+                None
+            );
+        }
+
+        // A block of another revision at the same revision height is outside of the range
+        assert_eq!(
+            filter_matching_range_event(Height::new(1, 15), send_packet_event(1), &request),
+            None
+        );
+    }
+
+    #[test]
+    fn events_not_matching_request_are_dropped() {
+        let request = range_request(vec![2, 3], 10, 20);
+        let height = Height::new(0, 15);
+
+        // Sequence not requested
+        assert_eq!(
+            filter_matching_range_event(height, send_packet_event(1), &request),
+            None
+        );
+        assert!(filter_matching_range_event(height, send_packet_event(3), &request).is_some());
+
+        // Packet sent on another channel
+        let mut other_channel = packet(2);
+        other_channel.source_channel = "channel-5".parse().unwrap();
+        let event = AbciEvent::try_from(SendPacket {
+            height: Height::default(),
+            packet: other_channel,
+        })
+        .unwrap();
+        assert_eq!(filter_matching_range_event(height, event, &request), None);
+
+        // Event of another type than requested
+        let event = AbciEvent::try_from(WriteAcknowledgement {
+            height: Height::default(),
+            packet: packet(2),
+            ack: b"ack".to_vec().into(),
+        })
+        .unwrap();
+        assert_eq!(filter_matching_range_event(height, event, &request), None);
+    }
+
+    #[test]
+    fn abci_event_from_simulation() {
+        let attribute = |key: &[u8], value: &[u8]| tendermint_proto::abci::EventAttribute {
+            key: key.to_vec(),
+            value: value.to_vec(),
+            index: false,
+        };
+
+        let event = abci_event(tendermint_proto::abci::Event {
+            r#type: "send_packet".to_string(),
+            attributes: vec![attribute(b"packet_sequence", b"2")],
+        })
+        .unwrap();
+        assert_eq!(event.type_str, "send_packet");
+        assert_eq!(event.attributes[0].key.as_ref(), "packet_sequence");
+        assert_eq!(event.attributes[0].value.as_ref(), "2");
+
+        // Attributes which are not valid UTF-8 are rejected rather than mangled
+        let event = tendermint_proto::abci::Event {
+            r#type: "send_packet".to_string(),
+            attributes: vec![attribute(b"packet_sequence", &[0xff, 0xfe])],
+        };
+        assert!(abci_event(event).is_err());
+    }
 }
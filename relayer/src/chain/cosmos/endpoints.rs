@@ -0,0 +1,525 @@
+//! The endpoints of a chain, and the failover between them.
+//!
+//! Each endpoint has a health score, between zero and [`MAX_HEALTH_SCORE`], which
+//! every endpoint starts with. The health of the endpoints is checked periodically
+//! by a background thread, as well as after a request to the active endpoint failed,
+//! such that the requests never wait for a health check. A check raises the score of
+//! the endpoints which are healthy, ie. whose RPC and gRPC servers can be reached,
+//! whose node is not catching up, and whose latest height does not lag too far behind
+//! the highest latest height among all the endpoints. It lowers the score of the
+//! lagging endpoints, and halves that of the unreachable ones, while every failed
+//! request lowers the score of the active endpoint. Once the score of the active
+//! endpoint falls below [`FAILOVER_HEALTH_SCORE`], the endpoint with the highest
+//! score, the first one in the order of the configuration among equals, becomes
+//! active if its score is higher. After the connection to the active endpoint was
+//! lost, the health of the endpoints is checked right away instead, such that the
+//! connection is re-established to the endpoint failed over to, if any.
+
+use alloc::sync::Arc;
+use core::str::FromStr;
+use core::time::Duration;
+use std::thread;
+
+use crossbeam_channel as channel;
+use futures::future::join_all;
+use tendermint_light_client::types::PeerId;
+use tendermint_rpc::{Client, HttpClient};
+use tokio::runtime::Runtime as TokioRuntime;
+use tonic::codegen::http::Uri;
+use tracing::{debug, warn};
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::config::{ChainConfig, EndpointConfig};
+use crate::error::{Error, ErrorDetail};
+use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
+
+/// Maximum duration of the health check of an endpoint
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The health score of an endpoint which passed all its recent health checks
+const MAX_HEALTH_SCORE: u32 = 10;
+
+/// The health score below which the active endpoint is failed over from
+const FAILOVER_HEALTH_SCORE: u32 = MAX_HEALTH_SCORE / 2;
+
+/// The penalty to the health score of an endpoint whose latest height lags behind
+const LAGGING_PENALTY: u32 = 2;
+
+/// An endpoint of a chain, along with the clients to reach it.
+#[derive(Clone, Debug)]
+pub struct Endpoint {
+    pub config: EndpointConfig,
+    pub rpc_client: HttpClient,
+    pub grpc_addr: Uri,
+}
+
+/// The outcome of the health check of an endpoint.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Probe {
+    /// The endpoint can be reached and its node is caught up
+    Reachable { latest_height: u64, peer_id: PeerId },
+    /// The endpoint cannot be reached, or its node is catching up
+    Unreachable,
+}
+
+#[derive(Clone, Debug)]
+struct EndpointHealth {
+    /// The health score of the endpoint
+    score: u32,
+    /// The identifier of the node of the endpoint, as of its last health check
+    peer_id: Option<PeerId>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    /// Index of the active endpoint
+    active: usize,
+    /// The health of each endpoint
+    health: Vec<EndpointHealth>,
+}
+
+impl PoolState {
+    fn new(endpoint_count: usize) -> Self {
+        let health = EndpointHealth {
+            score: MAX_HEALTH_SCORE,
+            peer_id: None,
+        };
+
+        Self {
+            active: 0,
+            health: vec![health; endpoint_count],
+        }
+    }
+
+    /// Update the health scores of the endpoints from the outcome of their health checks,
+    /// and return the index of the endpoint to fail over to, if any.
+    fn record_probes(&mut self, probes: &[Probe], max_height_lag: u64) -> Option<usize> {
+        let highest_height = probes
+            .iter()
+            .filter_map(|probe| match probe {
+                Probe::Reachable { latest_height, .. } => Some(*latest_height),
+                Probe::Unreachable => None,
+            })
+            .max();
+
+        for (health, probe) in self.health.iter_mut().zip(probes) {
+            match (probe, highest_height) {
+                (
+                    Probe::Reachable {
+                        latest_height,
+                        peer_id,
+                    },
+                    Some(highest),
+                ) => {
+                    health.peer_id = Some(*peer_id);
+                    health.score = if highest - latest_height <= max_height_lag {
+                        (health.score + 1).min(MAX_HEALTH_SCORE)
+                    } else {
+                        health.score.saturating_sub(LAGGING_PENALTY)
+                    };
+                }
+                _ => health.score /= 2,
+            }
+        }
+
+        self.failover_target()
+    }
+
+    /// Lower the health score of the active endpoint after a request to it failed.
+    fn record_failure(&mut self) {
+        let health = &mut self.health[self.active];
+        health.score = health.score.saturating_sub(1);
+    }
+
+    /// The index of the endpoint to fail over to, if the active endpoint is unhealthy
+    /// and another endpoint has a higher health score.
+    fn failover_target(&self) -> Option<usize> {
+        let active_score = self.health[self.active].score;
+
+        if active_score >= FAILOVER_HEALTH_SCORE {
+            return None;
+        }
+
+        // The first endpoint with the highest score
+        let (next, health) = self
+            .health
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, health)| health.score)?;
+
+        (health.score > active_score).then(|| next)
+    }
+}
+
+/// The endpoints of a chain, one of which is active at any given time.
+///
+/// Clones of a pool share the same active endpoint, so that the chain runtime,
+/// its light client and its event monitor all fail over together.
+#[derive(Clone, Debug)]
+pub struct EndpointPool {
+    chain_id: ChainId,
+    endpoints: Arc<Vec<Endpoint>>,
+    state: RwArc<PoolState>,
+    /// The maximum number of blocks an endpoint may lag behind the others and be healthy
+    max_height_lag: u64,
+    /// Wakes up the health checker of the pool after a request failed,
+    /// if the chain has several endpoints
+    failures: Option<channel::Sender<()>>,
+}
+
+impl EndpointPool {
+    /// Create the pool of the endpoints of the given chain and, if the chain has
+    /// several endpoints, spawn the thread checking their health on the given runtime,
+    /// which stops once all the clones of the pool are dropped.
+    pub fn new(config: &ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
+        let endpoints = config
+            .endpoints()
+            .into_iter()
+            .map(|endpoint| {
+                let rpc_client = HttpClient::new(endpoint.rpc_addr.clone())
+                    .map_err(|e| Error::rpc(endpoint.rpc_addr.clone(), e))?;
+
+                let grpc_addr = Uri::from_str(&endpoint.grpc_addr.to_string())
+                    .map_err(|e| Error::invalid_uri(endpoint.grpc_addr.to_string(), e))?;
+
+                Ok(Endpoint {
+                    config: endpoint,
+                    rpc_client,
+                    grpc_addr,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        telemetry!(
+            active_endpoint,
+            &config.id,
+            &endpoints[0].config.rpc_addr.to_string(),
+            true
+        );
+
+        let mut pool = Self {
+            chain_id: config.id.clone(),
+            state: RwArc::new_lock(PoolState::new(endpoints.len())),
+            endpoints: Arc::new(endpoints),
+            max_height_lag: config.failover.max_height_lag,
+            failures: None,
+        };
+
+        if pool.endpoints.len() > 1 {
+            let (failures, failures_rx) = channel::unbounded();
+
+            // The checker holds no sender, such that it stops once the pool is dropped
+            let checker = HealthChecker {
+                pool: pool.clone(),
+                failures: failures_rx,
+                interval: config.failover.health_check_interval,
+                rt,
+            };
+
+            pool.failures = Some(failures);
+
+            thread::spawn(move || checker.run());
+        }
+
+        Ok(pool)
+    }
+
+    /// The active endpoint
+    pub fn active(&self) -> &Endpoint {
+        let active = self.state.acquire_read().active;
+        &self.endpoints[active]
+    }
+
+    /// The identifier of the node of the active endpoint, as of its last health check
+    pub fn active_peer_id(&self) -> Option<PeerId> {
+        let state = self.state.acquire_read();
+        state.health[state.active].peer_id
+    }
+
+    /// Record that a request to the active endpoint failed, which lowers its
+    /// health score and triggers a health check of the endpoints.
+    pub fn record_failure(&self) {
+        if let Some(failures) = &self.failures {
+            self.state.acquire_write().record_failure();
+            let _ = failures.send(());
+        }
+    }
+
+    /// Record a failure of the active endpoint if the outcome of a request
+    /// to it shows that it could not be reached or could not serve the request.
+    pub fn record_outcome(&self, outcome: &impl RequestOutcome) {
+        if outcome.is_endpoint_failure() {
+            self.record_failure();
+        }
+    }
+
+    /// Record that the connection to the active endpoint was lost, and check the health
+    /// of the endpoints on the given runtime before returning, such that the endpoint
+    /// active afterwards is the one failed over to, if any, rather than the one lost.
+    pub fn record_disconnection(&self, rt: &TokioRuntime) {
+        if self.failures.is_some() {
+            self.state.acquire_write().record_failure();
+            self.check_health(rt);
+        }
+    }
+
+    /// Check the health of the endpoints, and fail over to another endpoint
+    /// if the active one is unhealthy.
+    fn check_health(&self, rt: &TokioRuntime) {
+        let probes = rt.block_on(join_all(self.endpoints.iter().map(probe)));
+        self.record_probes(&probes);
+    }
+
+    /// Update the health of the endpoints from the outcome of their health checks,
+    /// and fail over to another endpoint if the active one is unhealthy.
+    fn record_probes(&self, probes: &[Probe]) {
+        let mut state = self.state.acquire_write();
+
+        let next = state.record_probes(probes, self.max_height_lag);
+
+        debug!(
+            "[{}] checked endpoints health, probes: {:?}, scores: {:?}",
+            self.chain_id,
+            probes,
+            state
+                .health
+                .iter()
+                .map(|health| health.score)
+                .collect::<Vec<_>>()
+        );
+
+        let current = state.active;
+
+        match next {
+            Some(next) => {
+                warn!(
+                    "[{}] endpoint {} is unhealthy, failing over to endpoint {}",
+                    self.chain_id,
+                    self.endpoints[current].config.rpc_addr,
+                    self.endpoints[next].config.rpc_addr
+                );
+
+                telemetry!(
+                    active_endpoint,
+                    &self.chain_id,
+                    &self.endpoints[current].config.rpc_addr.to_string(),
+                    false
+                );
+
+                telemetry!(
+                    active_endpoint,
+                    &self.chain_id,
+                    &self.endpoints[next].config.rpc_addr.to_string(),
+                    true
+                );
+
+                state.active = next;
+            }
+            None if state.health[current].score < FAILOVER_HEALTH_SCORE => warn!(
+                "[{}] no endpoint is healthier, keeping endpoint {}",
+                self.chain_id, self.endpoints[current].config.rpc_addr
+            ),
+            None => {}
+        }
+    }
+}
+
+/// Checks the health of the endpoints of a pool, in its own thread.
+struct HealthChecker {
+    pool: EndpointPool,
+    failures: channel::Receiver<()>,
+    interval: Duration,
+    rt: Arc<TokioRuntime>,
+}
+
+impl HealthChecker {
+    fn run(self) {
+        loop {
+            match self.failures.recv_timeout(self.interval) {
+                Ok(()) | Err(channel::RecvTimeoutError::Timeout) => {}
+                Err(channel::RecvTimeoutError::Disconnected) => break,
+            }
+
+            // The failures recorded in the meantime are covered by this check
+            while self.failures.try_recv().is_ok() {}
+
+            self.pool.check_health(&self.rt);
+        }
+
+        debug!(
+            "[{}] endpoints health checker is shutting down",
+            self.pool.chain_id
+        );
+    }
+}
+
+/// Check the health of an endpoint, which is reachable if its RPC and gRPC servers
+/// respond in time and its node is not catching up.
+async fn probe(endpoint: &Endpoint) -> Probe {
+    let probe = async {
+        let status = endpoint.rpc_client.status().await.ok()?;
+
+        if status.sync_info.catching_up {
+            return None;
+        }
+
+        tonic::transport::Endpoint::from(endpoint.grpc_addr.clone())
+            .connect()
+            .await
+            .ok()?;
+
+        Some(Probe::Reachable {
+            latest_height: status.sync_info.latest_block_height.value(),
+            peer_id: status.node_info.id,
+        })
+    };
+
+    tokio::time::timeout(HEALTH_CHECK_TIMEOUT, probe)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(Probe::Unreachable)
+}
+
+/// The outcome of a request to an endpoint, which tells whether the request failed
+/// because the endpoint could not be reached or could not serve it, as opposed to
+/// the request being rejected by the chain.
+pub trait RequestOutcome {
+    fn is_endpoint_failure(&self) -> bool;
+}
+
+impl<T> RequestOutcome for Result<T, Error> {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            Ok(_) => false,
+            Err(e) => match e.detail() {
+                ErrorDetail::Rpc(e) => is_rpc_failure(&e.source),
+                ErrorDetail::GrpcTransport(_) => true,
+                ErrorDetail::GrpcStatus(e) => is_grpc_failure(&e.status),
+                ErrorDetail::ChainNotCaughtUp(_) => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl<T> RequestOutcome for Result<T, tendermint_rpc::Error> {
+    fn is_endpoint_failure(&self) -> bool {
+        matches!(self, Err(e) if is_rpc_failure(e))
+    }
+}
+
+impl<T> RequestOutcome for Result<T, tonic::Status> {
+    fn is_endpoint_failure(&self) -> bool {
+        matches!(self, Err(status) if is_grpc_failure(status))
+    }
+}
+
+impl<T> RequestOutcome for Result<T, tonic::transport::Error> {
+    fn is_endpoint_failure(&self) -> bool {
+        self.is_err()
+    }
+}
+
+/// Any error but an error response to the request
+fn is_rpc_failure(e: &tendermint_rpc::Error) -> bool {
+    !matches!(e.detail(), tendermint_rpc::error::ErrorDetail::Response(_))
+}
+
+fn is_grpc_failure(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        tonic::Code::Unavailable | tonic::Code::DeadlineExceeded | tonic::Code::ResourceExhausted
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reachable(latest_height: u64) -> Probe {
+        Probe::Reachable {
+            latest_height,
+            peer_id: PeerId::new([0; 20]),
+        }
+    }
+
+    fn scores(state: &PoolState) -> Vec<u32> {
+        state.health.iter().map(|health| health.score).collect()
+    }
+
+    #[test]
+    fn healthy_endpoints_keep_max_score() {
+        let mut state = PoolState::new(2);
+
+        assert_eq!(state.record_probes(&[reachable(10), reachable(9)], 5), None);
+        assert_eq!(scores(&state), vec![MAX_HEALTH_SCORE, MAX_HEALTH_SCORE]);
+        assert_eq!(state.health[1].peer_id, Some(PeerId::new([0; 20])));
+    }
+
+    #[test]
+    fn fail_over_to_healthiest_endpoint() {
+        let mut state = PoolState::new(3);
+
+        // A single failed check does not fail over
+        let probes = [Probe::Unreachable, reachable(20), reachable(10)];
+        assert_eq!(state.record_probes(&probes, 5), None);
+        assert_eq!(scores(&state), vec![5, 10, 8]);
+
+        // The first endpoint with the highest score becomes active
+        state.record_failure();
+        assert_eq!(state.failover_target(), Some(1));
+
+        state.active = 1;
+        state.health[1].score = 0;
+        state.health[2].score = 0;
+        assert_eq!(state.failover_target(), Some(0));
+    }
+
+    #[test]
+    fn keep_active_endpoint_if_none_is_healthier() {
+        let mut state = PoolState::new(2);
+
+        let probes = [Probe::Unreachable, Probe::Unreachable];
+        state.record_probes(&probes, 5);
+        assert_eq!(state.record_probes(&probes, 5), None);
+        assert_eq!(scores(&state), vec![2, 2]);
+    }
+
+    #[test]
+    fn failed_requests_lower_active_score() {
+        let mut state = PoolState::new(2);
+
+        for _ in 0..=MAX_HEALTH_SCORE - FAILOVER_HEALTH_SCORE {
+            assert_eq!(state.failover_target(), None);
+            state.record_failure();
+        }
+
+        assert_eq!(state.failover_target(), Some(1));
+    }
+
+    #[test]
+    fn fail_over_on_disconnection_from_unreachable_endpoint() {
+        let mut state = PoolState::new(2);
+
+        // The failure recorded on disconnection, along with a failed check of the
+        // active endpoint, is enough to fail over
+        state.record_failure();
+        assert_eq!(
+            state.record_probes(&[Probe::Unreachable, reachable(10)], 5),
+            Some(1)
+        );
+
+        // While a disconnection from a reachable endpoint is not
+        let mut state = PoolState::new(2);
+
+        state.record_failure();
+        assert_eq!(
+            state.record_probes(&[reachable(10), reachable(10)], 5),
+            None
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Accounting of the fees spent on a chain against its fee budget.
+//!
+//! The fees are accounted for per denomination, such that the fees paid in the
+//! denomination of a fee policy are capped as well as those paid in the
+//! denomination of the chain's gas price. No fee may be paid in a denomination
+//! the budget does not specify a maximum for.
+
+use alloc::collections::BTreeMap as HashMap;
+
+use ibc_proto::cosmos::base::v1beta1::Coin;
+
+use crate::config::FeeBudget;
+use crate::util::rate_limit::RateLimiter;
+
+/// The amount of a fee, in the given denomination, which exceeds what remains of the budget.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExceededBudget {
+    pub denom: String,
+    pub fee: u64,
+    pub remaining: u64,
+}
+
+/// The fees spent on a chain within the sliding window of its budget, per denomination,
+/// along with the fees of the txs which are signed but not broadcast yet.
+#[derive(Clone, Debug)]
+pub struct FeeBudgetTracker {
+    spent: HashMap<String, RateLimiter>,
+    reserved: HashMap<String, u64>,
+}
+
+impl FeeBudgetTracker {
+    pub fn new(budget: &FeeBudget, gas_price_denom: &str) -> Self {
+        let spent = budget
+            .max_fees_per_denom
+            .iter()
+            .map(|(denom, max_fees)| (denom.clone(), *max_fees))
+            .chain(Some((gas_price_denom.to_string(), budget.max_fees)))
+            .map(|(denom, max_fees)| (denom, RateLimiter::new(max_fees, budget.window)))
+            .collect();
+
+        Self {
+            spent,
+            reserved: HashMap::new(),
+        }
+    }
+
+    /// Fails with the first amount of the given fee which exceeds what remains
+    /// of the budget for its denomination within the current window, once the
+    /// reserved fees are deducted.
+    pub fn check(&mut self, fee: &[Coin]) -> Result<(), ExceededBudget> {
+        for (denom, amount) in amounts(fee) {
+            let reserved = self.reserved.get(&denom).copied().unwrap_or(0);
+            let remaining = self
+                .spent
+                .get_mut(&denom)
+                .map_or(0, |spent| spent.remaining().saturating_sub(reserved));
+
+            if amount > remaining {
+                return Err(ExceededBudget {
+                    denom,
+                    fee: amount,
+                    remaining,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the given fee as spent now.
+    pub fn record(&mut self, fee: &[Coin]) {
+        for (denom, amount) in amounts(fee) {
+            if let Some(spent) = self.spent.get_mut(&denom) {
+                spent.record(amount);
+            }
+        }
+    }
+
+    /// Reserve the given fee of a signed tx until it is broadcast, such that
+    /// the txs signed meanwhile are checked against what remains of the budget.
+    pub fn reserve(&mut self, fee: &[Coin]) {
+        for (denom, amount) in amounts(fee) {
+            if self.spent.contains_key(&denom) {
+                let reserved = self.reserved.entry(denom).or_insert(0);
+                *reserved = reserved.saturating_add(amount);
+            }
+        }
+    }
+
+    /// Release the reservation of the given fee, once the tx paying it
+    /// is broadcast, or will not be.
+    pub fn release(&mut self, fee: &[Coin]) {
+        for (denom, amount) in amounts(fee) {
+            if let Some(reserved) = self.reserved.get_mut(&denom) {
+                *reserved = reserved.saturating_sub(amount);
+            }
+        }
+    }
+}
+
+fn amounts(fee: &[Coin]) -> impl Iterator<Item = (String, u64)> + '_ {
+    fee.iter()
+        .map(|coin| (coin.denom.clone(), coin.amount.parse().unwrap_or(u64::MAX)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+
+    fn coin(amount: u64, denom: &str) -> Coin {
+        Coin {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    #[test]
+    fn fees_are_budgeted_per_denom() {
+        let budget = FeeBudget {
+            max_fees: 100,
+            max_fees_per_denom: vec![("uusdc".to_string(), 10)].into_iter().collect(),
+            window: Duration::from_secs(3600),
+        };
+        let mut tracker = FeeBudgetTracker::new(&budget, "stake");
+
+        tracker.record(&[coin(60, "stake")]);
+        assert_eq!(tracker.check(&[coin(40, "stake")]), Ok(()));
+        assert_eq!(
+            tracker.check(&[coin(41, "stake")]),
+            Err(ExceededBudget {
+                denom: "stake".to_string(),
+                fee: 41,
+                remaining: 40,
+            })
+        );
+
+        // The fees paid in the denomination of a fee policy are capped by their own budget
+        tracker.record(&[coin(8, "uusdc")]);
+        assert_eq!(tracker.check(&[coin(2, "uusdc")]), Ok(()));
+        assert!(tracker.check(&[coin(3, "uusdc")]).is_err());
+
+        // No fee may be paid in a denomination without a budget
+        assert_eq!(
+            tracker.check(&[coin(1, "uatom")]),
+            Err(ExceededBudget {
+                denom: "uatom".to_string(),
+                fee: 1,
+                remaining: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn reserved_fees_are_not_spent() {
+        let budget = FeeBudget {
+            max_fees: 100,
+            max_fees_per_denom: Default::default(),
+            window: Duration::from_secs(3600),
+        };
+        let mut tracker = FeeBudgetTracker::new(&budget, "stake");
+
+        tracker.reserve(&[coin(60, "stake")]);
+        assert!(tracker.check(&[coin(41, "stake")]).is_err());
+
+        // The tx was rejected, its fee is available again
+        tracker.release(&[coin(60, "stake")]);
+        assert_eq!(tracker.check(&[coin(100, "stake")]), Ok(()));
+
+        // The tx was accepted, its fee is spent
+        tracker.reserve(&[coin(60, "stake")]);
+        tracker.release(&[coin(60, "stake")]);
+        tracker.record(&[coin(60, "stake")]);
+        assert_eq!(tracker.check(&[coin(40, "stake")]), Ok(()));
+        assert!(tracker.check(&[coin(41, "stake")]).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Reuse of the gas estimates of simulated transactions when they are resent.
+//!
+//! The estimates are recorded per batch of messages, identified by the type and
+//! the encoding of each of its messages, such that sending a recently simulated
+//! batch again, e.g. after an account sequence mismatch or after its transaction
+//! was rejected, reuses its estimate instead of simulating it again.
+//!
+//! This does not reduce the number of simulations of the transactions sent for the
+//! first time, which always carry other messages, e.g. fresh proofs. Batches which
+//! merely have the same shape as a simulated one are simulated, since the gas used
+//! by a message depends on its contents, e.g. on the size of the header of a client
+//! update, and since only the simulation detects the messages which fail, and which
+//! are then dropped from their batch.
+
+use alloc::collections::BTreeMap as HashMap;
+use core::hash::{Hash, Hasher};
+use core::time::Duration;
+use std::collections::hash_map::DefaultHasher;
+use std::time::Instant;
+
+use prost_types::Any;
+
+/// The digest of the type and encoding of the messages of a batch.
+type BatchDigest = u64;
+
+#[derive(Clone, Debug)]
+struct CachedEstimate {
+    gas: u64,
+    estimated_at: Instant,
+}
+
+/// The gas estimates of recently simulated batches of messages, which expire
+/// after the given time to live.
+#[derive(Clone, Debug)]
+pub struct ResendGasEstimates {
+    ttl: Duration,
+    estimates: HashMap<BatchDigest, CachedEstimate>,
+}
+
+impl ResendGasEstimates {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            estimates: HashMap::new(),
+        }
+    }
+
+    /// The recorded gas estimate for a batch of the same messages as the given ones, if any
+    /// was recorded within the time to live.
+    pub fn get(&self, msgs: &[Any]) -> Option<u64> {
+        self.estimates
+            .get(&batch_digest(msgs))
+            .filter(|estimate| estimate.estimated_at.elapsed() < self.ttl)
+            .map(|estimate| estimate.gas)
+    }
+
+    /// Record the gas used by the simulation of a transaction carrying the given messages,
+    /// and evict the estimates which have expired.
+    pub fn record(&mut self, msgs: &[Any], gas: u64) {
+        let ttl = self.ttl;
+        self.estimates
+            .retain(|_, estimate| estimate.estimated_at.elapsed() < ttl);
+
+        let estimate = CachedEstimate {
+            gas,
+            estimated_at: Instant::now(),
+        };
+
+        self.estimates.insert(batch_digest(msgs), estimate);
+    }
+}
+
+fn batch_digest(msgs: &[Any]) -> BatchDigest {
+    let mut hasher = DefaultHasher::new();
+
+    for msg in msgs {
+        msg.type_url.hash(&mut hasher);
+        msg.value.hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(type_url: &str, value: &[u8]) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: value.to_vec(),
+        }
+    }
+
+    #[test]
+    fn estimates_per_batch() {
+        let mut cache = ResendGasEstimates::new(Duration::from_secs(60));
+
+        let update = msg("/ibc.core.client.v1.MsgUpdateClient", &[1; 32]);
+        let recv_1 = msg("/ibc.core.channel.v1.MsgRecvPacket", &[1]);
+        let recv_2 = msg("/ibc.core.channel.v1.MsgRecvPacket", &[2]);
+
+        cache.record(&[update.clone(), recv_1.clone(), recv_2.clone()], 250_000);
+
+        assert_eq!(
+            cache.get(&[update.clone(), recv_1.clone(), recv_2.clone()]),
+            Some(250_000)
+        );
+
+        // Batches of the same shape carrying other messages are not estimated
+        assert_eq!(cache.get(&[update.clone(), recv_2.clone(), recv_1]), None);
+        assert_eq!(cache.get(&[update, recv_2.clone(), recv_2.clone()]), None);
+
+        let larger_update = msg("/ibc.core.client.v1.MsgUpdateClient", &[1; 64]);
+        assert_eq!(cache.get(&[larger_update, recv_2.clone(), recv_2]), None);
+    }
+
+    #[test]
+    fn estimates_expire() {
+        let mut cache = ResendGasEstimates::new(Duration::from_secs(0));
+        let recv = msg("/ibc.core.channel.v1.MsgRecvPacket", &[1]);
+
+        cache.record(&[recv.clone()], 100_000);
+
+        assert_eq!(cache.get(&[recv]), None);
+    }
+
+    #[test]
+    fn expired_estimates_are_evicted() {
+        let mut cache = ResendGasEstimates::new(Duration::from_secs(0));
+
+        cache.record(&[msg("/ibc.core.channel.v1.MsgRecvPacket", &[1])], 100_000);
+        cache.record(&[msg("/ibc.core.channel.v1.MsgRecvPacket", &[2])], 100_000);
+
+        assert_eq!(cache.estimates.len(), 1);
+    }
+}
@@ -0,0 +1,225 @@
+//! Queries of the current gas price of chains running a fee market module.
+
+use core::time::Duration;
+use std::time::Instant;
+
+use prost::Message;
+
+use crate::config::{GasPrice, GasPriceQuery};
+use crate::error::Error;
+
+/// Number of decimal places of the `sdk.Dec` type
+const DEC_PRECISION: i32 = 18;
+
+#[derive(Clone, PartialEq, Message)]
+struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    price: Option<DecCoin>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct DecCoin {
+    #[prost(string, tag = "1")]
+    denom: String,
+    #[prost(string, tag = "2")]
+    amount: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct BaseFeeRequest {}
+
+#[derive(Clone, PartialEq, Message)]
+struct BaseFeeResponse {
+    #[prost(string, tag = "1")]
+    base_fee: String,
+}
+
+/// A gas price obtained from the chain, along with the time it was obtained at.
+#[derive(Clone, Debug)]
+pub struct CachedGasPrice {
+    pub price: GasPrice,
+    pub fetched_at: Instant,
+}
+
+/// The last gas price obtained from the chain, along with the time of the
+/// last attempt at querying it, whether it succeeded or not.
+#[derive(Clone, Debug, Default)]
+pub struct GasPriceCache {
+    cached: Option<CachedGasPrice>,
+    last_attempt: Option<Instant>,
+}
+
+impl GasPriceCache {
+    /// Whether the gas price should be queried again, i.e. whether the last
+    /// attempt at querying it was made more than `refresh_interval` ago.
+    pub fn needs_refresh(&self, refresh_interval: Duration, now: Instant) -> bool {
+        self.last_attempt.map_or(true, |at| {
+            now.saturating_duration_since(at) >= refresh_interval
+        })
+    }
+
+    pub fn record_success(&mut self, price: GasPrice, now: Instant) {
+        self.cached = Some(CachedGasPrice {
+            price,
+            fetched_at: now,
+        });
+        self.last_attempt = Some(now);
+    }
+
+    pub fn record_failure(&mut self, now: Instant) {
+        self.last_attempt = Some(now);
+    }
+
+    /// The last gas price obtained from the chain, unless it was obtained
+    /// more than `max_age` ago.
+    pub fn price(&self, max_age: Duration, now: Instant) -> Option<&GasPrice> {
+        self.cached
+            .as_ref()
+            .filter(|cached| now.saturating_duration_since(cached.fetched_at) < max_age)
+            .map(|cached| &cached.price)
+    }
+}
+
+/// Encode the request of the given query for the gas price in the given denomination.
+pub fn encode_request(query: GasPriceQuery, denom: &str) -> Vec<u8> {
+    match query {
+        GasPriceQuery::Feemarket => GasPriceRequest {
+            denom: denom.to_string(),
+        }
+        .encode_to_vec(),
+        GasPriceQuery::EthermintBaseFee => BaseFeeRequest {}.encode_to_vec(),
+    }
+}
+
+/// Decode the gas price in the given denomination from the response of the given query.
+pub fn decode_response(query: GasPriceQuery, denom: &str, bytes: &[u8]) -> Result<f64, Error> {
+    match query {
+        GasPriceQuery::Feemarket => {
+            let response = GasPriceResponse::decode(bytes)
+                .map_err(|e| Error::protobuf_decode("GasPriceResponse".to_string(), e))?;
+
+            let price = response
+                .price
+                .ok_or_else(|| Error::dynamic_gas_price("missing gas price".to_string()))?;
+
+            if price.denom != denom {
+                return Err(Error::dynamic_gas_price(format!(
+                    "expected gas price in {}, got {}",
+                    denom, price.denom
+                )));
+            }
+
+            parse_dec(&price.amount)
+        }
+        GasPriceQuery::EthermintBaseFee => {
+            let response = BaseFeeResponse::decode(bytes)
+                .map_err(|e| Error::protobuf_decode("BaseFeeResponse".to_string(), e))?;
+
+            parse_amount(&response.base_fee)
+        }
+    }
+}
+
+/// Parse an `sdk.Dec`, which is encoded as the integer value of the
+/// decimal number multiplied by 10^18 (or as a decimal number by some chains).
+fn parse_dec(value: &str) -> Result<f64, Error> {
+    if value.contains('.') {
+        parse_amount(value)
+    } else {
+        parse_amount(value).map(|amount| amount / 10f64.powi(DEC_PRECISION))
+    }
+}
+
+fn parse_amount(value: &str) -> Result<f64, Error> {
+    let amount = value
+        .parse::<f64>()
+        .map_err(|e| Error::dynamic_gas_price(format!("invalid amount '{}': {}", value, e)))?;
+
+    if !amount.is_finite() || amount < 0.0 {
+        return Err(Error::dynamic_gas_price(format!(
+            "invalid amount '{}'",
+            value
+        )));
+    }
+
+    Ok(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_feemarket_gas_price() {
+        let response = GasPriceResponse {
+            price: Some(DecCoin {
+                denom: "uatom".to_string(),
+                amount: "25000000000000000".to_string(),
+            }),
+        }
+        .encode_to_vec();
+
+        let price = decode_response(GasPriceQuery::Feemarket, "uatom", &response).unwrap();
+        assert!((price - 0.025).abs() < f64::EPSILON);
+
+        assert!(decode_response(GasPriceQuery::Feemarket, "stake", &response).is_err());
+    }
+
+    #[test]
+    fn decode_ethermint_base_fee() {
+        let response = BaseFeeResponse {
+            base_fee: "1000000000".to_string(),
+        }
+        .encode_to_vec();
+
+        let price = decode_response(GasPriceQuery::EthermintBaseFee, "aevmos", &response).unwrap();
+        assert!((price - 1_000_000_000.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn failed_refresh_waits_for_refresh_interval() {
+        let refresh_interval = Duration::from_secs(30);
+        let start = Instant::now();
+
+        let mut cache = GasPriceCache::default();
+        assert!(cache.needs_refresh(refresh_interval, start));
+
+        cache.record_failure(start);
+        assert!(!cache.needs_refresh(refresh_interval, start + Duration::from_secs(1)));
+        assert!(cache.needs_refresh(refresh_interval, start + refresh_interval));
+        assert!(cache.price(Duration::from_secs(300), start).is_none());
+    }
+
+    #[test]
+    fn stale_price_expires_after_max_age() {
+        let max_age = Duration::from_secs(300);
+        let start = Instant::now();
+
+        let mut cache = GasPriceCache::default();
+        cache.record_success(GasPrice::new(0.025, "uatom".to_string()), start);
+
+        // Later refreshes fail, the last price is used until it is too old
+        cache.record_failure(start + Duration::from_secs(60));
+        assert_eq!(
+            cache.price(max_age, start + Duration::from_secs(60)),
+            Some(&GasPrice::new(0.025, "uatom".to_string()))
+        );
+
+        cache.record_failure(start + max_age);
+        assert!(cache.price(max_age, start + max_age).is_none());
+    }
+
+    #[test]
+    fn parse_dec_values() {
+        assert!((parse_dec("1500000000000000000").unwrap() - 1.5).abs() < f64::EPSILON);
+        assert!((parse_dec("0.0025").unwrap() - 0.0025).abs() < f64::EPSILON);
+        assert!(parse_dec("-1").is_err());
+        assert!(parse_dec("abc").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+//! Dispatch of transactions across a pool of signing keys.
+//!
+//! Each key of the pool signs with its own account, and thus its own sequence
+//! number, so that transactions signed by different keys do not contend for the
+//! same account sequence. Only batches made up of messages whose signer can be
+//! replaced, i.e. packet relaying messages and client updates, are dispatched
+//! across the pool. Other batches, such as handshake messages, are always signed
+//! by the primary key.
+//!
+//! The transactions of the requests queued at the chain runtime at the same time
+//! are signed in turn, each request with a single key, then the transactions
+//! signed with different keys are broadcast concurrently.
+
+use prost::Message;
+use prost_types::Any;
+
+use ibc::core::ics02_client::msgs::update_client;
+use ibc::core::ics04_channel::msgs::{acknowledgement, recv_packet, timeout, timeout_on_close};
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose,
+};
+use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+
+use crate::error::Error;
+
+/// A pool of signing keys, the first of which is the primary key.
+#[derive(Clone, Debug)]
+pub struct KeyPool {
+    key_names: Vec<String>,
+    next: usize,
+}
+
+impl KeyPool {
+    pub fn new(primary: String, others: impl IntoIterator<Item = String>) -> Self {
+        let mut key_names = vec![primary];
+
+        for key_name in others {
+            if !key_names.contains(&key_name) {
+                key_names.push(key_name);
+            }
+        }
+
+        Self { key_names, next: 0 }
+    }
+
+    pub fn primary(&self) -> &str {
+        &self.key_names[0]
+    }
+
+    pub fn key_names(&self) -> &[String] {
+        &self.key_names
+    }
+
+    /// Select the key to sign a batch of messages with: the keys of the pool are
+    /// used in turn if all the messages support a change of signer, otherwise
+    /// the primary key is used.
+    pub fn next_key(&mut self, msgs: &[Any]) -> &str {
+        if self.key_names.len() == 1 || !msgs.iter().all(supports_signer_change) {
+            return self.primary();
+        }
+
+        let index = self.next;
+        self.next = (self.next + 1) % self.key_names.len();
+
+        &self.key_names[index]
+    }
+}
+
+fn supports_signer_change(msg: &Any) -> bool {
+    matches!(
+        msg.type_url.as_str(),
+        update_client::TYPE_URL
+            | recv_packet::TYPE_URL
+            | acknowledgement::TYPE_URL
+            | timeout::TYPE_URL
+            | timeout_on_close::TYPE_URL
+    )
+}
+
+/// Replace the signer of a message which supports a change of signer.
+/// Other messages are returned unchanged.
+pub fn with_signer(msg: Any, signer: &str) -> Result<Any, Error> {
+    fn replace<M: Message + Default>(
+        msg: Any,
+        set_signer: impl FnOnce(&mut M),
+    ) -> Result<Any, Error> {
+        let mut raw = M::decode(msg.value.as_slice())
+            .map_err(|e| Error::protobuf_decode(msg.type_url.clone(), e))?;
+        set_signer(&mut raw);
+
+        let mut value = Vec::new();
+        raw.encode(&mut value)
+            .map_err(|e| Error::protobuf_encode(msg.type_url.clone(), e))?;
+
+        Ok(Any {
+            type_url: msg.type_url,
+            value,
+        })
+    }
+
+    let signer = signer.to_string();
+
+    match msg.type_url.as_str() {
+        update_client::TYPE_URL => replace(msg, |m: &mut MsgUpdateClient| m.signer = signer),
+        recv_packet::TYPE_URL => replace(msg, |m: &mut MsgRecvPacket| m.signer = signer),
+        acknowledgement::TYPE_URL => replace(msg, |m: &mut MsgAcknowledgement| m.signer = signer),
+        timeout::TYPE_URL => replace(msg, |m: &mut MsgTimeout| m.signer = signer),
+        timeout_on_close::TYPE_URL => replace(msg, |m: &mut MsgTimeoutOnClose| m.signer = signer),
+        _ => Ok(msg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc::core::ics03_connection::msgs::conn_open_init;
+
+    fn recv_packet(signer: &str) -> Any {
+        let raw = MsgRecvPacket {
+            signer: signer.to_string(),
+            ..Default::default()
+        };
+
+        Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: raw.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn round_robin_over_pool() {
+        let mut pool = KeyPool::new(
+            "primary".to_string(),
+            vec!["a".to_string(), "primary".to_string(), "b".to_string()],
+        );
+        assert_eq!(pool.key_names(), ["primary", "a", "b"]);
+
+        let msgs = vec![recv_packet("cosmos1primary")];
+        let keys = (0..6)
+            .map(|_| pool.next_key(&msgs).to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(keys, ["primary", "a", "b", "primary", "a", "b"]);
+    }
+
+    #[test]
+    fn primary_key_for_unsupported_messages() {
+        let mut pool = KeyPool::new("primary".to_string(), vec!["a".to_string()]);
+
+        let handshake = Any {
+            type_url: conn_open_init::TYPE_URL.to_string(),
+            value: vec![],
+        };
+        let msgs = vec![recv_packet("cosmos1primary"), handshake];
+
+        for _ in 0..3 {
+            assert_eq!(pool.next_key(&msgs), "primary");
+        }
+    }
+
+    #[test]
+    fn single_key_pool() {
+        let mut pool = KeyPool::new("primary".to_string(), vec![]);
+        let msgs = vec![recv_packet("cosmos1primary")];
+
+        for _ in 0..3 {
+            assert_eq!(pool.next_key(&msgs), "primary");
+        }
+    }
+
+    #[test]
+    fn replace_signer() {
+        let msg = with_signer(recv_packet("cosmos1primary"), "cosmos1other").unwrap();
+
+        assert_eq!(msg.type_url, recv_packet::TYPE_URL);
+        assert_eq!(
+            MsgRecvPacket::decode(msg.value.as_slice()).unwrap().signer,
+            "cosmos1other"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Dry-run mode, in which the chain runtimes simulate the transactions
+//! carrying the messages they are asked to submit, instead of broadcasting
+//! them, and report the messages along with the gas they would use.
+//!
+//! The mode applies to all the chain runtimes of the process, and is meant to
+//! be enabled once, before any of them is spawned.
+
+use once_cell::sync::OnceCell;
+use prost_types::Any;
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+type Reporter = Box<dyn Fn(DryRunTx) + Send + Sync>;
+
+static REPORTER: OnceCell<Reporter> = OnceCell::new();
+
+/// Enable the dry-run mode for all the chain runtimes of the process, which
+/// pass each transaction they simulate to the given reporter.
+pub fn enable(reporter: impl Fn(DryRunTx) + Send + Sync + 'static) {
+    // The mode is enabled at most once, with the first reporter
+    let _ = REPORTER.set(Box::new(reporter));
+}
+
+/// Returns true if the dry-run mode is enabled.
+pub fn is_enabled() -> bool {
+    REPORTER.get().is_some()
+}
+
+/// Report the given transaction, which was simulated instead of submitted.
+pub fn report(tx: DryRunTx) {
+    if let Some(reporter) = REPORTER.get() {
+        reporter(tx);
+    }
+}
+
+/// A transaction which would have been submitted to a chain, had the
+/// dry-run mode been disabled.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunTx {
+    pub chain_id: ChainId,
+    /// The gas used by the simulation of the transaction, if it succeeded
+    pub estimated_gas: Option<u64>,
+    pub messages: Vec<DryRunMsg>,
+}
+
+/// A message of a [`DryRunTx`], whose protobuf encoding is hex-encoded.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunMsg {
+    pub type_url: String,
+    pub value: String,
+}
+
+impl DryRunTx {
+    pub fn new(chain_id: ChainId, msgs: &[Any], estimated_gas: Option<u64>) -> Self {
+        let messages = msgs
+            .iter()
+            .map(|msg| DryRunMsg {
+                type_url: msg.type_url.clone(),
+                value: hex::encode(&msg.value),
+            })
+            .collect();
+
+        Self {
+            chain_id,
+            estimated_gas,
+            messages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_dry_run_tx() {
+        let msg = Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: vec![0x0a, 0xff],
+        };
+
+        let tx = DryRunTx::new(ChainId::new("ibc".to_string(), 0), &[msg], Some(120_000));
+
+        assert_eq!(
+            serde_json::to_string(&tx).unwrap(),
+            r#"{"chain_id":"ibc-0","estimated_gas":120000,"messages":[{"type_url":"/ibc.core.client.v1.MsgUpdateClient","value":"0aff"}]}"#
+        );
+    }
+}
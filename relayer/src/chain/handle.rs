@@ -46,8 +46,8 @@ pub use prod::ProdChainHandle;
 
 use crate::{
     chain::handle::requests::AppVersion,
-    chain::StatusResponse,
-    config::ChainConfig,
+    chain::{CheckTxResponses, StatusResponse},
+    config::{ChainConfig, FeePolicy},
     connection::ConnectionMsgType,
     error::Error,
     event::monitor::{EventBatch, Result as MonitorResult},
@@ -110,14 +110,27 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Subscription>,
     },
 
+    ResumeEventsFrom {
+        height: Height,
+        reply_to: ReplyTo<()>,
+    },
+
     SendMessagesAndWaitCommit {
         proto_msgs: Vec<prost_types::Any>,
+        fee_policy: Option<FeePolicy>,
         reply_to: ReplyTo<Vec<IbcEvent>>,
     },
 
     SendMessagesAndWaitCheckTx {
         proto_msgs: Vec<prost_types::Any>,
-        reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
+        fee_policy: Option<FeePolicy>,
+        reply_to: ReplyTo<CheckTxResponses>,
+    },
+
+    SendTransactionsAndWaitCheckTx {
+        txs: Vec<Vec<prost_types::Any>>,
+        fee_policy: Option<FeePolicy>,
+        reply_to: ReplyTo<Vec<Result<CheckTxResponses, Error>>>,
     },
 
     Config {
@@ -346,21 +359,56 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug {
     /// Subscribe to the events emitted by the chain.
     fn subscribe(&self) -> Result<Subscription, Error>;
 
+    /// Collect the events of the blocks committed after the given height, at which
+    /// the events were last processed before a restart, once subscribed to the events
+    /// emitted by the chain. Must be called before subscribing to have any effect.
+    fn resume_events_from(&self, height: Height) -> Result<(), Error>;
+
     /// Send the given `msgs` to the chain, packaged as one or more transactions,
     /// and return the list of events emitted by the chain after the transaction was committed.
     fn send_messages_and_wait_commit(
         &self,
         proto_msgs: Vec<prost_types::Any>,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.send_messages_and_wait_commit_with_fee_policy(proto_msgs, None)
+    }
+
+    /// Same as [`ChainHandle::send_messages_and_wait_commit`], but pays the fees of the
+    /// transactions according to the given fee policy instead of the chain's default one.
+    fn send_messages_and_wait_commit_with_fee_policy(
+        &self,
+        proto_msgs: Vec<prost_types::Any>,
+        fee_policy: Option<FeePolicy>,
     ) -> Result<Vec<IbcEvent>, Error>;
 
     /// Submit messages asynchronously.
     /// Does not block waiting on the chain to produce the
     /// resulting events. Instead of events, this method
-    /// returns a set of transaction hashes.
+    /// returns the responses of the transactions, which
+    /// carry their hashes, along with the error events of
+    /// the messages dropped from them.
     fn send_messages_and_wait_check_tx(
         &self,
         proto_msgs: Vec<prost_types::Any>,
-    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error>;
+    ) -> Result<CheckTxResponses, Error> {
+        self.send_messages_and_wait_check_tx_with_fee_policy(proto_msgs, None)
+    }
+
+    /// Same as [`ChainHandle::send_messages_and_wait_check_tx`], but pays the fees of the
+    /// transactions according to the given fee policy instead of the chain's default one.
+    fn send_messages_and_wait_check_tx_with_fee_policy(
+        &self,
+        proto_msgs: Vec<prost_types::Any>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<CheckTxResponses, Error>;
+
+    /// Submit a transaction carrying each of the given batches of messages as is, all of
+    /// them signed with the same key. Returns the result of each transaction, in order.
+    fn send_transactions_and_wait_check_tx(
+        &self,
+        txs: Vec<Vec<prost_types::Any>>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Vec<Result<CheckTxResponses, Error>>, Error>;
 
     fn get_signer(&self) -> Result<Signer, Error>;
 
@@ -38,8 +38,12 @@ use ibc_proto::ibc::core::connection::v1::QueryClientConnectionsRequest;
 use ibc_proto::ibc::core::connection::v1::QueryConnectionsRequest;
 
 use crate::{
-    chain::handle::requests::AppVersion, chain::StatusResponse, config::ChainConfig,
-    connection::ConnectionMsgType, error::Error, keyring::KeyEntry,
+    chain::handle::requests::AppVersion,
+    chain::{CheckTxResponses, StatusResponse},
+    config::{ChainConfig, FeePolicy},
+    connection::ConnectionMsgType,
+    error::Error,
+    keyring::KeyEntry,
 };
 
 use super::{reply_channel, ChainHandle, ChainRequest, HealthCheck, ReplyTo, Subscription};
@@ -61,6 +65,11 @@ impl ProdChainHandle {
         }
     }
 
+    /// The sender of the requests to the chain runtime
+    pub fn sender(&self) -> &channel::Sender<ChainRequest> {
+        &self.runtime_sender
+    }
+
     fn send<F, O>(&self, f: F) -> Result<O, Error>
     where
         F: FnOnce(ReplyTo<O>) -> ChainRequest,
@@ -96,22 +105,42 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::Subscribe { reply_to })
     }
 
-    fn send_messages_and_wait_commit(
+    fn resume_events_from(&self, height: Height) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::ResumeEventsFrom { height, reply_to })
+    }
+
+    fn send_messages_and_wait_commit_with_fee_policy(
         &self,
         proto_msgs: Vec<prost_types::Any>,
+        fee_policy: Option<FeePolicy>,
     ) -> Result<Vec<IbcEvent>, Error> {
         self.send(|reply_to| ChainRequest::SendMessagesAndWaitCommit {
             proto_msgs,
+            fee_policy,
             reply_to,
         })
     }
 
-    fn send_messages_and_wait_check_tx(
+    fn send_messages_and_wait_check_tx_with_fee_policy(
         &self,
         proto_msgs: Vec<prost_types::Any>,
-    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<CheckTxResponses, Error> {
         self.send(|reply_to| ChainRequest::SendMessagesAndWaitCheckTx {
             proto_msgs,
+            fee_policy,
+            reply_to,
+        })
+    }
+
+    fn send_transactions_and_wait_check_tx(
+        &self,
+        txs: Vec<Vec<prost_types::Any>>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Vec<Result<CheckTxResponses, Error>>, Error> {
+        self.send(|reply_to| ChainRequest::SendTransactionsAndWaitCheckTx {
+            txs,
+            fee_policy,
             reply_to,
         })
     }
@@ -1,12 +1,18 @@
 use alloc::sync::Arc;
 use core::ops::Add;
 use core::time::Duration;
+use std::thread;
 
 use crossbeam_channel as channel;
 use prost_types::Any;
+use tendermint::abci::transaction::Hash;
+use tendermint::abci::Code;
+use tendermint_rpc::endpoint::broadcast::tx_sync;
 use tendermint_testgen::light_block::TmLightBlock;
 use tokio::runtime::Runtime;
+use tracing::debug;
 
+use ibc::applications::ics20_fungible_token_transfer::PORT_ID;
 use ibc::clients::ics07_tendermint::client_state::{
     AllowUpdate, ClientState as TendermintClientState,
 };
@@ -14,15 +20,20 @@ use ibc::clients::ics07_tendermint::consensus_state::ConsensusState as Tendermin
 use ibc::clients::ics07_tendermint::header::Header as TendermintHeader;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
+use ibc::core::ics02_client::context::ClientReader;
+use ibc::core::ics02_client::events::NewBlock;
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd};
-use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
+use ibc::core::ics03_connection::context::ConnectionReader;
+use ibc::core::ics04_channel::channel::{
+    ChannelEnd, IdentifiedChannelEnd, Order, QueryPacketEventDataRequest,
+};
 use ibc::core::ics04_channel::context::ChannelReader;
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::core::ics04_channel::Version;
 use ibc::core::ics23_commitment::{commitment::CommitmentPrefix, specs::ProofSpecs};
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::downcast;
-use ibc::events::IbcEvent;
+use ibc::events::{IbcEvent, WithBlockDataType};
 use ibc::mock::context::MockContext;
 use ibc::mock::host::HostType;
 use ibc::query::{QueryBlockRequest, QueryTxRequest};
@@ -42,13 +53,15 @@ use ibc_proto::ibc::core::connection::v1::{
     QueryClientConnectionsRequest, QueryConnectionsRequest,
 };
 
-use crate::chain::{handle::requests::AppVersion, ChainEndpoint, StatusResponse};
-use crate::config::ChainConfig;
+use crate::chain::dry_run::{self, DryRunTx};
+use crate::chain::{handle::requests::AppVersion, ChainEndpoint, CheckTxResponses, StatusResponse};
+use crate::config::{ChainConfig, FeePolicy};
 use crate::error::Error;
-use crate::event::monitor::{EventReceiver, EventSender, TxMonitorCmd};
-use crate::keyring::{KeyEntry, KeyRing};
+use crate::event::monitor::{EventBatch, EventReceiver, EventSender, TxMonitorCmd};
+use crate::keyring::{KeyEntry, KeyRing, Store};
 use crate::light_client::Verified;
 use crate::light_client::{mock::LightClient as MockLightClient, LightClient};
+use crate::util::lock::{LockExt, RwArc};
 
 use super::HealthCheck;
 
@@ -56,21 +69,66 @@ use super::HealthCheck;
 /// The relayer runtime and the light client will engage with the MockChain to query/send tx; the
 /// primary interface for doing so is captured by `ICS18Context` which this struct can access via
 /// the `context` field.
+///
+/// If the `block_time` of its configuration is set, the chain produces a new block at that
+/// interval, and reports it with a `NewBlock` event. Otherwise, it only produces a new block
+/// for each message it is sent.
 pub struct MockChain {
     config: ChainConfig,
-    context: MockContext,
+    context: RwArc<MockContext>,
+    keybase: KeyRing,
+    txs: Vec<MockTx>,
 
     // keep a reference to event sender to prevent it from being dropped
     _event_sender: EventSender,
     event_receiver: EventReceiver,
 }
 
+/// A transaction sent to a mock chain, along with the events it emitted.
+struct MockTx {
+    hash: Hash,
+    events: Vec<IbcEvent>,
+}
+
 impl MockChain {
     fn trusting_period(&self) -> Duration {
         self.config
             .trusting_period
             .unwrap_or_else(|| Duration::from_secs(14 * 24 * 60 * 60)) // 14 days
     }
+
+    /// Submits the given messages to the context, and records them as a new transaction.
+    fn send_tx(&mut self, proto_msgs: Vec<Any>) -> Result<&MockTx, Error> {
+        // Use the ICS18Context interface to submit the set of messages.
+        let events = self
+            .context
+            .acquire_write()
+            .send(proto_msgs)
+            .map_err(Error::ics18)?;
+
+        // The hash of a mock transaction is derived from its index
+        let mut hash = [0; 32];
+        hash[24..].copy_from_slice(&(self.txs.len() as u64).to_be_bytes());
+
+        self.txs.push(MockTx {
+            hash: Hash::new(hash),
+            events,
+        });
+
+        Ok(&self.txs[self.txs.len() - 1])
+    }
+
+    /// Returns the port and channel identifiers of the given query request.
+    fn port_channel_id(port_id: &str, channel_id: &str) -> Result<(PortId, ChannelId), Error> {
+        let port_id = port_id
+            .parse()
+            .map_err(|_| Error::query(format!("invalid port identifier {}", port_id)))?;
+        let channel_id = channel_id
+            .parse()
+            .map_err(|_| Error::query(format!("invalid channel identifier {}", channel_id)))?;
+
+        Ok((port_id, channel_id))
+    }
 }
 
 impl ChainEndpoint for MockChain {
@@ -82,14 +140,30 @@ impl ChainEndpoint for MockChain {
 
     fn bootstrap(config: ChainConfig, _rt: Arc<Runtime>) -> Result<Self, Error> {
         let (sender, receiver) = channel::unbounded();
+
+        let keybase = KeyRing::new(Store::Memory, &config.account_prefix, &config.id)
+            .map_err(Error::key_base)?;
+
+        let context = RwArc::new_lock(MockContext::new(
+            config.id.clone(),
+            HostType::SyntheticTendermint,
+            50,
+            Height::new(config.id.version(), 20),
+        ));
+
+        if let Some(block_time) = config.block_time {
+            let chain_id = config.id.clone();
+            let context = context.clone();
+            let sender = sender.clone();
+
+            thread::spawn(move || produce_blocks(chain_id, context, block_time, sender));
+        }
+
         Ok(MockChain {
-            config: config.clone(),
-            context: MockContext::new(
-                config.id.clone(),
-                HostType::SyntheticTendermint,
-                50,
-                Height::new(config.id.version(), 20),
-            ),
+            config,
+            context,
+            keybase,
+            txs: vec![],
             _event_sender: sender,
             event_receiver: receiver,
         })
@@ -102,6 +176,7 @@ impl ChainEndpoint for MockChain {
     fn init_event_monitor(
         &self,
         _rt: Arc<Runtime>,
+        _resume_from: Option<Height>,
     ) -> Result<(EventReceiver, TxMonitorCmd), Error> {
         let (tx, _) = crossbeam_channel::unbounded();
         Ok((self.event_receiver.clone(), tx))
@@ -120,28 +195,53 @@ impl ChainEndpoint for MockChain {
     }
 
     fn keybase(&self) -> &KeyRing {
-        unimplemented!()
+        &self.keybase
     }
 
     fn keybase_mut(&mut self) -> &mut KeyRing {
-        unimplemented!()
+        &mut self.keybase
     }
 
     fn send_messages_and_wait_commit(
         &mut self,
         proto_msgs: Vec<Any>,
+        _fee_policy: Option<&FeePolicy>,
     ) -> Result<Vec<IbcEvent>, Error> {
-        // Use the ICS18Context interface to submit the set of messages.
-        let events = self.context.send(proto_msgs).map_err(Error::ics18)?;
-
-        Ok(events)
+        let tx = self.send_tx(proto_msgs)?;
+        Ok(tx.events.clone())
     }
 
     fn send_messages_and_wait_check_tx(
         &mut self,
-        _proto_msgs: Vec<Any>,
-    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
-        todo!()
+        proto_msgs: Vec<Any>,
+        _fee_policy: Option<&FeePolicy>,
+    ) -> Result<CheckTxResponses, Error> {
+        // The messages are delivered right away, such that they are committed once checked
+        let tx = self.send_tx(proto_msgs)?;
+
+        Ok(CheckTxResponses {
+            responses: vec![tx_sync::Response {
+                code: Code::Ok,
+                data: Default::default(),
+                log: Default::default(),
+                hash: tx.hash,
+            }],
+            dropped_events: vec![],
+        })
+    }
+
+    fn simulate_messages(
+        &mut self,
+        proto_msgs: Vec<Any>,
+        _fee_policy: Option<&FeePolicy>,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        // Submit the messages to a copy of the context, which is then discarded
+        let mut context = self.context.acquire_read().clone();
+        let events = context.send(proto_msgs.clone()).map_err(Error::ics18)?;
+
+        dry_run::report(DryRunTx::new(self.id().clone(), &proto_msgs, None));
+
+        Ok(events)
     }
 
     fn get_signer(&mut self) -> Result<Signer, Error> {
@@ -153,21 +253,25 @@ impl ChainEndpoint for MockChain {
     }
 
     fn get_key(&mut self) -> Result<KeyEntry, Error> {
-        unimplemented!()
+        self.keybase
+            .get_key(&self.config.key_name)
+            .map_err(|e| Error::key_not_found(self.config.key_name.clone(), e))
     }
 
-    fn add_key(&mut self, _key_name: &str, _key: KeyEntry) -> Result<(), Error> {
-        unimplemented!()
+    fn add_key(&mut self, key_name: &str, key: KeyEntry) -> Result<(), Error> {
+        self.keybase.add_key(key_name, key).map_err(Error::key_base)
     }
 
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
-        unimplemented!()
+        Ok(self.context.acquire_read().commitment_prefix())
     }
 
     fn query_status(&self) -> Result<StatusResponse, Error> {
+        let context = self.context.acquire_read();
+
         Ok(StatusResponse {
-            height: self.context.host_height(),
-            timestamp: self.context.host_timestamp(),
+            height: context.host_height(),
+            timestamp: context.host_timestamp(),
         })
     }
 
@@ -175,7 +279,18 @@ impl ChainEndpoint for MockChain {
         &self,
         _request: QueryClientStatesRequest,
     ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
-        unimplemented!()
+        let context = self.context.acquire_read();
+
+        let clients = context
+            .client_ids()
+            .into_iter()
+            .filter_map(|client_id| {
+                let client_state = context.query_client_full_state(&client_id)?;
+                Some(IdentifiedAnyClientState::new(client_id, client_state))
+            })
+            .collect();
+
+        Ok(clients)
     }
 
     fn query_client_state(
@@ -186,6 +301,7 @@ impl ChainEndpoint for MockChain {
         // TODO: unclear what are the scenarios where we need to take height into account.
         let any_state = self
             .context
+            .acquire_read()
             .query_client_full_state(client_id)
             .ok_or_else(Error::empty_response_value)?;
         let client_state = downcast!(any_state.clone() => AnyClientState::Tendermint)
@@ -197,113 +313,334 @@ impl ChainEndpoint for MockChain {
         &self,
         _height: Height,
     ) -> Result<(Self::ClientState, MerkleProof), Error> {
-        unimplemented!()
+        // Mock chains are never upgraded
+        Err(Error::empty_upgraded_client_state())
     }
 
     fn query_connection(
         &self,
-        _connection_id: &ConnectionId,
+        connection_id: &ConnectionId,
         _height: Height,
     ) -> Result<ConnectionEnd, Error> {
-        unimplemented!()
+        ConnectionReader::connection_end(&*self.context.acquire_read(), connection_id)
+            .map_err(Error::ics03)
     }
 
     fn query_client_connections(
         &self,
-        _request: QueryClientConnectionsRequest,
+        request: QueryClientConnectionsRequest,
     ) -> Result<Vec<ConnectionId>, Error> {
-        unimplemented!()
+        let client_id = request
+            .client_id
+            .parse()
+            .map_err(|_| Error::query(format!("connections of client {}", request.client_id)))?;
+
+        let connection_id = self
+            .context
+            .acquire_read()
+            .client_connection(&client_id)
+            .cloned();
+
+        Ok(connection_id.into_iter().collect())
     }
 
     fn query_connections(
         &self,
         _request: QueryConnectionsRequest,
     ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
-        unimplemented!()
+        let context = self.context.acquire_read();
+
+        context
+            .connection_ids()
+            .into_iter()
+            .map(|connection_id| {
+                let connection_end = ConnectionReader::connection_end(&*context, &connection_id)
+                    .map_err(Error::ics03)?;
+
+                Ok(IdentifiedConnectionEnd::new(connection_id, connection_end))
+            })
+            .collect()
     }
 
     fn query_connection_channels(
         &self,
-        _request: QueryConnectionChannelsRequest,
+        request: QueryConnectionChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        unimplemented!()
+        let connection_id = request
+            .connection
+            .parse()
+            .map_err(|_| Error::query(format!("channels of connection {}", request.connection)))?;
+
+        let context = self.context.acquire_read();
+
+        // A connection without channels is not known to the context
+        let port_channel_ids = match context.connection_channels(&connection_id) {
+            Ok(port_channel_ids) => port_channel_ids,
+            Err(_) => return Ok(vec![]),
+        };
+
+        port_channel_ids
+            .into_iter()
+            .map(|(port_id, channel_id)| {
+                let channel_end = context
+                    .channel_end(&(port_id.clone(), channel_id.clone()))
+                    .map_err(|e| Error::query(e.to_string()))?;
+
+                Ok(IdentifiedChannelEnd::new(port_id, channel_id, channel_end))
+            })
+            .collect()
     }
 
     fn query_channels(
         &self,
         _request: QueryChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        unimplemented!()
+        let context = self.context.acquire_read();
+
+        context
+            .channel_ids()
+            .into_iter()
+            .map(|(port_id, channel_id)| {
+                let channel_end = context
+                    .channel_end(&(port_id.clone(), channel_id.clone()))
+                    .map_err(|e| Error::query(e.to_string()))?;
+
+                Ok(IdentifiedChannelEnd::new(port_id, channel_id, channel_end))
+            })
+            .collect()
     }
 
     fn query_channel(
         &self,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
         _height: Height,
     ) -> Result<ChannelEnd, Error> {
-        unimplemented!()
+        self.context
+            .acquire_read()
+            .channel_end(&(port_id.clone(), channel_id.clone()))
+            .map_err(|e| Error::query(e.to_string()))
     }
 
     fn query_channel_client_state(
         &self,
-        _request: QueryChannelClientStateRequest,
+        request: QueryChannelClientStateRequest,
     ) -> Result<Option<IdentifiedAnyClientState>, Error> {
-        unimplemented!()
+        let port_channel_id = Self::port_channel_id(&request.port_id, &request.channel_id)?;
+        let context = self.context.acquire_read();
+
+        let channel_end = context
+            .channel_end(&port_channel_id)
+            .map_err(|e| Error::query(e.to_string()))?;
+
+        let connection_id = match channel_end.connection_hops().first() {
+            Some(connection_id) => connection_id,
+            None => return Ok(None),
+        };
+
+        let connection_end =
+            ConnectionReader::connection_end(&*context, connection_id).map_err(Error::ics03)?;
+
+        let client_id = connection_end.client_id().clone();
+        let client_state = context.query_client_full_state(&client_id);
+
+        Ok(client_state.map(|client_state| IdentifiedAnyClientState::new(client_id, client_state)))
     }
 
     fn query_packet_commitments(
         &self,
-        _request: QueryPacketCommitmentsRequest,
+        request: QueryPacketCommitmentsRequest,
     ) -> Result<(Vec<PacketState>, Height), Error> {
-        unimplemented!()
+        let (port_id, channel_id) = Self::port_channel_id(&request.port_id, &request.channel_id)?;
+        let context = self.context.acquire_read();
+
+        let commitments = context
+            .packet_commitment_sequences(&port_id, &channel_id)
+            .into_iter()
+            .map(|sequence| {
+                let commitment = context
+                    .get_packet_commitment(&(port_id.clone(), channel_id.clone(), sequence))
+                    .map_err(|e| Error::query(e.to_string()))?;
+
+                Ok(PacketState {
+                    port_id: request.port_id.clone(),
+                    channel_id: request.channel_id.clone(),
+                    sequence: sequence.into(),
+                    data: commitment.into_vec(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok((commitments, context.host_height()))
     }
 
     fn query_unreceived_packets(
         &self,
-        _request: QueryUnreceivedPacketsRequest,
+        request: QueryUnreceivedPacketsRequest,
     ) -> Result<Vec<u64>, Error> {
-        unimplemented!()
+        let port_channel_id = Self::port_channel_id(&request.port_id, &request.channel_id)?;
+        let context = self.context.acquire_read();
+
+        let channel_end = context
+            .channel_end(&port_channel_id)
+            .map_err(|e| Error::query(e.to_string()))?;
+
+        // The packets of an ordered channel are received in order of their sequence,
+        // while the receipt of each packet of an unordered channel is stored.
+        let sequences = if channel_end.ordering() == &Order::Ordered {
+            let next_sequence_recv = context
+                .get_next_sequence_recv(&port_channel_id)
+                .map_err(|e| Error::query(e.to_string()))?;
+
+            request
+                .packet_commitment_sequences
+                .into_iter()
+                .filter(|sequence| *sequence >= u64::from(next_sequence_recv))
+                .collect()
+        } else {
+            let (port_id, channel_id) = port_channel_id;
+
+            request
+                .packet_commitment_sequences
+                .into_iter()
+                .filter(|sequence| {
+                    context
+                        .get_packet_receipt(&(
+                            port_id.clone(),
+                            channel_id.clone(),
+                            Sequence::from(*sequence),
+                        ))
+                        .is_err()
+                })
+                .collect()
+        };
+
+        Ok(sequences)
     }
 
     fn query_packet_acknowledgements(
         &self,
-        _request: QueryPacketAcknowledgementsRequest,
+        request: QueryPacketAcknowledgementsRequest,
     ) -> Result<(Vec<PacketState>, Height), Error> {
-        unimplemented!()
+        let (port_id, channel_id) = Self::port_channel_id(&request.port_id, &request.channel_id)?;
+        let context = self.context.acquire_read();
+
+        let acks = context
+            .packet_acknowledgement_sequences(&port_id, &channel_id)
+            .into_iter()
+            .filter(|sequence| {
+                request.packet_commitment_sequences.is_empty()
+                    || request
+                        .packet_commitment_sequences
+                        .contains(&u64::from(*sequence))
+            })
+            .map(|sequence| {
+                let ack = context
+                    .get_packet_acknowledgement(&(port_id.clone(), channel_id.clone(), sequence))
+                    .map_err(|e| Error::query(e.to_string()))?;
+
+                Ok(PacketState {
+                    port_id: request.port_id.clone(),
+                    channel_id: request.channel_id.clone(),
+                    sequence: sequence.into(),
+                    data: ack.into_vec(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok((acks, context.host_height()))
     }
 
     fn query_unreceived_acknowledgements(
         &self,
-        _request: QueryUnreceivedAcksRequest,
+        request: QueryUnreceivedAcksRequest,
     ) -> Result<Vec<u64>, Error> {
-        unimplemented!()
+        let (port_id, channel_id) = Self::port_channel_id(&request.port_id, &request.channel_id)?;
+        let context = self.context.acquire_read();
+
+        // The commitment of a packet is deleted once its acknowledgement is received
+        let sequences = request
+            .packet_ack_sequences
+            .into_iter()
+            .filter(|sequence| {
+                context
+                    .get_packet_commitment(&(
+                        port_id.clone(),
+                        channel_id.clone(),
+                        Sequence::from(*sequence),
+                    ))
+                    .is_ok()
+            })
+            .collect();
+
+        Ok(sequences)
     }
 
     fn query_next_sequence_receive(
         &self,
-        _request: QueryNextSequenceReceiveRequest,
+        request: QueryNextSequenceReceiveRequest,
     ) -> Result<Sequence, Error> {
-        unimplemented!()
-    }
+        let port_channel_id = Self::port_channel_id(&request.port_id, &request.channel_id)?;
+
+        self.context
+            .acquire_read()
+            .get_next_sequence_recv(&port_channel_id)
+            .map_err(|e| Error::query(e.to_string()))
+    }
+
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error> {
+        let events = match request {
+            QueryTxRequest::Transaction(tx) => self
+                .txs
+                .iter()
+                .find(|mock_tx| mock_tx.hash == tx.0)
+                .map(|mock_tx| mock_tx.events.clone())
+                .unwrap_or_default(),
+
+            QueryTxRequest::Packet(request) => self
+                .txs
+                .iter()
+                .flat_map(|mock_tx| mock_tx.events.iter())
+                .filter(|event| matches_packet_event(&request, event))
+                .cloned()
+                .collect(),
+
+            QueryTxRequest::Client(request) => self
+                .txs
+                .iter()
+                .flat_map(|mock_tx| mock_tx.events.iter())
+                .find(|event| match event {
+                    IbcEvent::UpdateClient(update) => {
+                        update.client_id() == &request.client_id
+                            && update.consensus_height() == request.consensus_height
+                    }
+                    _ => false,
+                })
+                .cloned()
+                .into_iter()
+                .collect(),
+        };
 
-    fn query_txs(&self, _request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error> {
-        unimplemented!()
+        Ok(events)
     }
 
     fn query_blocks(
         &self,
         _request: QueryBlockRequest,
     ) -> Result<(Vec<IbcEvent>, Vec<IbcEvent>), Error> {
-        unimplemented!()
+        // All the events of a mock chain are emitted by its transactions
+        Ok((vec![], vec![]))
     }
 
+    // The mock context does not keep a Merkle store of its state, such that
+    // it cannot prove any of it.
+
     fn proven_client_state(
         &self,
         _client_id: &ClientId,
         _height: Height,
     ) -> Result<(Self::ClientState, MerkleProof), Error> {
-        unimplemented!()
+        Err(Error::empty_response_proof())
     }
 
     fn proven_connection(
@@ -311,7 +648,7 @@ impl ChainEndpoint for MockChain {
         _connection_id: &ConnectionId,
         _height: Height,
     ) -> Result<(ConnectionEnd, MerkleProof), Error> {
-        unimplemented!()
+        Err(Error::empty_response_proof())
     }
 
     fn proven_client_consensus(
@@ -320,7 +657,7 @@ impl ChainEndpoint for MockChain {
         _consensus_height: Height,
         _height: Height,
     ) -> Result<(Self::ConsensusState, MerkleProof), Error> {
-        unimplemented!()
+        Err(Error::empty_response_proof())
     }
 
     fn proven_channel(
@@ -329,7 +666,7 @@ impl ChainEndpoint for MockChain {
         _channel_id: &ChannelId,
         _height: Height,
     ) -> Result<(ChannelEnd, MerkleProof), Error> {
-        unimplemented!()
+        Err(Error::empty_response_proof())
     }
 
     fn proven_packet(
@@ -340,7 +677,7 @@ impl ChainEndpoint for MockChain {
         _sequence: Sequence,
         _height: Height,
     ) -> Result<(Vec<u8>, MerkleProof), Error> {
-        unimplemented!()
+        Err(Error::empty_response_proof())
     }
 
     fn build_client_state(
@@ -413,28 +750,88 @@ impl ChainEndpoint for MockChain {
     ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
         Ok(self
             .context
+            .acquire_read()
             .consensus_states(&request.client_id.parse().unwrap()))
     }
 
     /// Performs a query to retrieve the identifiers of all connections.
     fn query_consensus_state(
         &self,
-        _client_id: ClientId,
-        _consensus_height: Height,
+        client_id: ClientId,
+        consensus_height: Height,
         _query_height: Height,
     ) -> Result<AnyConsensusState, Error> {
-        unimplemented!()
+        self.context
+            .acquire_read()
+            .consensus_state(&client_id, consensus_height)
+            .map_err(|e| Error::query(e.to_string()))
     }
 
     fn query_upgraded_consensus_state(
         &self,
         _height: Height,
     ) -> Result<(Self::ConsensusState, MerkleProof), Error> {
-        unimplemented!()
+        // Mock chains are never upgraded
+        Err(Error::empty_upgraded_client_state())
+    }
+
+    fn query_app_version(&self, request: AppVersion) -> Result<Version, Error> {
+        // The applications of a mock chain accept the proposed version, which
+        // defaults to the ICS20 one on the transfer port.
+        if request.proposed_version == Version::empty() && request.port_id.as_str() == PORT_ID {
+            Ok(Version::ics20())
+        } else {
+            Ok(request.proposed_version)
+        }
     }
+}
+
+/// Whether the given event is a packet event matching the channel and sequences of the request.
+fn matches_packet_event(request: &QueryPacketEventDataRequest, event: &IbcEvent) -> bool {
+    let packet = match (&request.event_id, event) {
+        (WithBlockDataType::SendPacket, IbcEvent::SendPacket(ev)) => &ev.packet,
+        (WithBlockDataType::WriteAck, IbcEvent::WriteAcknowledgement(ev)) => &ev.packet,
+        _ => return false,
+    };
+
+    packet.source_port == request.source_port_id
+        && packet.source_channel == request.source_channel_id
+        && packet.destination_port == request.destination_port_id
+        && packet.destination_channel == request.destination_channel_id
+        && request.sequences.contains(&packet.sequence)
+}
+
+/// Produces a new block of the given mock chain every `block_time`, and reports it
+/// with a `NewBlock` event, until the chain is shut down.
+fn produce_blocks(
+    chain_id: ChainId,
+    context: RwArc<MockContext>,
+    block_time: Duration,
+    sender: EventSender,
+) {
+    loop {
+        thread::sleep(block_time);
+
+        let height = {
+            let mut context = context.acquire_write();
+            context.advance_host_chain_height();
+            context.host_height()
+        };
 
-    fn query_app_version(&self, _request: AppVersion) -> Result<Version, Error> {
-        todo!()
+        let batch = EventBatch {
+            chain_id: chain_id.clone(),
+            height,
+            events: vec![NewBlock::new(height).into()],
+        };
+
+        // The chain, and thus its event receiver, was dropped
+        if sender.send(Ok(batch)).is_err() {
+            debug!(
+                "[{}] mock chain shut down, stopping block production",
+                chain_id
+            );
+            break;
+        }
     }
 }
 
@@ -452,29 +849,133 @@ pub mod test_utils {
     pub fn get_basic_chain_config(id: &str) -> ChainConfig {
         ChainConfig {
             id: ChainId::from_str(id).unwrap(),
+            chain_type: crate::config::default::chain_type(),
             rpc_addr: "http://127.0.0.1:26656".parse().unwrap(),
             grpc_addr: "http://127.0.0.1:9090".parse().unwrap(),
             websocket_addr: "ws://127.0.0.1:26656/websocket".parse().unwrap(),
             rpc_timeout: crate::config::default::rpc_timeout(),
             account_prefix: "".to_string(),
             key_name: "".to_string(),
+            extra_key_names: vec![],
             store_prefix: "".to_string(),
             default_gas: None,
             key_store_type: Default::default(),
             max_gas: None,
             gas_price: GasPrice::new(0.001, "uatom".to_string()),
+            dynamic_gas_price: None,
             gas_adjustment: None,
+            resend_gas_estimate_ttl: None,
             fee_granter: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
             clock_drift: Duration::from_secs(5),
             max_block_time: Duration::from_secs(10),
             trusting_period: Some(Duration::from_secs(14 * 24 * 60 * 60)), // 14 days
+            block_time: None,
             trust_threshold: Default::default(),
             packet_filter: PacketFilter::default(),
             address_type: AddressType::default(),
+            remote_signer: None,
+            fee_budget: None,
+            fee_policies: vec![],
+            packet_rate_limits: vec![],
+            transfer_filters: vec![],
+            event_source: Default::default(),
+            extra_endpoints: vec![],
+            failover: Default::default(),
+            backend: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utils::get_basic_chain_config;
+
+    #[test]
+    fn produce_blocks_at_block_time() {
+        let mut config = get_basic_chain_config("chain-a");
+        config.block_time = Some(Duration::from_millis(10));
+
+        let rt = Arc::new(Runtime::new().unwrap());
+        let chain = MockChain::bootstrap(config, rt.clone()).unwrap();
+        let (receiver, _) = chain.init_event_monitor(rt, None).unwrap();
+
+        let batch = receiver
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap()
+            .unwrap();
+
+        // Mock chains start at height 20
+        assert_eq!(batch.height, Height::new(0, 21));
+        assert!(matches!(batch.events[..], [IbcEvent::NewBlock(_)]));
+        assert!(chain.query_status().unwrap().height >= batch.height);
+    }
+
+    #[test]
+    fn query_packets_of_context() {
+        let rt = Arc::new(Runtime::new().unwrap());
+        let mut chain = MockChain::bootstrap(get_basic_chain_config("chain-a"), rt).unwrap();
+
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::default();
+
+        let context = chain
+            .context
+            .acquire_read()
+            .clone()
+            .with_channel(port_id.clone(), channel_id.clone(), ChannelEnd::default())
+            .with_packet_commitment(
+                port_id.clone(),
+                channel_id.clone(),
+                Sequence::from(2),
+                vec![2].into(),
+            );
+        chain.context = RwArc::new_lock(context);
+
+        let (commitments, _) = chain
+            .query_packet_commitments(QueryPacketCommitmentsRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                pagination: None,
+            })
+            .unwrap();
+        assert_eq!(
+            commitments,
+            vec![PacketState {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                sequence: 2,
+                data: vec![2],
+            }]
+        );
+
+        // No packet was received on the channel
+        let unreceived = chain
+            .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                packet_commitment_sequences: vec![1, 2],
+            })
+            .unwrap();
+        assert_eq!(unreceived, vec![1, 2]);
+
+        // The acknowledgement of the first packet was received, as its commitment was deleted
+        let unreceived = chain
+            .query_unreceived_acknowledgements(QueryUnreceivedAcksRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                packet_ack_sequences: vec![1, 2],
+            })
+            .unwrap();
+        assert_eq!(unreceived, vec![2]);
+
+        assert!(chain
+            .proven_channel(&port_id, &channel_id, Height::new(0, 20))
+            .is_err());
+    }
+}
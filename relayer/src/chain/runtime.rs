@@ -3,7 +3,7 @@ use std::thread;
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
-use tracing::error;
+use tracing::{error, warn};
 
 use ibc::{
     core::{
@@ -46,8 +46,8 @@ use ibc_proto::ibc::core::{
 
 use crate::{
     chain::handle::requests::AppVersion,
-    chain::StatusResponse,
-    config::ChainConfig,
+    chain::{CheckTxResponses, StatusResponse},
+    config::{ChainConfig, FeePolicy},
     connection::ConnectionMsgType,
     error::Error,
     event::{
@@ -59,6 +59,7 @@ use crate::{
 };
 
 use super::{
+    dry_run,
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
     ChainEndpoint, HealthCheck,
 };
@@ -144,6 +145,9 @@ pub struct ChainRuntime<Endpoint: ChainEndpoint> {
     /// Interface to the event monitor
     event_monitor_ctrl: EventMonitorCtrl,
 
+    /// The height after which the event monitor collects the events once enabled, if any
+    resume_height: Option<Height>,
+
     /// A handle to the light client
     light_client: Endpoint::LightClient,
 
@@ -205,6 +209,7 @@ where
             request_receiver,
             event_bus: EventBus::new(),
             event_monitor_ctrl: EventMonitorCtrl::none(),
+            resume_height: None,
             light_client,
         }
     }
@@ -251,12 +256,20 @@ where
                             self.subscribe(reply_to)?
                         },
 
-                        Ok(ChainRequest::SendMessagesAndWaitCommit { proto_msgs, reply_to }) => {
-                            self.send_messages_and_wait_commit(proto_msgs, reply_to)?
+                        Ok(ChainRequest::ResumeEventsFrom { height, reply_to }) => {
+                            self.resume_events_from(height, reply_to)?
+                        },
+
+                        Ok(ChainRequest::SendMessagesAndWaitCommit { proto_msgs, fee_policy, reply_to }) => {
+                            self.send_messages_and_wait_commit(proto_msgs, fee_policy, reply_to)?
                         },
 
-                        Ok(ChainRequest::SendMessagesAndWaitCheckTx { proto_msgs, reply_to }) => {
-                            self.send_messages_and_wait_check_tx(proto_msgs, reply_to)?
+                        Ok(ChainRequest::SendMessagesAndWaitCheckTx { proto_msgs, fee_policy, reply_to }) => {
+                            self.send_messages_and_wait_check_tx(proto_msgs, fee_policy, reply_to)?
+                        },
+
+                        Ok(ChainRequest::SendTransactionsAndWaitCheckTx { txs, fee_policy, reply_to }) => {
+                            self.send_transactions_and_wait_check_tx(txs, fee_policy, reply_to)?
                         },
 
                         Ok(ChainRequest::Signer { reply_to }) => {
@@ -435,8 +448,24 @@ where
         reply_to.send(Ok(subscription)).map_err(Error::send)
     }
 
+    fn resume_events_from(&mut self, height: Height, reply_to: ReplyTo<()>) -> Result<(), Error> {
+        if self.event_monitor_ctrl.is_live() {
+            warn!(
+                "[{}] event monitor is already running, not resuming events from height {}",
+                ChainEndpoint::id(&self.chain),
+                height
+            );
+        } else {
+            self.resume_height = Some(height);
+        }
+
+        reply_to.send(Ok(())).map_err(Error::send)
+    }
+
     fn enable_event_monitor(&mut self) -> Result<(), Error> {
-        let (event_receiver, tx_monitor_cmd) = self.chain.init_event_monitor(self.rt.clone())?;
+        let (event_receiver, tx_monitor_cmd) = self
+            .chain
+            .init_event_monitor(self.rt.clone(), self.resume_height.take())?;
 
         self.event_monitor_ctrl
             .enable(event_receiver, tx_monitor_cmd);
@@ -447,18 +476,86 @@ where
     fn send_messages_and_wait_commit(
         &mut self,
         proto_msgs: Vec<prost_types::Any>,
+        fee_policy: Option<FeePolicy>,
         reply_to: ReplyTo<Vec<IbcEvent>>,
     ) -> Result<(), Error> {
-        let result = self.chain.send_messages_and_wait_commit(proto_msgs);
+        let result = if dry_run::is_enabled() {
+            self.chain
+                .simulate_messages(proto_msgs, fee_policy.as_ref())
+        } else {
+            self.chain
+                .send_messages_and_wait_commit(proto_msgs, fee_policy.as_ref())
+        };
+
         reply_to.send(result).map_err(Error::send)
     }
 
+    /// Sends the given messages along with those of the requests of the same kind which are
+    /// already queued, so that the chain can broadcast the transactions of the requests
+    /// signed with different keys concurrently.
     fn send_messages_and_wait_check_tx(
         &mut self,
         proto_msgs: Vec<prost_types::Any>,
-        reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
+        fee_policy: Option<FeePolicy>,
+        reply_to: ReplyTo<CheckTxResponses>,
+    ) -> Result<(), Error> {
+        let mut submissions = vec![(proto_msgs, fee_policy)];
+        let mut replies = vec![reply_to];
+
+        // The requests of other kinds are queued again. Each handle awaits the reply to
+        // its request before sending the next one, so they do not depend on each other.
+        let mut others = vec![];
+        for _ in 0..self.request_receiver.len() {
+            match self.request_receiver.try_recv() {
+                Ok(ChainRequest::SendMessagesAndWaitCheckTx {
+                    proto_msgs,
+                    fee_policy,
+                    reply_to,
+                }) => {
+                    submissions.push((proto_msgs, fee_policy));
+                    replies.push(reply_to);
+                }
+                Ok(request) => others.push(request),
+                Err(_) => break,
+            }
+        }
+
+        for request in others {
+            self.request_sender.send(request).map_err(Error::send)?;
+        }
+
+        // In dry-run mode, no transaction is broadcast, hence there is no response
+        // through which to report the events of the simulated transactions
+        let results: Vec<_> = if dry_run::is_enabled() {
+            submissions
+                .iter()
+                .map(|_| Err(Error::dry_run_check_tx()))
+                .collect()
+        } else {
+            self.chain
+                .send_messages_concurrently_and_wait_check_tx(submissions)
+        };
+
+        for (reply_to, result) in replies.into_iter().zip(results) {
+            reply_to.send(result).map_err(Error::send)?;
+        }
+
+        Ok(())
+    }
+
+    fn send_transactions_and_wait_check_tx(
+        &mut self,
+        txs: Vec<Vec<prost_types::Any>>,
+        fee_policy: Option<FeePolicy>,
+        reply_to: ReplyTo<Vec<Result<CheckTxResponses, Error>>>,
     ) -> Result<(), Error> {
-        let result = self.chain.send_messages_and_wait_check_tx(proto_msgs);
+        let result = if dry_run::is_enabled() {
+            Err(Error::dry_run_check_tx())
+        } else {
+            self.chain
+                .send_transactions_and_wait_check_tx(txs, fee_policy.as_ref())
+        };
+
         reply_to.send(result).map_err(Error::send)
     }
 
@@ -1,16 +1,24 @@
 //! Relayer configuration
 
 mod error;
+mod filter;
 mod proof_specs;
 pub mod reload;
+mod transfer_filter;
 pub mod types;
 
 use alloc::collections::BTreeMap as HashMap;
 use alloc::collections::BTreeSet as HashSet;
 use core::{fmt, time::Duration};
 use std::sync::{Arc, RwLock};
-use std::{fs, fs::File, io::Write, path::Path};
-
+use std::{
+    fs,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_light_client::types::TrustThreshold;
 
@@ -22,6 +30,8 @@ use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
 use crate::keyring::Store;
 
 pub use error::Error;
+pub use filter::{FilterPattern, Wildcard};
+pub use transfer_filter::TransferFilter;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -41,6 +51,67 @@ impl fmt::Display for GasPrice {
     }
 }
 
+/// The query used to obtain the current gas price of a chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasPriceQuery {
+    /// The `GasPrice` query of the `feemarket` module, which returns
+    /// the minimum gas price in the denomination of the configured gas price.
+    Feemarket,
+    /// The `BaseFee` query of the Ethermint `feemarket` module, which returns
+    /// the EIP-1559 base fee.
+    EthermintBaseFee,
+}
+
+impl GasPriceQuery {
+    /// The gRPC method path of the query, performed through an ABCI query.
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            Self::Feemarket => "/feemarket.feemarket.v1.Query/GasPrice",
+            Self::EthermintBaseFee => "/ethermint.feemarket.v1.Query/BaseFee",
+        }
+    }
+}
+
+/// Configuration of the dynamic gas price of a chain, queried from the chain
+/// in place of the static `gas_price`, which is kept as a fallback for when
+/// the query fails and the last queried gas price is too old.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DynamicGasPrice {
+    pub query: GasPriceQuery,
+    /// Overrides the gRPC method path of the query
+    pub query_path: Option<String>,
+    /// The factor applied to the queried gas price
+    #[serde(default = "default::gas_price_multiplier")]
+    pub multiplier: f64,
+    /// The maximum gas price to pay, after applying the multiplier
+    pub max: f64,
+    #[serde(
+        default = "default::gas_price_refresh_interval",
+        with = "humantime_serde"
+    )]
+    pub refresh_interval: Duration,
+    /// How long a gas price obtained from the chain remains in use when
+    /// querying it again fails, before falling back on the static `gas_price`
+    #[serde(default = "default::gas_price_max_age", with = "humantime_serde")]
+    pub max_age: Duration,
+}
+
+impl DynamicGasPrice {
+    pub fn query_path(&self) -> &str {
+        self.query_path
+            .as_deref()
+            .unwrap_or_else(|| self.query.default_path())
+    }
+
+    /// The gas price to pay given the queried gas price, i.e. the queried
+    /// price with the multiplier applied, capped at the maximum price.
+    pub fn effective_price(&self, queried_price: f64) -> f64 {
+        (queried_price * self.multiplier).min(self.max)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(
     rename_all = "lowercase",
@@ -66,27 +137,136 @@ impl PacketFilter {
     /// false otherwise.
     pub fn is_allowed(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
         match self {
-            PacketFilter::Allow(spec) => spec.contains(&(port_id.clone(), channel_id.clone())),
-            PacketFilter::Deny(spec) => !spec.contains(&(port_id.clone(), channel_id.clone())),
+            PacketFilter::Allow(spec) => spec.matches(port_id, channel_id),
+            PacketFilter::Deny(spec) => !spec.matches(port_id, channel_id),
             PacketFilter::AllowAll => true,
         }
     }
 }
 
+/// A set of `(port, channel)` patterns, where both the port and the channel
+/// may either be an exact identifier or a wildcard pattern, eg. `ica*` or `channel-*`.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct ChannelsSpec(HashSet<(PortId, ChannelId)>);
+pub struct ChannelsSpec(HashSet<(FilterPattern<PortId>, FilterPattern<ChannelId>)>);
 
 impl ChannelsSpec {
-    pub fn contains(&self, channel_port: &(PortId, ChannelId)) -> bool {
-        self.0.contains(channel_port)
+    /// Returns true if the channel with [`PortId`] and [`ChannelId`] matches any of the patterns.
+    pub fn matches(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        self.0
+            .iter()
+            .any(|(port, channel)| port.matches(port_id) && channel.matches(channel_id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(FilterPattern<PortId>, FilterPattern<ChannelId>)> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A fee policy, which sets how the fees of the transactions relaying packets
+/// on some channels, or towards some counterparty chains, are paid.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeePolicy {
+    /// The channels on this chain the policy applies to
+    #[serde(default)]
+    pub channels: ChannelsSpec,
+    /// The counterparty chains the policy applies to
+    #[serde(default)]
+    pub counterparty_chains: Vec<ChainId>,
+    /// The account granting the fees, overrides the chain's `fee_granter`
+    pub fee_granter: Option<String>,
+    /// The gas price, and thus the denomination, the fees are paid in
+    pub gas_price: GasPrice,
+}
+
+impl FeePolicy {
+    /// Returns true if the policy applies to the channel with [`PortId`] and [`ChannelId`]
+    /// on this chain, whose counterparty is the chain with the given [`ChainId`].
+    /// A policy which lists neither channels nor counterparty chains applies to all channels.
+    pub fn applies_to(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_chain_id: &ChainId,
+    ) -> bool {
+        if self.channels.is_empty() && self.counterparty_chains.is_empty() {
+            return true;
+        }
+
+        self.channels.matches(port_id, channel_id)
+            || self.counterparty_chains.contains(counterparty_chain_id)
+    }
+
+    /// The gas price the fees are paid at under this policy, given the current gas price
+    /// of the chain, i.e. its dynamic gas price if one is configured. The price of the
+    /// policy acts as a floor for the gas price of the chain when both are in the same
+    /// denomination, otherwise the price of the policy is used as is.
+    pub fn effective_gas_price(&self, chain_gas_price: &GasPrice) -> GasPrice {
+        if self.gas_price.denom == chain_gas_price.denom {
+            GasPrice::new(
+                self.gas_price.price.max(chain_gas_price.price),
+                self.gas_price.denom.clone(),
+            )
+        } else {
+            self.gas_price.clone()
+        }
+    }
+}
+
+/// The priority and rate limit of the relaying of packets sent on some channels of a chain.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PacketRateLimit {
+    /// The channels on this chain the rate limit applies to, all channels if empty
+    #[serde(default)]
+    pub channels: ChannelsSpec,
+    /// The transactions of channels with a higher priority are submitted first
+    #[serde(default)]
+    pub priority: u32,
+    /// The maximum number of messages relayed for these channels within each `window`
+    pub max_msgs: Option<u64>,
+    /// The duration of the sliding window over which the messages are counted
+    #[serde(default = "default::rate_limit_window", with = "humantime_serde")]
+    pub window: Duration,
+}
+
+impl PacketRateLimit {
+    /// Returns true if the rate limit applies to the channel with [`PortId`] and [`ChannelId`]
+    pub fn applies_to(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        self.channels.is_empty() || self.channels.matches(port_id, channel_id)
     }
 }
 
+/// The budget of the fees the relayer may spend on a chain within a sliding window,
+/// per denomination. No fee may be paid in a denomination without a budget.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeBudget {
+    /// The maximum amount of fees paid in the denomination of the chain's `gas_price`
+    /// within each `window`
+    pub max_fees: u64,
+    /// The maximum amount of fees paid in other denominations within each `window`,
+    /// eg. in those of the gas prices of the fee policies
+    #[serde(default)]
+    pub max_fees_per_denom: HashMap<String, u64>,
+    /// The duration of the sliding window over which the fees are accounted for
+    #[serde(default = "default::fee_budget_window", with = "humantime_serde")]
+    pub window: Duration,
+}
+
 /// Defaults for various fields
 pub mod default {
     use super::*;
 
+    pub fn chain_type() -> String {
+        crate::chain::backend::COSMOS_SDK_CHAIN_TYPE.to_string()
+    }
+
     pub fn tx_confirmation() -> bool {
         true
     }
@@ -114,6 +294,42 @@ pub mod default {
     pub fn connection_delay() -> Duration {
         ZERO_DURATION
     }
+
+    pub fn remote_signer_timeout() -> Duration {
+        Duration::from_secs(10)
+    }
+
+    pub fn gas_price_multiplier() -> f64 {
+        1.1
+    }
+
+    pub fn gas_price_refresh_interval() -> Duration {
+        Duration::from_secs(30)
+    }
+
+    pub fn gas_price_max_age() -> Duration {
+        Duration::from_secs(300)
+    }
+
+    pub fn rate_limit_window() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn fee_budget_window() -> Duration {
+        Duration::from_secs(3600)
+    }
+
+    pub fn event_poll_interval() -> Duration {
+        Duration::from_secs(1)
+    }
+
+    pub fn max_height_lag() -> u64 {
+        5
+    }
+
+    pub fn health_check_interval() -> Duration {
+        Duration::from_secs(30)
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -127,6 +343,8 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub store: StoreConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -204,6 +422,7 @@ impl Default for ModeConfig {
                 clear_on_start: true,
                 filter: false,
                 tx_confirmation: true,
+                batch_window: None,
             },
         }
     }
@@ -243,6 +462,10 @@ pub struct Packets {
     pub filter: bool,
     #[serde(default = "default::tx_confirmation")]
     pub tx_confirmation: bool,
+    /// The window during which the messages of the paths relaying to the same chain
+    /// are collected to be submitted together, or `None` if batching is disabled
+    #[serde(default, with = "humantime_serde")]
+    pub batch_window: Option<Duration>,
 }
 
 impl Default for Packets {
@@ -253,6 +476,7 @@ impl Default for Packets {
             clear_on_start: false,
             filter: default::filter(),
             tx_confirmation: default::tx_confirmation(),
+            batch_window: None,
         }
     }
 }
@@ -330,6 +554,16 @@ impl Default for RestConfig {
     }
 }
 
+/// Configuration of the on-disk store in which the relayer persists its state,
+/// such as the transactions pending confirmation, across restarts.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StoreConfig {
+    pub enabled: bool,
+    /// Path of the store, defaults to `$HOME/.hermes/store`
+    pub path: Option<PathBuf>,
+}
+
 /// It defines the address generation method
 /// TODO: Ethermint `pk_type` to be restricted
 /// after the Cosmos SDK release with ethsecp256k1
@@ -361,10 +595,84 @@ impl fmt::Display for AddressType {
     }
 }
 
+/// The RPC, WebSocket and gRPC addresses of a node of a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EndpointConfig {
+    pub rpc_addr: tendermint_rpc::Url,
+    pub websocket_addr: tendermint_rpc::Url,
+    pub grpc_addr: tendermint_rpc::Url,
+}
+
+/// Configuration of the failover between the endpoints of a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FailoverConfig {
+    /// The maximum number of blocks the latest height of a healthy endpoint
+    /// may lag behind the highest latest height among all the endpoints
+    #[serde(default = "default::max_height_lag")]
+    pub max_height_lag: u64,
+    /// The interval at which the health of the endpoints is checked
+    #[serde(default = "default::health_check_interval", with = "humantime_serde")]
+    pub health_check_interval: Duration,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            max_height_lag: default::max_height_lag(),
+            health_check_interval: default::health_check_interval(),
+        }
+    }
+}
+
+/// The source of the IBC events of a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase", tag = "mode", deny_unknown_fields)]
+pub enum EventSource {
+    /// Events are pushed by the node over a subscription to its WebSocket endpoint
+    Push,
+    /// Events are pulled by polling the RPC endpoint of the node for the results
+    /// of each new block, at the given interval
+    Pull {
+        #[serde(default = "default::event_poll_interval", with = "humantime_serde")]
+        interval: Duration,
+    },
+}
+
+impl Default for EventSource {
+    fn default() -> Self {
+        EventSource::Push
+    }
+}
+
+/// The configuration of a chain specific to its backend, given in the `[chains.backend]`
+/// table, which the backends other than the built-in `cosmos` one deserialize into their
+/// own configuration type with [`BackendConfig::parse`].
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct BackendConfig(toml::value::Table);
+
+impl BackendConfig {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Deserialize the configuration into the configuration type of a backend
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        toml::Value::Table(self.0.clone())
+            .try_into()
+            .map_err(Error::decode)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChainConfig {
     pub id: ChainId,
+    /// The backend the chain runtime is spawned with, see [`crate::chain::backend`]
+    #[serde(default = "default::chain_type", rename = "type")]
+    pub chain_type: String,
     pub rpc_addr: tendermint_rpc::Url,
     pub websocket_addr: tendermint_rpc::Url,
     pub grpc_addr: tendermint_rpc::Url,
@@ -373,11 +681,15 @@ pub struct ChainConfig {
     pub account_prefix: String,
     pub key_name: String,
     #[serde(default)]
+    pub extra_key_names: Vec<String>,
+    #[serde(default)]
     pub key_store_type: Store,
     pub store_prefix: String,
     pub default_gas: Option<u64>,
     pub max_gas: Option<u64>,
     pub gas_adjustment: Option<f64>,
+    #[serde(default, with = "humantime_serde")]
+    pub resend_gas_estimate_ttl: Option<Duration>,
     pub fee_granter: Option<String>,
     #[serde(default)]
     pub max_msg_num: MaxMsgNum,
@@ -389,6 +701,8 @@ pub struct ChainConfig {
     pub max_block_time: Duration,
     #[serde(default, with = "humantime_serde")]
     pub trusting_period: Option<Duration>,
+    #[serde(default, with = "humantime_serde")]
+    pub block_time: Option<Duration>,
     #[serde(default)]
     pub memo_prefix: Memo,
     #[serde(default, with = "self::proof_specs")]
@@ -398,10 +712,93 @@ pub struct ChainConfig {
     #[serde(default)]
     pub trust_threshold: TrustThreshold,
     pub gas_price: GasPrice,
+    pub dynamic_gas_price: Option<DynamicGasPrice>,
     #[serde(default)]
     pub packet_filter: PacketFilter,
     #[serde(default)]
     pub address_type: AddressType,
+    pub remote_signer: Option<RemoteSignerConfig>,
+    pub fee_budget: Option<FeeBudget>,
+    #[serde(default)]
+    pub fee_policies: Vec<FeePolicy>,
+    #[serde(default)]
+    pub packet_rate_limits: Vec<PacketRateLimit>,
+    #[serde(default)]
+    pub transfer_filters: Vec<TransferFilter>,
+    #[serde(default)]
+    pub event_source: EventSource,
+    #[serde(default)]
+    pub extra_endpoints: Vec<EndpointConfig>,
+    #[serde(default)]
+    pub failover: FailoverConfig,
+    /// The configuration specific to the backend of the chain, see [`BackendConfig`]
+    #[serde(default, skip_serializing_if = "BackendConfig::is_empty")]
+    pub backend: BackendConfig,
+}
+
+impl ChainConfig {
+    /// The endpoints of this chain, starting with the primary endpoint
+    /// given by `rpc_addr`, `websocket_addr` and `grpc_addr`, followed by the
+    /// `extra_endpoints`, in order of preference.
+    pub fn endpoints(&self) -> Vec<EndpointConfig> {
+        let primary = EndpointConfig {
+            rpc_addr: self.rpc_addr.clone(),
+            websocket_addr: self.websocket_addr.clone(),
+            grpc_addr: self.grpc_addr.clone(),
+        };
+
+        core::iter::once(primary)
+            .chain(self.extra_endpoints.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the first fee policy which applies to the channel with [`PortId`] and
+    /// [`ChannelId`] on this chain, whose counterparty is the chain with the given [`ChainId`].
+    pub fn fee_policy(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_chain_id: &ChainId,
+    ) -> Option<&FeePolicy> {
+        self.fee_policies
+            .iter()
+            .find(|policy| policy.applies_to(port_id, channel_id, counterparty_chain_id))
+    }
+
+    /// Returns the first rate limit which applies to the channel with [`PortId`] and
+    /// [`ChannelId`] on this chain.
+    pub fn packet_rate_limit(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Option<&PacketRateLimit> {
+        self.packet_rate_limits
+            .iter()
+            .find(|limit| limit.applies_to(port_id, channel_id))
+    }
+
+    /// Returns the first transfer filter which applies to the channel with [`PortId`] and
+    /// [`ChannelId`] on this chain.
+    pub fn transfer_filter(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Option<&TransferFilter> {
+        self.transfer_filters
+            .iter()
+            .find(|filter| filter.applies_to(port_id, channel_id))
+    }
+}
+
+/// Configuration of a remote signer, which holds the signing key of
+/// a chain in place of the key store.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    /// Address of the signer, either `unix:///path/to/socket` or `tcp://host:port`
+    pub address: String,
+    #[serde(default = "default::remote_signer_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
@@ -436,7 +833,12 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 
 #[cfg(test)]
 mod tests {
-    use super::{load, store_writer};
+    use super::{
+        default, load, store_writer, BackendConfig, ChannelsSpec, EndpointConfig, EventSource,
+        FeePolicy, GasPrice, PacketFilter, PacketRateLimit,
+    };
+    use core::time::Duration;
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
     use test_log::test;
 
     #[test]
@@ -463,4 +865,222 @@ mod tests {
         let mut buffer = Vec::new();
         store_writer(&config, &mut buffer).unwrap();
     }
+
+    #[test]
+    fn select_fee_policy() {
+        let transfer = PortId::transfer();
+        let channel_0 = ChannelId::new(0);
+        let channel_1 = ChannelId::new(1);
+        let chain_a = ChainId::new("chain-a".to_string(), 0);
+        let chain_b = ChainId::new("chain-b".to_string(), 0);
+
+        let policy =
+            |channels: Vec<(PortId, ChannelId)>, chains: Vec<ChainId>, denom: &str| FeePolicy {
+                channels: ChannelsSpec(
+                    channels
+                        .into_iter()
+                        .map(|(port, channel)| (port.into(), channel.into()))
+                        .collect(),
+                ),
+                counterparty_chains: chains,
+                fee_granter: None,
+                gas_price: GasPrice::new(0.1, denom.to_string()),
+            };
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let mut chain_config = load(path).expect("could not parse config").chains[0].clone();
+        chain_config.fee_policies = vec![
+            policy(
+                vec![(transfer.clone(), channel_0.clone())],
+                vec![],
+                "uchannel",
+            ),
+            policy(vec![], vec![chain_b.clone()], "uchain"),
+            policy(vec![], vec![], "udefault"),
+        ];
+
+        let denom = |channel_id: &ChannelId, chain_id: &ChainId| {
+            chain_config
+                .fee_policy(&transfer, channel_id, chain_id)
+                .map(|policy| policy.gas_price.denom.clone())
+        };
+
+        assert_eq!(denom(&channel_0, &chain_b).as_deref(), Some("uchannel"));
+        assert_eq!(denom(&channel_1, &chain_b).as_deref(), Some("uchain"));
+        assert_eq!(denom(&channel_1, &chain_a).as_deref(), Some("udefault"));
+
+        chain_config.fee_policies.pop();
+        assert_eq!(denom(&channel_1, &chain_a), None);
+    }
+
+    #[test]
+    fn fee_policy_gas_price_floor() {
+        let policy = |price: f64, denom: &str| FeePolicy {
+            channels: ChannelsSpec::default(),
+            counterparty_chains: vec![],
+            fee_granter: None,
+            gas_price: GasPrice::new(price, denom.to_string()),
+        };
+
+        let chain_gas_price = GasPrice::new(0.05, "stake".to_string());
+
+        assert_eq!(
+            policy(0.01, "stake").effective_gas_price(&chain_gas_price),
+            GasPrice::new(0.05, "stake".to_string())
+        );
+        assert_eq!(
+            policy(0.1, "stake").effective_gas_price(&chain_gas_price),
+            GasPrice::new(0.1, "stake".to_string())
+        );
+        assert_eq!(
+            policy(0.01, "uusdc").effective_gas_price(&chain_gas_price),
+            GasPrice::new(0.01, "uusdc".to_string())
+        );
+    }
+
+    #[test]
+    fn select_packet_rate_limit() {
+        let transfer = PortId::transfer();
+        let channel_0 = ChannelId::new(0);
+        let channel_1 = ChannelId::new(1);
+
+        let limit = |channels: Vec<(PortId, ChannelId)>, priority: u32| PacketRateLimit {
+            channels: ChannelsSpec(
+                channels
+                    .into_iter()
+                    .map(|(port, channel)| (port.into(), channel.into()))
+                    .collect(),
+            ),
+            priority,
+            max_msgs: Some(10),
+            window: default::rate_limit_window(),
+        };
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let mut chain_config = load(path).expect("could not parse config").chains[0].clone();
+        chain_config.packet_rate_limits = vec![
+            limit(vec![(transfer.clone(), channel_0.clone())], 10),
+            limit(vec![], 1),
+        ];
+
+        let priority = |channel_id: &ChannelId| {
+            chain_config
+                .packet_rate_limit(&transfer, channel_id)
+                .map(|limit| limit.priority)
+        };
+
+        assert_eq!(priority(&channel_0), Some(10));
+        assert_eq!(priority(&channel_1), Some(1));
+
+        chain_config.packet_rate_limits.pop();
+        assert_eq!(priority(&channel_1), None);
+    }
+
+    #[test]
+    fn packet_filter_wildcards() {
+        let filter: PacketFilter = toml::from_str(
+            r#"
+            policy = 'allow'
+            list = [
+              ['icacontroller-*', '*'],
+              ['transfer', 'channel-0'],
+              ['ica?ost', 'channel-1?'],
+            ]
+            "#,
+        )
+        .expect("could not parse packet filter");
+
+        let allowed = |port: &str, channel: &str| {
+            filter.is_allowed(&port.parse().unwrap(), &channel.parse().unwrap())
+        };
+
+        assert!(allowed("icacontroller-cosmos1abc", "channel-42"));
+        assert!(allowed("transfer", "channel-0"));
+        assert!(allowed("icahost", "channel-12"));
+        assert!(!allowed("transfer", "channel-1"));
+        assert!(!allowed("icahost", "channel-1"));
+        assert!(!allowed("icacontroller", "channel-0"));
+    }
+
+    #[test]
+    fn chain_endpoints() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let mut chain_config = load(path).expect("could not parse config").chains[0].clone();
+
+        let extra = EndpointConfig {
+            rpc_addr: "http://127.0.0.1:36657".parse().unwrap(),
+            websocket_addr: "ws://127.0.0.1:36657/websocket".parse().unwrap(),
+            grpc_addr: "http://127.0.0.1:9190".parse().unwrap(),
+        };
+        chain_config.extra_endpoints = vec![extra.clone()];
+
+        let endpoints = chain_config.endpoints();
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].rpc_addr, chain_config.rpc_addr);
+        assert_eq!(endpoints[0].websocket_addr, chain_config.websocket_addr);
+        assert_eq!(endpoints[0].grpc_addr, chain_config.grpc_addr);
+        assert_eq!(endpoints[1], extra);
+    }
+
+    #[test]
+    fn parse_backend_config() {
+        #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+        struct TestBackendConfig {
+            url: String,
+            retries: u32,
+        }
+
+        let backend: BackendConfig =
+            toml::from_str("url = 'ws://127.0.0.1:9944'\nretries = 3").expect("could not parse");
+
+        assert_eq!(
+            backend
+                .parse::<TestBackendConfig>()
+                .expect("could not parse"),
+            TestBackendConfig {
+                url: "ws://127.0.0.1:9944".to_string(),
+                retries: 3,
+            }
+        );
+        assert!(backend.parse::<EndpointConfig>().is_err());
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = load(path).expect("could not parse config");
+        assert!(config.chains[0].backend.is_empty());
+    }
+
+    #[test]
+    fn parse_event_source() {
+        let pull: EventSource =
+            toml::from_str("mode = 'pull'\ninterval = '500ms'").expect("could not parse");
+        assert_eq!(
+            pull,
+            EventSource::Pull {
+                interval: Duration::from_millis(500)
+            }
+        );
+
+        let pull: EventSource = toml::from_str("mode = 'pull'").expect("could not parse");
+        assert_eq!(
+            pull,
+            EventSource::Pull {
+                interval: default::event_poll_interval()
+            }
+        );
+
+        let push: EventSource = toml::from_str("mode = 'push'").expect("could not parse");
+        assert_eq!(push, EventSource::Push);
+    }
 }
@@ -0,0 +1,176 @@
+//! Patterns matching the port and channel identifiers listed in the channel
+//! filters of the configuration, either exactly or through wildcards.
+
+use core::fmt;
+use core::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A pattern matching an identifier, either exactly or through a wildcard.
+///
+/// Patterns containing `*` or `?` are parsed as wildcards, any other pattern
+/// must be a valid identifier and is matched exactly.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FilterPattern<T> {
+    Exact(T),
+    Wildcard(Wildcard),
+}
+
+impl<T> FilterPattern<T>
+where
+    T: PartialEq + fmt::Display,
+{
+    /// Returns true if the given identifier matches this pattern.
+    pub fn matches(&self, value: &T) -> bool {
+        match self {
+            FilterPattern::Exact(exact) => exact == value,
+            FilterPattern::Wildcard(wildcard) => wildcard.is_match(&value.to_string()),
+        }
+    }
+}
+
+impl<T> From<T> for FilterPattern<T> {
+    fn from(value: T) -> Self {
+        FilterPattern::Exact(value)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for FilterPattern<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterPattern::Exact(exact) => write!(f, "{}", exact),
+            FilterPattern::Wildcard(wildcard) => write!(f, "{}", wildcard),
+        }
+    }
+}
+
+impl<T> FromStr for FilterPattern<T>
+where
+    T: FromStr,
+{
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if Wildcard::is_wildcard(s) {
+            Ok(FilterPattern::Wildcard(Wildcard(s.to_string())))
+        } else {
+            T::from_str(s).map(FilterPattern::Exact)
+        }
+    }
+}
+
+impl<T: fmt::Display> Serialize for FilterPattern<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for FilterPattern<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        pattern.parse().map_err(D::Error::custom)
+    }
+}
+
+/// A glob pattern where `*` matches any sequence of characters, including
+/// the empty one, and `?` matches exactly one character.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Wildcard(String);
+
+impl Wildcard {
+    /// Returns true if the given pattern contains any wildcard character.
+    pub fn is_wildcard(pattern: &str) -> bool {
+        pattern.contains(|c| c == '*' || c == '?')
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if the whole of the given value matches this pattern.
+    pub fn is_match(&self, value: &str) -> bool {
+        let pattern: Vec<char> = self.0.chars().collect();
+        let value: Vec<char> = value.chars().collect();
+
+        let (mut p, mut v) = (0, 0);
+
+        // Position in the pattern of the last `*` seen, and the position in the
+        // value up to which that `*` currently matches, to backtrack to.
+        let mut backtrack = None;
+
+        while v < value.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    backtrack = Some((p, v));
+                    p += 1;
+                }
+                Some(&c) if c == '?' || c == value[v] => {
+                    p += 1;
+                    v += 1;
+                }
+                _ => match backtrack {
+                    Some((star_p, star_v)) => {
+                        backtrack = Some((star_p, star_v + 1));
+                        p = star_p + 1;
+                        v = star_v + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+}
+
+impl fmt::Display for Wildcard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+
+    fn wildcard(pattern: &str) -> Wildcard {
+        Wildcard(pattern.to_string())
+    }
+
+    #[test]
+    fn match_wildcards() {
+        assert!(wildcard("*").is_match(""));
+        assert!(wildcard("*").is_match("transfer"));
+        assert!(wildcard("ica*").is_match("ica"));
+        assert!(wildcard("ica*").is_match("icacontroller-cosmos1abc"));
+        assert!(wildcard("channel-?").is_match("channel-7"));
+        assert!(wildcard("*-*-1").is_match("icahost-channel-1"));
+        assert!(wildcard("a*b*c").is_match("aXbYbZc"));
+
+        assert!(!wildcard("ica*").is_match("transfer"));
+        assert!(!wildcard("channel-?").is_match("channel-10"));
+        assert!(!wildcard("channel-?").is_match("channel-"));
+        assert!(!wildcard("a*b*c").is_match("aXbYcZ"));
+    }
+
+    #[test]
+    fn parse_patterns() {
+        let exact: FilterPattern<PortId> = "transfer".parse().unwrap();
+        assert_eq!(exact, FilterPattern::Exact(PortId::transfer()));
+        assert!(exact.matches(&PortId::transfer()));
+
+        let glob: FilterPattern<ChannelId> = "channel-*".parse().unwrap();
+        assert_eq!(glob, FilterPattern::Wildcard(wildcard("channel-*")));
+        assert!(glob.matches(&ChannelId::new(42)));
+        assert_eq!(glob.to_string(), "channel-*");
+
+        assert!("not a port".parse::<FilterPattern<PortId>>().is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Filtering of the ICS20 fungible token transfers relayed on some channels,
+//! based on the content of their packets.
+
+use alloc::collections::BTreeMap as HashMap;
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc::applications::ics20_fungible_token_transfer;
+use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+
+use super::ChannelsSpec;
+
+/// Rules restricting the ICS20 transfers relayed on some channels of a chain to
+/// those whose packet data satisfies all of them. A rule whose list is empty
+/// does not restrict the transfers.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransferFilter {
+    /// The channels on this chain the filter applies to, all the channels bound
+    /// to the transfer port if empty
+    #[serde(default)]
+    pub channels: ChannelsSpec,
+    /// The denominations of the tokens relayed, including their trace path
+    #[serde(default)]
+    pub denoms: Vec<String>,
+    /// The minimum amount relayed, per denomination
+    #[serde(default)]
+    pub min_amounts: HashMap<String, u64>,
+    /// The senders whose transfers are relayed
+    #[serde(default)]
+    pub senders: Vec<String>,
+    /// The receivers whose transfers are relayed
+    #[serde(default)]
+    pub receivers: Vec<String>,
+}
+
+/// The JSON encoding of the `FungibleTokenPacketData` carried by ICS20 packets.
+#[derive(Clone, Debug, Deserialize)]
+struct FungibleTokenPacketData {
+    denom: String,
+    amount: String,
+    sender: String,
+    receiver: String,
+}
+
+impl TransferFilter {
+    /// Returns true if the filter applies to the channel with [`PortId`] and [`ChannelId`]
+    pub fn applies_to(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        if self.channels.is_empty() {
+            port_id.as_str() == ics20_fungible_token_transfer::PORT_ID
+        } else {
+            self.channels.matches(port_id, channel_id)
+        }
+    }
+
+    /// Returns true if the transfer carried by a packet with the given data is to be relayed,
+    /// or fails if the data is not that of a fungible token transfer.
+    pub fn allows(&self, packet_data: &[u8]) -> Result<bool, serde_json::Error> {
+        let data: FungibleTokenPacketData = serde_json::from_slice(packet_data)?;
+
+        if !self.denoms.is_empty() && !self.denoms.contains(&data.denom) {
+            return Ok(false);
+        }
+
+        if !self.senders.is_empty() && !self.senders.contains(&data.sender) {
+            return Ok(false);
+        }
+
+        if !self.receivers.is_empty() && !self.receivers.contains(&data.receiver) {
+            return Ok(false);
+        }
+
+        if let Some(min_amount) = self.min_amounts.get(&data.denom) {
+            // Amounts which do not fit in 128 bits are above any minimum
+            let above_min = match data.amount.parse::<u128>() {
+                Ok(amount) => amount >= u128::from(*min_amount),
+                Err(_) => {
+                    !data.amount.is_empty() && data.amount.bytes().all(|b| b.is_ascii_digit())
+                }
+            };
+
+            if !above_min {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet_data(denom: &str, amount: &str, receiver: &str) -> Vec<u8> {
+        format!(
+            r#"{{"amount":"{}","denom":"{}","receiver":"{}","sender":"cosmos1sender"}}"#,
+            amount, denom, receiver
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn filter_transfers_by_content() {
+        let filter = TransferFilter {
+            denoms: vec!["uatom".to_string(), "stake".to_string()],
+            min_amounts: vec![("uatom".to_string(), 1000)].into_iter().collect(),
+            receivers: vec!["cosmos1receiver".to_string()],
+            ..Default::default()
+        };
+
+        let allows = |denom: &str, amount: &str, receiver: &str| {
+            filter
+                .allows(&packet_data(denom, amount, receiver))
+                .unwrap()
+        };
+
+        assert!(allows("uatom", "1000", "cosmos1receiver"));
+        assert!(allows("stake", "1", "cosmos1receiver"));
+        assert!(allows(
+            "uatom",
+            "340282366920938463463374607431768211456",
+            "cosmos1receiver"
+        ));
+        assert!(!allows("uatom", "999", "cosmos1receiver"));
+        assert!(!allows("uosmo", "1000", "cosmos1receiver"));
+        assert!(!allows("uatom", "1000", "cosmos1other"));
+    }
+
+    #[test]
+    fn reject_non_transfer_packets() {
+        let filter = TransferFilter::default();
+
+        assert!(filter
+            .allows(&packet_data("uatom", "1", "cosmos1receiver"))
+            .unwrap());
+        assert!(filter.allows(b"not a transfer").is_err());
+    }
+
+    #[test]
+    fn empty_channels_apply_to_transfer_port_only() {
+        let filter = TransferFilter::default();
+        let channel_id = ChannelId::new(0);
+
+        assert!(filter.applies_to(&PortId::transfer(), &channel_id));
+        assert!(!filter.applies_to(&"icahost".parse().unwrap(), &channel_id));
+    }
+}
@@ -114,12 +114,29 @@ define_error! {
             }
             |e| { format!("node at {} running chain {} not caught up", e.address, e.chain_id) },
 
+        UnknownChainType
+            {
+                chain_id: ChainId,
+                chain_type: String,
+            }
+            |e| { format!("no backend is registered for the type '{}' of chain {}", e.chain_type, e.chain_id) },
+
         PrivateStore
             |_| { "Requested proof for a path in the private store" },
 
         Event
             |_| { "Bad notification" },
 
+        InvalidEventAttribute
+            {
+                event_type: String,
+                reason: String,
+            }
+            |e| { format!("invalid attribute of a `{}` event: {}", e.event_type, e.reason) },
+
+        DryRunCheckTx
+            |_| { "in dry-run mode, the transactions are simulated and can only be sent with `send_messages_and_wait_commit`" },
+
         ConversionFromAny
             [ TraceError<TendermintProtoError> ]
             |_| { "Conversion from a protobuf `Any` into a domain type failed" },
@@ -343,6 +360,22 @@ define_error! {
                     e.chain_id, e.estimated_gas, e.max_gas)
             },
 
+        FeeBudgetExhausted
+            {
+                chain_id: ChainId,
+                denom: String,
+                fee: u64,
+                remaining: u64,
+            }
+            |e| {
+                format!("{} fee {}{} of the transaction exceeds the {}{} remaining in the fee budget",
+                    e.chain_id, e.fee, e.denom, e.remaining, e.denom)
+            },
+
+        DynamicGasPrice
+            { reason: String }
+            |e| { format!("failed to obtain the dynamic gas price: {}", e.reason) },
+
         HealthCheckJsonRpc
             {
                 chain_id: ChainId,
@@ -532,6 +565,13 @@ impl GrpcStatusSubdetail {
             .contains("unknown service ibc.core.port.v1.Query")
     }
 
+    /// Returns the index of the message which failed the execution of the
+    /// transaction, if this gRPC error was returned by a tx simulation
+    /// which failed while executing one of its messages.
+    pub fn failed_message_index(&self) -> Option<usize> {
+        crate::sdk_error::failed_message_index(self.status.message())
+    }
+
     /// Check whether this gRPC error matches
     /// - status: InvalidArgument
     /// - message: account sequence mismatch ...
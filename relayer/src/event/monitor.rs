@@ -1,5 +1,6 @@
 use alloc::sync::Arc;
 use core::cmp::Ordering;
+use core::ops::RangeInclusive;
 
 use crossbeam_channel as channel;
 use futures::{
@@ -9,17 +10,20 @@ use futures::{
 };
 use tokio::task::JoinHandle;
 use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
+use tendermint::block::Height as TmHeight;
 use tendermint_rpc::{
-    event::Event as RpcEvent, query::Query, Error as RpcError, SubscriptionClient, Url,
-    WebSocketClient, WebSocketClientDriver,
+    endpoint::block_results, event::Event as RpcEvent, query::Query, Client, Error as RpcError,
+    SubscriptionClient, Url, WebSocketClient, WebSocketClientDriver,
 };
 
 use ibc::{
     core::ics02_client::height::Height, core::ics24_host::identifier::ChainId, events::IbcEvent,
 };
 
+use crate::chain::cosmos::endpoints::EndpointPool;
+use crate::event::rpc::get_events_from_block_results;
 use crate::util::{
     retry::{retry_count, retry_with_index, RetryResult},
     stream::try_group_while,
@@ -45,6 +49,11 @@ mod retry_strategy {
     }
 }
 
+/// Maximum number of blocks whose events are backfilled after a gap in the events
+/// received over the subscriptions. The events of earlier blocks are left to the
+/// periodic packet clearing.
+pub(crate) const MAX_BACKFILL_BLOCKS: u64 = 100;
+
 /// A batch of events from a chain at a specific height
 #[derive(Clone, Debug)]
 pub struct EventBatch {
@@ -88,10 +97,18 @@ pub struct EventMonitor {
     rx_cmd: channel::Receiver<MonitorCmd>,
     /// Node Address
     node_addr: Url,
+    /// The endpoints of the chain to fail over between when reconnecting, if any
+    endpoints: Option<EndpointPool>,
     /// Queries
     event_queries: Vec<Query>,
     /// All subscriptions combined in a single stream
     subscriptions: Box<SubscriptionStream>,
+    /// Height of the last batch of events sent to the handler
+    last_height: Option<Height>,
+    /// Height up to which the events were backfilled after the last reconnection.
+    /// The events received over the new subscriptions at or below that height
+    /// were already sent, and are thus dropped.
+    backfilled_height: Option<Height>,
     /// Tokio runtime
     rt: Arc<TokioRuntime>,
 }
@@ -161,12 +178,28 @@ impl EventMonitor {
             tx_err,
             rx_cmd,
             node_addr,
+            endpoints: None,
             subscriptions: Box::new(futures::stream::empty()),
+            last_height: None,
+            backfilled_height: None,
         };
 
         Ok((monitor, rx_batch, tx_cmd))
     }
 
+    /// Fail over between the given endpoints of the chain when reconnecting,
+    /// by connecting to the WebSocket endpoint of the active one.
+    pub fn set_endpoints(&mut self, endpoints: EndpointPool) {
+        self.endpoints = Some(endpoints);
+    }
+
+    /// Resume from the given height, at which the events were last processed before a restart,
+    /// by backfilling the events of the blocks committed since then, up to the first batch of
+    /// events received over the subscriptions.
+    pub fn resume_from(&mut self, height: Height) {
+        self.last_height = Some(height);
+    }
+
     /// The list of [`Query`] that this event monitor is subscribing for.
     pub fn queries(&self) -> &[Query] {
         &self.event_queries
@@ -195,6 +228,12 @@ impl EventMonitor {
     }
 
     fn try_reconnect(&mut self) -> Result<()> {
+        // Fail over before reconnecting, rather than reconnecting to the lost endpoint
+        if let Some(endpoints) = &self.endpoints {
+            endpoints.record_disconnection(&self.rt);
+            self.node_addr = endpoints.active().config.websocket_addr.clone();
+        }
+
         trace!(
             "[{}] trying to reconnect to WebSocket endpoint {}",
             self.chain_id,
@@ -267,10 +306,15 @@ impl EventMonitor {
         });
 
         match result {
-            Ok(()) => info!(
-                "[{}] successfully reconnected to WebSocket endpoint {}",
-                self.chain_id, self.node_addr
-            ),
+            Ok(()) => {
+                info!(
+                    "[{}] successfully reconnected to WebSocket endpoint {}",
+                    self.chain_id, self.node_addr
+                );
+
+                // Recover the events emitted while the subscriptions were down
+                self.backfill_missed_blocks();
+            }
             Err(retries) => error!(
                 "[{}] failed to reconnect to {} after {} retries",
                 self.chain_id,
@@ -398,14 +442,164 @@ impl EventMonitor {
         Ok(())
     }
 
-    /// Collect the IBC events from the subscriptions
-    fn process_batch(&self, batch: EventBatch) -> Result<()> {
+    /// Collect the IBC events from the subscriptions, after backfilling
+    /// the events of the blocks missing between the last batch and this one.
+    fn process_batch(&mut self, batch: EventBatch) -> Result<()> {
+        if let Some(backfilled_height) = self.backfilled_height {
+            if batch.height <= backfilled_height {
+                trace!(
+                    "[{}] dropping already backfilled events at height {}",
+                    self.chain_id,
+                    batch.height
+                );
+
+                return Ok(());
+            }
+        }
+
+        if let Some(last_height) = self.last_height {
+            match batch.height.blocks_since(&last_height) {
+                Ok(blocks) if blocks > 1 => {
+                    self.backfill(batch.height.revision_height - 1)
+                        .unwrap_or_else(|e| {
+                            warn!(
+                                "[{}] failed to backfill events before height {}: {}",
+                                self.chain_id, batch.height, e
+                            );
+                        });
+                }
+                Err(_) if !batch.height.is_same_revision(&last_height) => {
+                    // Block numbers restart with a new revision, so the blocks missed
+                    // across the upgrade cannot be told apart from the new ones.
+                    warn!(
+                        "[{}] chain moved from height {} to {} of another revision, not backfilling events",
+                        self.chain_id, last_height, batch.height
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let height = batch.height;
+
         self.tx_batch
             .send(Ok(batch))
             .map_err(|_| Error::channel_send_failed())?;
 
+        if self.last_height < Some(height) {
+            self.last_height = Some(height);
+        }
+
         Ok(())
     }
+
+    /// Backfill the events of the blocks committed since the last batch of events
+    /// was sent up to the latest block, ie. the blocks committed while the
+    /// subscriptions were down.
+    fn backfill_missed_blocks(&mut self) {
+        let latest_height = match self.rt.block_on(self.client.status()) {
+            Ok(status) => status.sync_info.latest_block_height.value(),
+            Err(e) => {
+                warn!(
+                    "[{}] failed to query the latest height to backfill events: {}",
+                    self.chain_id, e
+                );
+                return;
+            }
+        };
+
+        match self.backfill(latest_height) {
+            Ok(()) => self.backfilled_height = self.last_height,
+            Err(e) => warn!(
+                "[{}] failed to backfill events up to height {}: {}",
+                self.chain_id, latest_height, e
+            ),
+        }
+    }
+
+    /// Send the batches of events of the blocks after the last batch sent,
+    /// up to the given height, in order, by querying the results of these blocks.
+    fn backfill(&mut self, to_height: u64) -> Result<()> {
+        let range = match backfill_range(&self.chain_id, self.last_height, to_height) {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+
+        let missed_from = self.last_height.map_or(0, |h| h.revision_height) + 1;
+
+        if *range.start() > missed_from {
+            warn!(
+                "[{}] missed events from height {} to {}, only backfilling the last {} blocks",
+                self.chain_id, missed_from, to_height, MAX_BACKFILL_BLOCKS
+            );
+        }
+
+        info!(
+            "[{}] backfilling events from height {} to {}",
+            self.chain_id,
+            range.start(),
+            range.end()
+        );
+
+        for revision_height in range {
+            let tm_height = TmHeight::try_from(revision_height).map_err(Error::invalid_height)?;
+
+            let response = self
+                .rt
+                .block_on(self.client.block_results(tm_height))
+                .map_err(Error::rpc)?;
+
+            let batch = backfilled_batch(&self.chain_id, revision_height, response);
+            let height = batch.height;
+
+            self.tx_batch
+                .send(Ok(batch))
+                .map_err(|_| Error::channel_send_failed())?;
+
+            self.last_height = Some(height);
+        }
+
+        Ok(())
+    }
+}
+
+/// The heights of the blocks to backfill after the last batch of events sent, if any,
+/// up to the given height, limited to the last [`MAX_BACKFILL_BLOCKS`] blocks.
+/// Nothing is backfilled before the first batch or across a revision upgrade.
+fn backfill_range(
+    chain_id: &ChainId,
+    last_height: Option<Height>,
+    to_height: u64,
+) -> Option<RangeInclusive<u64>> {
+    let last_height = match last_height {
+        Some(last_height) if last_height.revision_number == chain_id.version() => {
+            last_height.revision_height
+        }
+        _ => return None,
+    };
+
+    if to_height <= last_height {
+        return None;
+    }
+
+    let from_height = (last_height + 1).max(to_height.saturating_sub(MAX_BACKFILL_BLOCKS - 1));
+
+    Some(from_height..=to_height)
+}
+
+/// The batch of the IBC events of a backfilled block, out of its results.
+fn backfilled_batch(
+    chain_id: &ChainId,
+    revision_height: u64,
+    response: block_results::Response,
+) -> EventBatch {
+    let height = Height::new(chain_id.version(), revision_height);
+
+    EventBatch {
+        chain_id: chain_id.clone(),
+        height,
+        events: get_events_from_block_results(height, response),
+    }
 }
 
 /// Collect the IBC events from an RPC event
@@ -476,3 +670,112 @@ pub enum Next {
     Abort,
     Continue,
 }
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::Event as AbciEvent;
+    use tendermint_rpc::endpoint::block_results;
+
+    use ibc::core::ics02_client::events::NewBlock;
+    use ibc::core::ics04_channel::events::SendPacket;
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+
+    use super::*;
+
+    fn chain_id() -> ChainId {
+        ChainId::new("ibc".to_string(), 1)
+    }
+
+    #[test]
+    fn nothing_to_backfill() {
+        // Before the first batch of events
+        assert_eq!(backfill_range(&chain_id(), None, 10), None);
+
+        // Up to date with the given height
+        assert_eq!(
+            backfill_range(&chain_id(), Some(Height::new(1, 10)), 10),
+            None
+        );
+        assert_eq!(
+            backfill_range(&chain_id(), Some(Height::new(1, 10)), 9),
+            None
+        );
+
+        // Across a revision upgrade
+        assert_eq!(
+            backfill_range(&chain_id(), Some(Height::new(0, 10)), 20),
+            None
+        );
+    }
+
+    #[test]
+    fn backfill_blocks_after_last_height() {
+        assert_eq!(
+            backfill_range(&chain_id(), Some(Height::new(1, 10)), 11),
+            Some(11..=11)
+        );
+        assert_eq!(
+            backfill_range(
+                &chain_id(),
+                Some(Height::new(1, 10)),
+                10 + MAX_BACKFILL_BLOCKS
+            ),
+            Some(11..=10 + MAX_BACKFILL_BLOCKS)
+        );
+    }
+
+    #[test]
+    fn backfill_last_blocks_only() {
+        assert_eq!(
+            backfill_range(
+                &chain_id(),
+                Some(Height::new(1, 10)),
+                11 + MAX_BACKFILL_BLOCKS
+            ),
+            Some(12..=11 + MAX_BACKFILL_BLOCKS)
+        );
+        assert_eq!(
+            backfill_range(&chain_id(), Some(Height::new(1, 1)), 1000),
+            Some(1001 - MAX_BACKFILL_BLOCKS..=1000)
+        );
+    }
+
+    #[test]
+    fn backfilled_batch_at_block_height() {
+        let send_packet = SendPacket {
+            height: Height::new(1, 12),
+            packet: Packet {
+                sequence: Sequence::from(1),
+                source_port: "transfer".parse().unwrap(),
+                source_channel: "channel-0".parse().unwrap(),
+                destination_port: "transfer".parse().unwrap(),
+                destination_channel: "channel-1".parse().unwrap(),
+                ..Packet::default()
+            },
+        };
+
+        let response = block_results::Response {
+            height: TmHeight::from(12_u32),
+            txs_results: None,
+            begin_block_events: None,
+            end_block_events: Some(vec![AbciEvent::try_from(IbcEvent::SendPacket(
+                send_packet.clone(),
+            ))
+            .unwrap()]),
+            validator_updates: vec![],
+            consensus_param_updates: None,
+        };
+
+        let batch = backfilled_batch(&chain_id(), 12, response);
+
+        assert_eq!(batch.chain_id, chain_id());
+        assert_eq!(batch.height, Height::new(1, 12));
+        assert_eq!(
+            batch.events,
+            vec![
+                IbcEvent::NewBlock(NewBlock::new(Height::new(1, 12))),
+                IbcEvent::SendPacket(send_packet),
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use flex_error::{define_error, TraceError};
 
+use tendermint::Error as TendermintError;
 use tendermint_rpc::{Error as RpcError, Url};
 
 use ibc::core::ics24_host::identifier::ChainId;
@@ -15,6 +16,10 @@ define_error! {
             { chain_id: ChainId, address: Url }
             |e| { format!("failed to create WebSocket driver for chain {0} with address {1}", e.chain_id, e.address) },
 
+        HttpClientCreationFailed
+            { chain_id: ChainId, address: Url }
+            |e| { format!("failed to create RPC client for chain {0} with address {1}", e.chain_id, e.address) },
+
         ClientTerminationFailed
             [ TraceError<tokio::task::JoinError> ]
             |_| { "failed to terminate previous WebSocket driver" },
@@ -45,6 +50,10 @@ define_error! {
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
+
+        InvalidHeight
+            [ TendermintError ]
+            |_| { "invalid height" },
     }
 }
 
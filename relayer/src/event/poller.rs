@@ -0,0 +1,233 @@
+//! A pull-based source of IBC events, which polls the RPC endpoint of a node
+//! for the results of each new block, for nodes which do not expose a
+//! WebSocket endpoint, or whose subscriptions are frequently dropped.
+
+use alloc::sync::Arc;
+use core::time::Duration;
+
+use crossbeam_channel as channel;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, error, trace, warn};
+
+use tendermint::block::Height as TmHeight;
+use tendermint_rpc::{Client, HttpClient, Url};
+
+use ibc::{core::ics02_client::height::Height, core::ics24_host::identifier::ChainId};
+
+use crate::chain::cosmos::endpoints::EndpointPool;
+use crate::event::monitor::{
+    Error, ErrorDetail, EventBatch, EventReceiver, MonitorCmd, Result, TxMonitorCmd,
+    MAX_BACKFILL_BLOCKS,
+};
+use crate::event::rpc::get_events_from_block_results;
+
+/// Poll a Tendermint node for its latest height at a given interval, and emit
+/// the IBC events of each new block as an [`EventBatch`], in the same way as
+/// the [`EventMonitor`](crate::event::monitor::EventMonitor) does.
+///
+/// Once an unreachable node can be reached again, the events of the blocks
+/// committed in the meantime are emitted in order, up to the last
+/// [`MAX_BACKFILL_BLOCKS`] blocks. The events of earlier blocks are left
+/// to the periodic packet clearing.
+pub struct EventPoller {
+    chain_id: ChainId,
+    /// RPC client to poll for blocks
+    client: HttpClient,
+    /// Node Address
+    node_addr: Url,
+    /// The endpoints of the chain to fail over between, if any
+    endpoints: Option<EndpointPool>,
+    /// Interval between two polls
+    poll_interval: Duration,
+    /// Channel to handler where the poller for this chain sends the events
+    tx_batch: channel::Sender<Result<EventBatch>>,
+    /// Channel where to receive commands
+    rx_cmd: channel::Receiver<MonitorCmd>,
+    /// Height of the last block whose events were emitted
+    last_height: Option<TmHeight>,
+    /// Tokio runtime
+    rt: Arc<TokioRuntime>,
+}
+
+impl EventPoller {
+    /// Create an event poller for the node at the given RPC address
+    pub fn new(
+        chain_id: ChainId,
+        node_addr: Url,
+        poll_interval: Duration,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, EventReceiver, TxMonitorCmd)> {
+        let (tx_batch, rx_batch) = channel::unbounded();
+        let (tx_cmd, rx_cmd) = channel::unbounded();
+
+        let client = HttpClient::new(node_addr.clone())
+            .map_err(|_| Error::http_client_creation_failed(chain_id.clone(), node_addr.clone()))?;
+
+        let poller = Self {
+            chain_id,
+            client,
+            node_addr,
+            endpoints: None,
+            poll_interval,
+            tx_batch,
+            rx_cmd,
+            last_height: None,
+            rt,
+        };
+
+        Ok((poller, rx_batch, tx_cmd))
+    }
+
+    /// Fail over between the given endpoints of the chain,
+    /// by polling the RPC endpoint of the active one.
+    pub fn set_endpoints(&mut self, endpoints: EndpointPool) {
+        self.endpoints = Some(endpoints);
+    }
+
+    /// Resume from the given height, at which the events were last processed before a restart,
+    /// by emitting the events of all the blocks committed since then on the first poll.
+    pub fn resume_from(&mut self, height: Height) {
+        if height.revision_number != self.chain_id.version() {
+            return;
+        }
+
+        self.last_height = TmHeight::try_from(height.revision_height).ok();
+    }
+
+    /// Event poller loop
+    pub fn run(mut self) {
+        debug!(
+            "[{}] starting event poller for {}, polling every {:?}",
+            self.chain_id, self.node_addr, self.poll_interval
+        );
+
+        loop {
+            if let Err(e) = self.poll() {
+                if let ErrorDetail::ChannelSendFailed(_) = e.detail() {
+                    error!("[{}] {}", self.chain_id, e);
+                    break;
+                }
+
+                error!(
+                    "[{}] failed to poll events from {}: {}",
+                    self.chain_id, self.node_addr, e
+                );
+
+                if let Some(endpoints) = &self.endpoints {
+                    endpoints.record_failure();
+                }
+            }
+
+            match self.rx_cmd.recv_timeout(self.poll_interval) {
+                Err(channel::RecvTimeoutError::Timeout) => continue,
+                Ok(MonitorCmd::Shutdown) | Err(channel::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        debug!("[{}] event poller is shutting down", self.chain_id);
+    }
+
+    /// Emit the events of the blocks committed since the last poll, up to the last
+    /// [`MAX_BACKFILL_BLOCKS`] blocks. Unless resuming from a given height, the first
+    /// poll only emits the events of the latest block.
+    fn poll(&mut self) -> Result<()> {
+        if let Some(endpoints) = &self.endpoints {
+            let active = endpoints.active();
+            if active.config.rpc_addr != self.node_addr {
+                self.client = active.rpc_client.clone();
+                self.node_addr = active.config.rpc_addr.clone();
+            }
+        }
+
+        let latest_height = self
+            .rt
+            .block_on(self.client.status())
+            .map_err(Error::rpc)?
+            .sync_info
+            .latest_block_height;
+
+        let from_height =
+            first_height_to_poll(self.last_height.map(TmHeight::value), latest_height.value());
+
+        if let Some(last_height) = self.last_height {
+            if from_height > last_height.increment().value() {
+                warn!(
+                    "[{}] missed events from height {} to {}, only polling the last {} blocks",
+                    self.chain_id,
+                    last_height.increment(),
+                    latest_height,
+                    MAX_BACKFILL_BLOCKS
+                );
+            }
+        }
+
+        let mut height = TmHeight::try_from(from_height).map_err(Error::invalid_height)?;
+
+        while height <= latest_height {
+            let batch = self.fetch_batch(height)?;
+
+            self.tx_batch
+                .send(Ok(batch))
+                .map_err(|_| Error::channel_send_failed())?;
+
+            self.last_height = Some(height);
+            height = height.increment();
+        }
+
+        Ok(())
+    }
+
+    /// Collect the IBC events of the block at the given height
+    fn fetch_batch(&self, height: TmHeight) -> Result<EventBatch> {
+        trace!("[{}] fetching events at height {}", self.chain_id, height);
+
+        let response = self
+            .rt
+            .block_on(self.client.block_results(height))
+            .map_err(Error::rpc)?;
+
+        let height = Height::new(self.chain_id.version(), height.value());
+
+        Ok(EventBatch {
+            chain_id: self.chain_id.clone(),
+            height,
+            events: get_events_from_block_results(height, response),
+        })
+    }
+}
+
+/// The height of the first block to poll the events of, after the block at the
+/// last height polled, if any, up to the latest height and limited to the last
+/// [`MAX_BACKFILL_BLOCKS`] blocks.
+fn first_height_to_poll(last_height: Option<u64>, latest_height: u64) -> u64 {
+    match last_height {
+        Some(last_height) => {
+            (last_height + 1).max(latest_height.saturating_sub(MAX_BACKFILL_BLOCKS - 1))
+        }
+        None => latest_height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_poll_starts_at_latest_height() {
+        assert_eq!(first_height_to_poll(None, 42), 42);
+    }
+
+    #[test]
+    fn poll_resumes_after_last_height() {
+        assert_eq!(first_height_to_poll(Some(10), 20), 11);
+        assert_eq!(first_height_to_poll(Some(20), 20), 21);
+    }
+
+    #[test]
+    fn poll_backfills_last_blocks_only() {
+        assert_eq!(
+            first_height_to_poll(Some(10), 10 + 2 * MAX_BACKFILL_BLOCKS),
+            11 + MAX_BACKFILL_BLOCKS
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use alloc::collections::BTreeMap as HashMap;
 
+use tendermint::abci::Event as AbciEvent;
+use tendermint_rpc::endpoint::block_results;
 use tendermint_rpc::{event::Event as RpcEvent, event::EventData as RpcEventData};
 
 use ibc::core::ics02_client::{events as ClientEvents, height::Height};
@@ -70,6 +72,46 @@ pub fn get_all_events(
     Ok(vals)
 }
 
+/// Collect the IBC events from the results of the block at the given height,
+/// starting with a `NewBlock` event, followed by the events emitted in the begin-block,
+/// by the transactions which were successfully delivered, and in the end-block.
+pub fn get_events_from_block_results(
+    height: Height,
+    response: block_results::Response,
+) -> Vec<IbcEvent> {
+    let tx_events = response
+        .txs_results
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|tx_result| tx_result.code.is_ok())
+        .flat_map(|tx_result| tx_result.events);
+
+    let abci_events = response
+        .begin_block_events
+        .unwrap_or_default()
+        .into_iter()
+        .chain(tx_events)
+        .chain(response.end_block_events.unwrap_or_default());
+
+    let mut vals: Vec<IbcEvent> = vec![ClientEvents::NewBlock::new(height).into()];
+
+    for abci_event in abci_events {
+        if let Some(mut ibc_event) = ibc_event_from_abci_event(&abci_event) {
+            ibc_event.set_height(height);
+            tracing::trace!("extracted ibc event {:?}", ibc_event);
+            vals.push(ibc_event);
+        }
+    }
+
+    vals
+}
+
+fn ibc_event_from_abci_event(abci_event: &AbciEvent) -> Option<IbcEvent> {
+    ClientEvents::try_from_tx(abci_event)
+        .or_else(|| ConnectionEvents::try_from_tx(abci_event))
+        .or_else(|| ChannelEvents::try_from_tx(abci_event))
+}
+
 fn send_packet_from_block_events(
     height: Height,
     events: HashMap<String, Vec<String>>,
@@ -86,3 +128,145 @@ fn send_packet_from_block_events(
     }
     vals
 }
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::{responses::DeliverTx, Code, Event as AbciEvent};
+    use tendermint_rpc::endpoint::block_results;
+
+    use ibc::core::ics02_client::events::{self as client_events, CreateClient, NewBlock};
+    use ibc::core::ics04_channel::events::{self as channel_events, SendPacket};
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+
+    use super::get_events_from_block_results;
+
+    const HEIGHT: Height = Height {
+        revision_number: 0,
+        revision_height: 10,
+    };
+
+    fn block_results(
+        begin_block_events: Vec<AbciEvent>,
+        txs_results: Vec<DeliverTx>,
+        end_block_events: Vec<AbciEvent>,
+    ) -> block_results::Response {
+        block_results::Response {
+            height: tendermint::block::Height::from(10_u32),
+            txs_results: Some(txs_results),
+            begin_block_events: Some(begin_block_events),
+            end_block_events: Some(end_block_events),
+            validator_updates: vec![],
+            consensus_param_updates: None,
+        }
+    }
+
+    fn tx_result(code: u32, events: Vec<AbciEvent>) -> DeliverTx {
+        DeliverTx {
+            code: Code::from(code),
+            events,
+            ..DeliverTx::default()
+        }
+    }
+
+    fn create_client() -> IbcEvent {
+        IbcEvent::CreateClient(CreateClient(client_events::Attributes {
+            height: HEIGHT,
+            client_id: "07-tendermint-0".parse().unwrap(),
+            consensus_height: HEIGHT,
+            ..Default::default()
+        }))
+    }
+
+    fn open_init_channel() -> IbcEvent {
+        IbcEvent::OpenInitChannel(channel_events::OpenInit(channel_events::Attributes {
+            height: HEIGHT,
+            port_id: "transfer".parse().unwrap(),
+            channel_id: Some("channel-0".parse().unwrap()),
+            connection_id: "connection-0".parse().unwrap(),
+            counterparty_port_id: "transfer".parse().unwrap(),
+            counterparty_channel_id: None,
+        }))
+    }
+
+    fn send_packet(sequence: u64) -> IbcEvent {
+        IbcEvent::SendPacket(SendPacket {
+            height: HEIGHT,
+            packet: Packet {
+                sequence: Sequence::from(sequence),
+                source_port: "transfer".parse().unwrap(),
+                source_channel: "channel-0".parse().unwrap(),
+                destination_port: "transfer".parse().unwrap(),
+                destination_channel: "channel-1".parse().unwrap(),
+                ..Packet::default()
+            },
+        })
+    }
+
+    fn abci_event(event: IbcEvent) -> AbciEvent {
+        AbciEvent::try_from(event).unwrap()
+    }
+
+    fn non_ibc_event() -> AbciEvent {
+        AbciEvent {
+            type_str: "transfer".to_string(),
+            attributes: vec![],
+        }
+    }
+
+    #[test]
+    fn events_from_block_results() {
+        let response = block_results(
+            vec![abci_event(create_client()), non_ibc_event()],
+            vec![
+                tx_result(0, vec![non_ibc_event(), abci_event(send_packet(1))]),
+                tx_result(0, vec![abci_event(send_packet(2))]),
+            ],
+            vec![abci_event(open_init_channel())],
+        );
+
+        assert_eq!(
+            get_events_from_block_results(HEIGHT, response),
+            vec![
+                IbcEvent::NewBlock(NewBlock::new(HEIGHT)),
+                create_client(),
+                send_packet(1),
+                send_packet(2),
+                open_init_channel(),
+            ]
+        );
+    }
+
+    #[test]
+    fn events_of_failed_txs_are_dropped() {
+        let response = block_results(
+            vec![],
+            vec![
+                tx_result(5, vec![abci_event(send_packet(1))]),
+                tx_result(0, vec![abci_event(send_packet(2))]),
+            ],
+            vec![],
+        );
+
+        assert_eq!(
+            get_events_from_block_results(HEIGHT, response),
+            vec![IbcEvent::NewBlock(NewBlock::new(HEIGHT)), send_packet(2)]
+        );
+    }
+
+    #[test]
+    fn missing_results_yield_new_block_only() {
+        let response = block_results::Response {
+            txs_results: None,
+            begin_block_events: None,
+            end_block_events: None,
+            ..block_results(vec![], vec![], vec![])
+        };
+
+        assert_eq!(
+            get_events_from_block_results(HEIGHT, response),
+            vec![IbcEvent::NewBlock(NewBlock::new(HEIGHT))]
+        );
+    }
+}
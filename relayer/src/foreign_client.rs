@@ -154,6 +154,17 @@ define_error! {
                     e.client_id, e.chain_id, e.description, e.source)
             },
 
+        ClientUpgradeRevision
+            {
+                client_id: ClientId,
+                chain_id: ChainId,
+                description: String,
+            }
+            |e| {
+                format_args!("cannot upgrade client id {0} for chain {1}: {2}",
+                    e.client_id, e.chain_id, e.description)
+            },
+
         ClientEventQuery
             {
                 client_id: ClientId,
@@ -360,6 +371,33 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
 
         info!("[{}] upgrade Height: {}", self, src_height);
 
+        let current_client_state = self
+            .dst_chain
+            .query_client_state(&self.id, Height::zero())
+            .map_err(|e| {
+                ForeignClientError::client_upgrade(
+                    self.id.clone(),
+                    self.dst_chain.id(),
+                    "failed while querying the client state on the destination chain".to_string(),
+                    e,
+                )
+            })?;
+
+        // The client can only be updated with headers from the revision it tracks,
+        // hence the upgrade must be performed at a height of that revision, ie.
+        // before the source chain restarts under its upgraded chain identifier.
+        let current_height = current_client_state.latest_height();
+        if !src_height.is_same_revision(&current_height) {
+            return Err(ForeignClientError::client_upgrade_revision(
+                self.id.clone(),
+                self.src_chain.id(),
+                format!(
+                    "the chain is at height {} while the client tracks revision {}",
+                    src_height, current_height.revision_number
+                ),
+            ));
+        }
+
         let mut msgs = self.build_update_client(src_height)?;
 
         // Query the host chain for the upgraded client state, consensus state & their proofs.
@@ -377,6 +415,27 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
 
         debug!("[{}] upgraded client state {:?}", self, client_state);
 
+        // Subsequent updates of the upgraded client are checked against the
+        // revision number of its chain identifier, eg. `2` for `foo-2`.
+        let upgraded_chain_id = client_state.chain_id();
+        let upgraded_height = client_state.latest_height();
+        if upgraded_height.revision_number != upgraded_chain_id.version()
+            || upgraded_height <= current_height
+        {
+            return Err(ForeignClientError::client_upgrade_revision(
+                self.id.clone(),
+                self.src_chain.id(),
+                format!(
+                    "the upgraded client state for chain {} has latest height {}, \
+                     which is not a height of revision {} above the current client height {}",
+                    upgraded_chain_id,
+                    upgraded_height,
+                    upgraded_chain_id.version(),
+                    current_height
+                ),
+            ));
+        }
+
         let (consensus_state, proof_upgrade_consensus_state) = self
             .src_chain
             .query_upgraded_consensus_state(src_height)
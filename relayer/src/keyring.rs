@@ -10,6 +10,7 @@ use bitcoin::{
     network::constants::Network,
     secp256k1::{Message, Secp256k1, SecretKey},
     util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
+    PublicKey,
 };
 use hdpath::StandardHDPath;
 use ibc::core::ics24_host::identifier::ChainId;
@@ -20,15 +21,20 @@ use sha2::{Digest, Sha256};
 use tiny_keccak::{Hasher, Keccak};
 
 use errors::Error;
+pub use file::{EncryptedFile, Passphrase, PassphraseSource};
 pub use pub_key::EncodedPubKey;
+pub use signer::{LocalSigner, MockSigner, RemoteSigner, TxSigner};
 
 pub mod errors;
+mod file;
 mod pub_key;
+mod signer;
 
 pub type HDPath = StandardHDPath;
 
 pub const KEYSTORE_DEFAULT_FOLDER: &str = ".hermes/keys/";
 pub const KEYSTORE_DISK_BACKEND: &str = "keyring-test";
+pub const KEYSTORE_FILE_BACKEND: &str = "keyring-file";
 pub const KEYSTORE_FILE_EXTENSION: &str = "json";
 
 // /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\
@@ -245,6 +251,7 @@ impl KeyStore for Test {
 pub enum Store {
     Memory,
     Test,
+    File,
 }
 
 impl Default for Store {
@@ -257,6 +264,7 @@ impl Default for Store {
 pub enum KeyRing {
     Memory(Memory),
     Test(Test),
+    File(EncryptedFile),
 }
 
 impl KeyRing {
@@ -265,22 +273,29 @@ impl KeyRing {
             Store::Memory => Ok(Self::Memory(Memory::new(account_prefix.to_string()))),
 
             Store::Test => {
-                let keys_folder = disk_store_path(chain_id.as_str())?;
-
-                // Create keys folder if it does not exist
-                fs::create_dir_all(&keys_folder).map_err(|e| {
-                    Error::key_file_io(
-                        keys_folder.display().to_string(),
-                        "failed to create keys folder".to_string(),
-                        e,
-                    )
-                })?;
+                let keys_folder = disk_store_path(chain_id.as_str(), KEYSTORE_DISK_BACKEND)?;
+                create_keys_folder(&keys_folder)?;
 
                 Ok(Self::Test(Test::new(
                     account_prefix.to_string(),
                     keys_folder,
                 )))
             }
+
+            Store::File => {
+                let keys_folder = disk_store_path(chain_id.as_str(), KEYSTORE_FILE_BACKEND)?;
+                create_keys_folder(&keys_folder)?;
+
+                // Ask for confirmation of the passphrase when it is first chosen
+                let confirm = EncryptedFile::is_empty(&keys_folder);
+                let passphrase = Passphrase::get_or_read(confirm)?;
+
+                Ok(Self::File(EncryptedFile::new(
+                    account_prefix.to_string(),
+                    keys_folder,
+                    passphrase,
+                )))
+            }
         }
     }
 
@@ -288,6 +303,7 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.get_key(key_name),
             KeyRing::Test(d) => d.get_key(key_name),
+            KeyRing::File(f) => f.get_key(key_name),
         }
     }
 
@@ -295,6 +311,7 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.add_key(key_name, key_entry),
             KeyRing::Test(d) => d.add_key(key_name, key_entry),
+            KeyRing::File(f) => f.add_key(key_name, key_entry),
         }
     }
 
@@ -302,6 +319,7 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.remove_key(key_name),
             KeyRing::Test(d) => d.remove_key(key_name),
+            KeyRing::File(f) => f.remove_key(key_name),
         }
     }
 
@@ -309,6 +327,7 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.keys(),
             KeyRing::Test(d) => d.keys(),
+            KeyRing::File(f) => f.keys(),
         }
     }
 
@@ -359,32 +378,37 @@ impl KeyRing {
         address_type: &AddressType,
     ) -> Result<Vec<u8>, Error> {
         let key = self.get_key(key_name)?;
-
-        let private_key_bytes = key.private_key.private_key.to_bytes();
-        match address_type {
-            AddressType::Ethermint { ref pk_type } if pk_type.ends_with(".ethsecp256k1.PubKey") => {
-                let hash = keccak256_hash(msg.as_slice());
-                let s = Secp256k1::signing_only();
-                // SAFETY: hash is 32 bytes, as expected in `Message::from_slice` -- see `keccak256_hash`, hence `unwrap`
-                let sign_msg = Message::from_slice(hash.as_slice()).unwrap();
-                let key = SecretKey::from_slice(private_key_bytes.as_slice())
-                    .map_err(Error::invalid_key_raw)?;
-                let (_, sig_bytes) = s.sign_recoverable(&sign_msg, &key).serialize_compact();
-                Ok(sig_bytes.to_vec())
-            }
-            AddressType::Cosmos | AddressType::Ethermint { .. } => {
-                let signing_key = SigningKey::from_bytes(private_key_bytes.as_slice())
-                    .map_err(Error::invalid_key)?;
-                let signature: Signature = signing_key.sign(&msg);
-                Ok(signature.as_ref().to_vec())
-            }
-        }
+        sign_with_key(&key, &msg, address_type)
     }
 
     pub fn account_prefix(&self) -> &str {
         match self {
             KeyRing::Memory(m) => &m.account_prefix,
             KeyRing::Test(d) => &d.account_prefix,
+            KeyRing::File(f) => &f.account_prefix,
+        }
+    }
+}
+
+/// Sign a message with the private key of the given key entry
+fn sign_with_key(key: &KeyEntry, msg: &[u8], address_type: &AddressType) -> Result<Vec<u8>, Error> {
+    let private_key_bytes = key.private_key.private_key.to_bytes();
+    match address_type {
+        AddressType::Ethermint { ref pk_type } if pk_type.ends_with(".ethsecp256k1.PubKey") => {
+            let hash = keccak256_hash(msg);
+            let s = Secp256k1::signing_only();
+            // SAFETY: hash is 32 bytes, as expected in `Message::from_slice` -- see `keccak256_hash`, hence `unwrap`
+            let sign_msg = Message::from_slice(hash.as_slice()).unwrap();
+            let key = SecretKey::from_slice(private_key_bytes.as_slice())
+                .map_err(Error::invalid_key_raw)?;
+            let (_, sig_bytes) = s.sign_recoverable(&sign_msg, &key).serialize_compact();
+            Ok(sig_bytes.to_vec())
+        }
+        AddressType::Cosmos | AddressType::Ethermint { .. } => {
+            let signing_key =
+                SigningKey::from_bytes(private_key_bytes.as_slice()).map_err(Error::invalid_key)?;
+            let signature: Signature = signing_key.sign(msg);
+            Ok(signature.as_ref().to_vec())
         }
     }
 }
@@ -408,9 +432,14 @@ fn private_key_from_mnemonic(
 
 /// Return an address from a Public Key
 fn get_address(pk: ExtendedPubKey, at: &AddressType) -> Vec<u8> {
+    address_from_public_key(&pk.public_key, at)
+}
+
+/// Return the address corresponding to a secp256k1 public key
+pub fn address_from_public_key(pk: &PublicKey, at: &AddressType) -> Vec<u8> {
     match at {
         AddressType::Ethermint { ref pk_type } if pk_type.ends_with(".ethsecp256k1.PubKey") => {
-            let public_key = pk.public_key.key.serialize_uncompressed();
+            let public_key = pk.key.serialize_uncompressed();
             // 0x04 is [SECP256K1_TAG_PUBKEY_UNCOMPRESSED](https://github.com/bitcoin-core/secp256k1/blob/d7ec49a6893751f068275cc8ddf4993ef7f31756/include/secp256k1.h#L196)
             debug_assert_eq!(public_key[0], 0x04);
 
@@ -421,7 +450,7 @@ fn get_address(pk: ExtendedPubKey, at: &AddressType) -> Vec<u8> {
         }
         AddressType::Cosmos | AddressType::Ethermint { .. } => {
             let mut hasher = Sha256::new();
-            hasher.update(pk.public_key.to_bytes().as_slice());
+            hasher.update(pk.to_bytes().as_slice());
 
             // Read hash digest over the public key bytes & consume hasher
             let pk_hash = hasher.finalize();
@@ -446,17 +475,45 @@ fn decode_bech32(input: &str) -> Result<Vec<u8>, Error> {
     Ok(bytes)
 }
 
-fn disk_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+fn disk_store_path(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
     let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
 
     let folder = Path::new(home.as_path())
         .join(KEYSTORE_DEFAULT_FOLDER)
         .join(folder_name)
-        .join(KEYSTORE_DISK_BACKEND);
+        .join(backend);
 
     Ok(folder)
 }
 
+/// Obtain the passphrase of the encrypted key stores of the given chains, if any,
+/// such that it is read once at startup rather than by the runtime of each chain.
+/// The passphrase is to be confirmed if none of these stores holds a key yet.
+pub fn read_passphrase<'a>(chain_ids: impl IntoIterator<Item = &'a ChainId>) -> Result<(), Error> {
+    let folders = chain_ids
+        .into_iter()
+        .map(|chain_id| disk_store_path(chain_id.as_str(), KEYSTORE_FILE_BACKEND))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    if folders.is_empty() {
+        return Ok(());
+    }
+
+    let confirm = folders.iter().all(|folder| EncryptedFile::is_empty(folder));
+    Passphrase::get_or_read(confirm).map(|_| ())
+}
+
+/// Create the keys folder if it does not exist
+fn create_keys_folder(keys_folder: &Path) -> Result<(), Error> {
+    fs::create_dir_all(keys_folder).map_err(|e| {
+        Error::key_file_io(
+            keys_folder.display().to_string(),
+            "failed to create keys folder".to_string(),
+            e,
+        )
+    })
+}
+
 fn keccak256_hash(bytes: &[u8]) -> Vec<u8> {
     let mut hasher = Keccak::v256();
     hasher.update(bytes);
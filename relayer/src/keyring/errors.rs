@@ -112,5 +112,114 @@ define_error! {
             |e| {
                 format!("invalid HD path: {0}", e.path)
             },
+
+        KeyEncryption
+            |_| { "failed to encrypt key" },
+
+        KeyDecryption
+            { file_path: String }
+            |e| {
+                format!("failed to decrypt key file at '{}': wrong passphrase or corrupted file",
+                    e.file_path)
+            },
+
+        KeyDerivation
+            |_| { "failed to derive encryption key from passphrase" },
+
+        UnsupportedKeyFileFormat
+            {
+                file_path: String,
+                reason: String,
+            }
+            |e| {
+                format!("unsupported format of encrypted key file at '{}': {}",
+                    e.file_path, e.reason)
+            },
+
+        KeyFileHexDecode
+            { file_path: String }
+            [ TraceError<hex::FromHexError> ]
+            |e| {
+                format!("error decoding hex-encoded field of key file at '{}'",
+                    e.file_path)
+            },
+
+        PassphraseRead
+            { source_name: String }
+            [ TraceError<IoError> ]
+            |e| {
+                format!("failed to read key store passphrase from {}",
+                    e.source_name)
+            },
+
+        MissingPassphraseEnv
+            { name: String }
+            |e| {
+                format!("environment variable {} holding the key store passphrase is not set",
+                    e.name)
+            },
+
+        InvalidPassphraseFd
+            { fd: String }
+            |e| {
+                format!("invalid file descriptor for the key store passphrase: '{}'",
+                    e.fd)
+            },
+
+        UnsupportedPassphraseFd
+            |_| { "reading the key store passphrase from a file descriptor is only supported on Unix" },
+
+        EmptyPassphrase
+            |_| { "the key store passphrase must not be empty" },
+
+        PassphraseMismatch
+            |_| { "the passphrases do not match" },
+
+        InvalidRemoteSignerAddress
+            { address: String }
+            |e| {
+                format!("invalid remote signer address '{}': expected 'unix://<path>' or 'tcp://<host>:<port>'",
+                    e.address)
+            },
+
+        RemoteSignerIo
+            { address: String }
+            [ TraceError<IoError> ]
+            |e| {
+                format!("I/O error while communicating with remote signer at '{}'",
+                    e.address)
+            },
+
+        RemoteSignerRequestEncode
+            [ TraceError<serde_json::Error> ]
+            |_| { "error encoding remote signer request" },
+
+        RemoteSignerResponseDecode
+            { address: String }
+            [ TraceError<serde_json::Error> ]
+            |e| {
+                format!("error decoding response of remote signer at '{}'",
+                    e.address)
+            },
+
+        RemoteSignerRejected
+            {
+                address: String,
+                reason: String,
+            }
+            |e| {
+                format!("remote signer at '{}' rejected the request: {}",
+                    e.address, e.reason)
+            },
+
+        InvalidRemoteSignerResponse
+            {
+                address: String,
+                reason: String,
+            }
+            |e| {
+                format!("invalid response from remote signer at '{}': {}",
+                    e.address, e.reason)
+            },
     }
 }
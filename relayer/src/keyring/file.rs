@@ -0,0 +1,496 @@
+//! Encrypted on-disk key store.
+//!
+//! Each key entry is stored in its own JSON file, encrypted with XChaCha20-Poly1305
+//! under a key derived from a passphrase with scrypt. The name of the key is bound
+//! to the ciphertext as associated data, so that an encrypted key file cannot be
+//! passed off as another key by renaming it.
+
+use core::fmt;
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use once_cell::sync::OnceCell;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::errors::Error;
+use super::{KeyEntry, KeyStore, KEYSTORE_FILE_EXTENSION};
+
+/// Name of the environment variable holding the passphrase of the encrypted key store.
+pub const KEYRING_PASSPHRASE_ENV: &str = "HERMES_KEYRING_PASSPHRASE";
+
+/// Name of the environment variable holding the number of an open file descriptor
+/// from which to read the passphrase of the encrypted key store.
+pub const KEYRING_PASSPHRASE_FD_ENV: &str = "HERMES_KEYRING_PASSPHRASE_FD";
+
+const PASSPHRASE_PROMPT: &str = "Enter the key store passphrase: ";
+
+const ENCRYPTED_KEY_FILE_VERSION: u32 = 1;
+
+const KDF_ALGORITHM: &str = "scrypt";
+const CIPHER_ALGORITHM: &str = "xchacha20poly1305";
+
+// Recommended scrypt parameters for interactive use, see https://www.tarsnap.com/scrypt.html
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// The passphrase shared by the encrypted key stores of all the chains,
+/// obtained at most once per process.
+static PASSPHRASE: OnceCell<Passphrase> = OnceCell::new();
+
+/// A passphrase, which is wiped from memory when dropped.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    pub fn new(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+
+    /// The passphrase of the encrypted key stores, which is read from the
+    /// [`PassphraseSource`] selected by the environment upon the first call only.
+    /// When prompting, the user is asked to enter it twice if `confirm` is set.
+    pub fn get_or_read(confirm: bool) -> Result<Passphrase, Error> {
+        PASSPHRASE
+            .get_or_try_init(|| PassphraseSource::from_env()?.read(PASSPHRASE_PROMPT, confirm))
+            .map(Clone::clone)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl Drop for Passphrase {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(<redacted>)")
+    }
+}
+
+/// Where to obtain the passphrase of an encrypted key store from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PassphraseSource {
+    /// The value of the given environment variable.
+    EnvVar(String),
+    /// The first line read from the given open file descriptor, on Unix only.
+    Fd(u32),
+    /// A prompt on the terminal.
+    Prompt,
+}
+
+impl PassphraseSource {
+    /// Selects the passphrase source from the environment: the passphrase is taken
+    /// from [`KEYRING_PASSPHRASE_ENV`] if set, otherwise it is read from the file
+    /// descriptor given in [`KEYRING_PASSPHRASE_FD_ENV`] if set, and the user is
+    /// prompted for it otherwise.
+    pub fn from_env() -> Result<Self, Error> {
+        if std::env::var_os(KEYRING_PASSPHRASE_ENV).is_some() {
+            return Ok(Self::EnvVar(KEYRING_PASSPHRASE_ENV.to_string()));
+        }
+
+        match std::env::var(KEYRING_PASSPHRASE_FD_ENV) {
+            Ok(_) if cfg!(not(unix)) => Err(Error::unsupported_passphrase_fd()),
+            Ok(fd) => fd
+                .trim()
+                .parse()
+                .map(Self::Fd)
+                .map_err(|_| Error::invalid_passphrase_fd(fd)),
+            Err(_) => Ok(Self::Prompt),
+        }
+    }
+
+    /// Obtains the passphrase. When prompting, the user is asked to enter the
+    /// passphrase twice if `confirm` is set, eg. when creating a new key store.
+    ///
+    /// A file descriptor can only be read once, see [`Passphrase::get_or_read`]
+    /// to obtain the passphrase once for the whole process.
+    pub fn read(&self, prompt: &str, confirm: bool) -> Result<Passphrase, Error> {
+        let passphrase = match self {
+            Self::EnvVar(name) => Passphrase::new(
+                std::env::var(name).map_err(|_| Error::missing_passphrase_env(name.clone()))?,
+            ),
+            Self::Fd(fd) => {
+                // Avoid `unsafe` by reading the file descriptor through its `/dev/fd` entry.
+                let path = format!("/dev/fd/{}", fd);
+                let contents =
+                    Zeroizing::new(fs::read_to_string(&path).map_err(|e| {
+                        Error::passphrase_read(format!("file descriptor {}", fd), e)
+                    })?);
+                Passphrase::new(contents.lines().next().unwrap_or_default().to_string())
+            }
+            Self::Prompt => {
+                let passphrase = Passphrase::new(
+                    rpassword::prompt_password_stderr(prompt)
+                        .map_err(|e| Error::passphrase_read("terminal".to_string(), e))?,
+                );
+
+                if confirm {
+                    let confirmation = Passphrase::new(
+                        rpassword::prompt_password_stderr("Repeat the passphrase: ")
+                            .map_err(|e| Error::passphrase_read("terminal".to_string(), e))?,
+                    );
+
+                    if passphrase != confirmation {
+                        return Err(Error::passphrase_mismatch());
+                    }
+                }
+
+                passphrase
+            }
+        };
+
+        if passphrase.0.is_empty() {
+            return Err(Error::empty_passphrase());
+        }
+
+        Ok(passphrase)
+    }
+}
+
+/// The on-disk format of an encrypted key entry.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EncryptedKeyFile {
+    version: u32,
+    kdf: KdfParams,
+    cipher: String,
+    /// Hex-encoded nonce
+    nonce: String,
+    /// Hex-encoded ciphertext of the JSON-encoded `KeyEntry`
+    ciphertext: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    /// Hex-encoded salt
+    salt: String,
+}
+
+impl EncryptedKeyFile {
+    fn encrypt(
+        key_name: &str,
+        key_entry: &KeyEntry,
+        passphrase: &Passphrase,
+    ) -> Result<Self, Error> {
+        let plaintext = Zeroizing::new(serde_json::to_vec(key_entry).map_err(Error::encode)?);
+
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let kdf = KdfParams {
+            algorithm: KDF_ALGORITHM.to_string(),
+            log_n: SCRYPT_LOG_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt: hex::encode(salt),
+        };
+
+        let key = derive_key(passphrase, &kdf, &salt)?;
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(key.as_ref()));
+
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext.as_slice(),
+                    aad: key_name.as_bytes(),
+                },
+            )
+            .map_err(|_| Error::key_encryption())?;
+
+        Ok(Self {
+            version: ENCRYPTED_KEY_FILE_VERSION,
+            kdf,
+            cipher: CIPHER_ALGORITHM.to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    fn decrypt(
+        &self,
+        key_name: &str,
+        passphrase: &Passphrase,
+        file_path: &str,
+    ) -> Result<KeyEntry, Error> {
+        let unsupported =
+            |reason: String| Error::unsupported_key_file_format(file_path.to_string(), reason);
+
+        if self.version != ENCRYPTED_KEY_FILE_VERSION {
+            return Err(unsupported(format!("version {}", self.version)));
+        }
+
+        if self.kdf.algorithm != KDF_ALGORITHM {
+            return Err(unsupported(format!(
+                "key derivation {}",
+                self.kdf.algorithm
+            )));
+        }
+
+        if self.cipher != CIPHER_ALGORITHM {
+            return Err(unsupported(format!("cipher {}", self.cipher)));
+        }
+
+        // Bound the cost of the key derivation, so that an edited key file
+        // cannot make the relayer allocate an arbitrary amount of memory.
+        if self.kdf.log_n > SCRYPT_LOG_N || self.kdf.r > SCRYPT_R || self.kdf.p > SCRYPT_P {
+            return Err(unsupported(format!(
+                "scrypt parameters log_n={}, r={}, p={} (at most log_n={}, r={}, p={})",
+                self.kdf.log_n, self.kdf.r, self.kdf.p, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P
+            )));
+        }
+
+        let decode = |value: &str| {
+            hex::decode(value).map_err(|e| Error::key_file_hex_decode(file_path.to_string(), e))
+        };
+
+        let salt = decode(&self.kdf.salt)?;
+        let nonce = decode(&self.nonce)?;
+        let ciphertext = decode(&self.ciphertext)?;
+
+        if nonce.len() != NONCE_LEN {
+            return Err(unsupported(format!("nonce length {}", nonce.len())));
+        }
+
+        let key = derive_key(passphrase, &self.kdf, &salt)?;
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(key.as_ref()));
+
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    XNonce::from_slice(&nonce),
+                    Payload {
+                        msg: ciphertext.as_slice(),
+                        aad: key_name.as_bytes(),
+                    },
+                )
+                .map_err(|_| Error::key_decryption(file_path.to_string()))?,
+        );
+
+        serde_json::from_slice(plaintext.as_slice())
+            .map_err(|e| Error::key_file_decode(file_path.to_string(), e))
+    }
+}
+
+fn derive_key(
+    passphrase: &Passphrase,
+    kdf: &KdfParams,
+    salt: &[u8],
+) -> Result<Zeroizing<[u8; KEY_LEN]>, Error> {
+    let params =
+        scrypt::Params::new(kdf.log_n, kdf.r, kdf.p).map_err(|_| Error::key_derivation())?;
+
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key[..])
+        .map_err(|_| Error::key_derivation())?;
+
+    Ok(key)
+}
+
+/// A key store which keeps each key entry in its own encrypted file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedFile {
+    pub(crate) account_prefix: String,
+    store: PathBuf,
+    #[serde(skip)]
+    passphrase: Passphrase,
+}
+
+impl EncryptedFile {
+    pub fn new(account_prefix: String, store: PathBuf, passphrase: Passphrase) -> Self {
+        Self {
+            account_prefix,
+            store,
+            passphrase,
+        }
+    }
+
+    /// Returns `true` if the store at the given location does not hold any key yet.
+    pub fn is_empty(store: &Path) -> bool {
+        key_files(store).map_or(true, |files| files.is_empty())
+    }
+
+    fn key_file(&self, key_name: &str) -> PathBuf {
+        let mut key_file = self.store.join(key_name);
+        key_file.set_extension(KEYSTORE_FILE_EXTENSION);
+        key_file
+    }
+}
+
+impl KeyStore for EncryptedFile {
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        let key_file = self.key_file(key_name);
+        let file_path = key_file.display().to_string();
+
+        if !key_file.as_path().exists() {
+            return Err(Error::key_file_not_found(file_path));
+        }
+
+        let contents = fs::read(&key_file).map_err(|e| {
+            Error::key_file_io(file_path.clone(), "failed to read file".to_string(), e)
+        })?;
+
+        let encrypted: EncryptedKeyFile = serde_json::from_slice(&contents)
+            .map_err(|e| Error::key_file_decode(file_path.clone(), e))?;
+
+        encrypted.decrypt(key_name, &self.passphrase, &file_path)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error> {
+        let key_file = self.key_file(key_name);
+        let file_path = key_file.display().to_string();
+
+        if key_file.as_path().exists() {
+            return Err(Error::key_already_exist());
+        }
+
+        let encrypted = EncryptedKeyFile::encrypt(key_name, &key_entry, &self.passphrase)?;
+        let contents = serde_json::to_vec_pretty(&encrypted)
+            .map_err(|e| Error::key_file_encode(file_path.clone(), e))?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        // Only the owner may read the encrypted key file
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&key_file).map_err(|e| {
+            Error::key_file_io(file_path.clone(), "failed to create file".to_string(), e)
+        })?;
+
+        file.write_all(&contents)
+            .map_err(|e| Error::key_file_io(file_path, "failed to write file".to_string(), e))?;
+
+        Ok(())
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        let key_file = self.key_file(key_name);
+
+        fs::remove_file(&key_file)
+            .map_err(|e| Error::remove_io_fail(key_file.display().to_string(), e))?;
+
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        key_files(&self.store)?
+            .into_iter()
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+/// Lists the names of the keys in the store at the given location.
+fn key_files(store: &Path) -> Result<Vec<String>, Error> {
+    let dir = fs::read_dir(store).map_err(|e| {
+        Error::key_file_io(
+            store.display().to_string(),
+            "failed to list keys".to_string(),
+            e,
+        )
+    })?;
+
+    let ext = OsStr::new(KEYSTORE_FILE_EXTENSION);
+
+    Ok(dir
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension() == Some(ext))
+        .flat_map(|path| path.file_stem().map(OsStr::to_owned))
+        .flat_map(|stem| stem.to_str().map(ToString::to_string))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::util::bip32::ExtendedPubKey;
+
+    use crate::config::AddressType;
+    use crate::keyring::{get_address, private_key_from_mnemonic, HDPath};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn key_entry() -> KeyEntry {
+        let hd_path: HDPath = "m/44'/118'/0'/0/0".parse().unwrap();
+        let private_key = private_key_from_mnemonic(MNEMONIC, &hd_path).unwrap();
+        let public_key = ExtendedPubKey::from_private(&Secp256k1::new(), &private_key);
+        let address = get_address(public_key, &AddressType::Cosmos);
+
+        KeyEntry {
+            public_key,
+            private_key,
+            account: "cosmos1test".to_string(),
+            address,
+        }
+    }
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let passphrase = Passphrase::new("correct horse battery staple".to_string());
+        let entry = key_entry();
+
+        let encrypted = EncryptedKeyFile::encrypt("relayer", &entry, &passphrase).unwrap();
+        assert!(!encrypted.ciphertext.contains(&hex::encode(&entry.address)));
+
+        let decrypted = encrypted.decrypt("relayer", &passphrase, "test").unwrap();
+        assert_eq!(decrypted, entry);
+    }
+
+    #[test]
+    fn decrypt_fails_with_wrong_passphrase_or_name() {
+        let passphrase = Passphrase::new("correct horse battery staple".to_string());
+        let encrypted = EncryptedKeyFile::encrypt("relayer", &key_entry(), &passphrase).unwrap();
+
+        let wrong_passphrase = Passphrase::new("wrong".to_string());
+        assert!(encrypted
+            .decrypt("relayer", &wrong_passphrase, "test")
+            .is_err());
+
+        assert!(encrypted.decrypt("other", &passphrase, "test").is_err());
+    }
+
+    #[test]
+    fn decrypt_rejects_costlier_kdf_params() {
+        let passphrase = Passphrase::new("correct horse battery staple".to_string());
+        let encrypted = EncryptedKeyFile::encrypt("relayer", &key_entry(), &passphrase).unwrap();
+
+        let mut costlier = encrypted;
+        costlier.kdf.log_n = 30;
+
+        assert!(costlier.decrypt("relayer", &passphrase, "test").is_err());
+    }
+
+    #[test]
+    fn passphrase_is_redacted() {
+        let passphrase = Passphrase::new("secret".to_string());
+        assert!(!format!("{:?}", passphrase).contains("secret"));
+    }
+}
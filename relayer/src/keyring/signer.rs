@@ -0,0 +1,540 @@
+//! Signing of transactions, either with a key held in the key store,
+//! or by a remote signer which holds the key on behalf of the relayer.
+//!
+//! # Remote signer protocol
+//!
+//! The relayer opens a connection to the signer for each request, either over
+//! a Unix domain socket (`unix:///path/to/socket`) or over TCP (`tcp://host:port`).
+//! It then writes a single request as a JSON object followed by a newline, and
+//! reads back a single JSON response followed by a newline.
+//!
+//! Requesting the public key of a signing key:
+//!
+//! ```json
+//! {"method":"public_key","chain_id":"ibc-0","key_name":"relayer"}
+//! ```
+//!
+//! ```json
+//! {"public_key":"<hex-encoded compressed secp256k1 public key>"}
+//! ```
+//!
+//! Requesting a signature over a message, i.e. the protobuf encoding of a `SignDoc`:
+//!
+//! ```json
+//! {"method":"sign","chain_id":"ibc-0","key_name":"relayer","algorithm":"secp256k1_sha256","message":"<hex>"}
+//! ```
+//!
+//! ```json
+//! {"signature":"<hex-encoded 64-byte compact signature>"}
+//! ```
+//!
+//! The `algorithm` is either `secp256k1_sha256`, where the signature is computed
+//! over the SHA-256 digest of the message, or `eth_secp256k1_keccak256` for Ethermint
+//! chains, where it is computed over the Keccak-256 digest of the message.
+//!
+//! A signer which refuses to handle a request responds with `{"error":"<reason>"}`.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use bitcoin::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use super::errors::Error;
+use super::{sign_with_key, KeyEntry, KeyRing};
+use crate::config::{AddressType, RemoteSignerConfig};
+
+/// Length of a compressed secp256k1 public key
+const PUBLIC_KEY_LEN: usize = 33;
+
+/// Length of a compact secp256k1 signature
+const SIGNATURE_LEN: usize = 64;
+
+/// Signs transactions on behalf of the relayer.
+pub trait TxSigner {
+    /// The public key of the signing key.
+    fn public_key(&self) -> Result<PublicKey, Error>;
+
+    /// Sign the given message, i.e. the protobuf encoding of a `SignDoc`.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+impl<S: TxSigner + ?Sized> TxSigner for &S {
+    fn public_key(&self) -> Result<PublicKey, Error> {
+        (**self).public_key()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        (**self).sign(message)
+    }
+}
+
+/// Signs with a key held in the key store.
+#[derive(Debug)]
+pub struct LocalSigner<'a> {
+    keyring: &'a KeyRing,
+    key_name: &'a str,
+    address_type: &'a AddressType,
+}
+
+impl<'a> LocalSigner<'a> {
+    pub fn new(keyring: &'a KeyRing, key_name: &'a str, address_type: &'a AddressType) -> Self {
+        Self {
+            keyring,
+            key_name,
+            address_type,
+        }
+    }
+}
+
+impl TxSigner for LocalSigner<'_> {
+    fn public_key(&self) -> Result<PublicKey, Error> {
+        Ok(self.keyring.get_key(self.key_name)?.public_key.public_key)
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.keyring
+            .sign_msg(self.key_name, message.to_vec(), self.address_type)
+    }
+}
+
+/// The signature algorithm requested from a remote signer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignAlgorithm {
+    Secp256k1Sha256,
+    EthSecp256k1Keccak256,
+}
+
+impl SignAlgorithm {
+    pub fn for_address_type(address_type: &AddressType) -> Self {
+        match address_type {
+            AddressType::Ethermint { pk_type } if pk_type.ends_with(".ethsecp256k1.PubKey") => {
+                Self::EthSecp256k1Keccak256
+            }
+            AddressType::Cosmos | AddressType::Ethermint { .. } => Self::Secp256k1Sha256,
+        }
+    }
+}
+
+/// A request sent to a remote signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    PublicKey {
+        chain_id: String,
+        key_name: String,
+    },
+    Sign {
+        chain_id: String,
+        key_name: String,
+        algorithm: SignAlgorithm,
+        /// Hex-encoded message to sign
+        message: String,
+    },
+}
+
+/// A response of a remote signer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerResponse {
+    /// Hex-encoded compressed public key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// Hex-encoded compact signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Endpoint {
+    #[cfg(unix)]
+    Unix(PathBuf),
+    Tcp(String),
+}
+
+impl Endpoint {
+    fn parse(address: &str) -> Result<Self, Error> {
+        match address.split_once("://") {
+            #[cfg(unix)]
+            Some(("unix", path)) if !path.is_empty() => Ok(Self::Unix(PathBuf::from(path))),
+            Some(("tcp", host_port)) if !host_port.is_empty() => {
+                Ok(Self::Tcp(host_port.to_string()))
+            }
+            _ => Err(Error::invalid_remote_signer_address(address.to_string())),
+        }
+    }
+}
+
+/// Signs by sending requests to a remote signer, following the protocol
+/// described in the [module documentation](self).
+#[derive(Debug)]
+pub struct RemoteSigner {
+    address: String,
+    endpoint: Endpoint,
+    timeout: Duration,
+    chain_id: ChainId,
+    key_name: String,
+    algorithm: SignAlgorithm,
+    /// The public key does not change, so it is only requested once
+    public_key: Mutex<Option<PublicKey>>,
+}
+
+impl RemoteSigner {
+    pub fn new(
+        config: &RemoteSignerConfig,
+        chain_id: ChainId,
+        key_name: String,
+        address_type: &AddressType,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            address: config.address.clone(),
+            endpoint: Endpoint::parse(&config.address)?,
+            timeout: config.timeout,
+            chain_id,
+            key_name,
+            algorithm: SignAlgorithm::for_address_type(address_type),
+            public_key: Mutex::new(None),
+        })
+    }
+
+    fn call(&self, request: &SignerRequest) -> Result<SignerResponse, Error> {
+        let mut request =
+            serde_json::to_vec(request).map_err(Error::remote_signer_request_encode)?;
+        request.push(b'\n');
+
+        let response = match &self.endpoint {
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                std::os::unix::net::UnixStream::connect(path).and_then(|stream| {
+                    stream.set_read_timeout(Some(self.timeout))?;
+                    stream.set_write_timeout(Some(self.timeout))?;
+                    exchange(stream, &request)
+                })
+            }
+            Endpoint::Tcp(host_port) => connect_tcp(host_port, self.timeout).and_then(|stream| {
+                stream.set_read_timeout(Some(self.timeout))?;
+                stream.set_write_timeout(Some(self.timeout))?;
+                exchange(stream, &request)
+            }),
+        }
+        .map_err(|e| Error::remote_signer_io(self.address.clone(), e))?;
+
+        let response: SignerResponse = serde_json::from_str(&response)
+            .map_err(|e| Error::remote_signer_response_decode(self.address.clone(), e))?;
+
+        match response.error {
+            Some(reason) => Err(Error::remote_signer_rejected(self.address.clone(), reason)),
+            None => Ok(response),
+        }
+    }
+
+    fn decode_field(
+        &self,
+        field: &str,
+        value: Option<String>,
+        len: usize,
+    ) -> Result<Vec<u8>, Error> {
+        let invalid =
+            |reason: String| Error::invalid_remote_signer_response(self.address.clone(), reason);
+
+        let value = value.ok_or_else(|| invalid(format!("missing field `{}`", field)))?;
+        let bytes =
+            hex::decode(value).map_err(|e| invalid(format!("invalid `{}`: {}", field, e)))?;
+
+        if bytes.len() != len {
+            return Err(invalid(format!(
+                "expected `{}` to be {} bytes long, got {}",
+                field,
+                len,
+                bytes.len()
+            )));
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl TxSigner for RemoteSigner {
+    fn public_key(&self) -> Result<PublicKey, Error> {
+        let mut cached = self
+            .public_key
+            .lock()
+            .expect("poisoned lock on remote signer public key");
+
+        if let Some(public_key) = *cached {
+            return Ok(public_key);
+        }
+
+        let response = self.call(&SignerRequest::PublicKey {
+            chain_id: self.chain_id.to_string(),
+            key_name: self.key_name.clone(),
+        })?;
+
+        let bytes = self.decode_field("public_key", response.public_key, PUBLIC_KEY_LEN)?;
+        let public_key = PublicKey::from_slice(&bytes).map_err(|e| {
+            Error::invalid_remote_signer_response(self.address.clone(), e.to_string())
+        })?;
+
+        *cached = Some(public_key);
+        Ok(public_key)
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let response = self.call(&SignerRequest::Sign {
+            chain_id: self.chain_id.to_string(),
+            key_name: self.key_name.clone(),
+            algorithm: self.algorithm,
+            message: hex::encode(message),
+        })?;
+
+        self.decode_field("signature", response.signature, SIGNATURE_LEN)
+    }
+}
+
+fn connect_tcp(host_port: &str, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_error = None;
+
+    for addr in host_port.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "could not resolve address")))
+}
+
+/// Send a newline-terminated request and read back a newline-terminated response.
+fn exchange<S: Read + Write>(mut stream: S, request: &[u8]) -> io::Result<String> {
+    stream.write_all(request)?;
+    stream.flush()?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+
+    Ok(response)
+}
+
+/// A signer holding its key in memory, which records the messages it signs.
+/// Useful as a test double, and as the backend of a test remote signer.
+#[derive(Debug)]
+pub struct MockSigner {
+    key: KeyEntry,
+    address_type: AddressType,
+    signed: Mutex<Vec<Vec<u8>>>,
+}
+
+impl MockSigner {
+    pub fn new(key: KeyEntry, address_type: AddressType) -> Self {
+        Self {
+            key,
+            address_type,
+            signed: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The messages signed so far, in order.
+    pub fn signed_messages(&self) -> Vec<Vec<u8>> {
+        self.signed
+            .lock()
+            .expect("poisoned lock on mock signer messages")
+            .clone()
+    }
+
+    /// Handle a request of the remote signer protocol.
+    pub fn handle(&self, request: SignerRequest) -> SignerResponse {
+        let result = match request {
+            SignerRequest::PublicKey { .. } => self.public_key().map(|public_key| SignerResponse {
+                public_key: Some(hex::encode(public_key.to_bytes())),
+                ..Default::default()
+            }),
+            SignerRequest::Sign { message, .. } => match hex::decode(message) {
+                Ok(message) => self.sign(&message).map(|signature| SignerResponse {
+                    signature: Some(hex::encode(signature)),
+                    ..Default::default()
+                }),
+                Err(e) => Ok(SignerResponse {
+                    error: Some(format!("invalid message: {}", e)),
+                    ..Default::default()
+                }),
+            },
+        };
+
+        result.unwrap_or_else(|e| SignerResponse {
+            error: Some(e.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+impl TxSigner for MockSigner {
+    fn public_key(&self) -> Result<PublicKey, Error> {
+        Ok(self.key.public_key.public_key)
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let signature = sign_with_key(&self.key, message, &self.address_type)?;
+
+        self.signed
+            .lock()
+            .expect("poisoned lock on mock signer messages")
+            .push(message.to_vec());
+
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::keyring::{HDPath, Store};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn keyring() -> KeyRing {
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let hd_path: HDPath = "m/44'/118'/0'/0/0".parse().unwrap();
+
+        let mut keyring = KeyRing::new(Store::Memory, "cosmos", &chain_id).unwrap();
+        let key = keyring
+            .key_from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos)
+            .unwrap();
+        keyring.add_key("relayer", key).unwrap();
+
+        keyring
+    }
+
+    /// Serve the given number of connections with the given signer.
+    fn serve(signer: Arc<MockSigner>, connections: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("tcp://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+
+                let mut line = String::new();
+                BufReader::new(&stream).read_line(&mut line).unwrap();
+
+                let response = match serde_json::from_str(&line) {
+                    Ok(request) => signer.handle(request),
+                    Err(e) => SignerResponse {
+                        error: Some(e.to_string()),
+                        ..Default::default()
+                    },
+                };
+
+                let mut response = serde_json::to_vec(&response).unwrap();
+                response.push(b'\n');
+                stream.write_all(&response).unwrap();
+            }
+        });
+
+        address
+    }
+
+    fn remote_signer(address: String) -> RemoteSigner {
+        let config = RemoteSignerConfig {
+            address,
+            timeout: Duration::from_secs(5),
+        };
+
+        RemoteSigner::new(
+            &config,
+            ChainId::new("ibc".to_string(), 0),
+            "relayer".to_string(),
+            &AddressType::Cosmos,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn mock_signer_matches_local_signer() {
+        let keyring = keyring();
+        let local = LocalSigner::new(&keyring, "relayer", &AddressType::Cosmos);
+        let mock = MockSigner::new(keyring.get_key("relayer").unwrap(), AddressType::Cosmos);
+
+        assert_eq!(local.public_key().unwrap(), mock.public_key().unwrap());
+        assert_eq!(local.sign(b"doc").unwrap(), mock.sign(b"doc").unwrap());
+        assert_eq!(mock.signed_messages(), vec![b"doc".to_vec()]);
+    }
+
+    #[test]
+    fn remote_signer_roundtrip() {
+        let keyring = keyring();
+        let local = LocalSigner::new(&keyring, "relayer", &AddressType::Cosmos);
+        let mock = Arc::new(MockSigner::new(
+            keyring.get_key("relayer").unwrap(),
+            AddressType::Cosmos,
+        ));
+
+        // The public key is only requested once
+        let remote = remote_signer(serve(mock.clone(), 3));
+
+        assert_eq!(remote.public_key().unwrap(), local.public_key().unwrap());
+        assert_eq!(remote.public_key().unwrap(), local.public_key().unwrap());
+        assert_eq!(
+            remote.sign(b"doc 1").unwrap(),
+            local.sign(b"doc 1").unwrap()
+        );
+        assert_eq!(
+            remote.sign(b"doc 2").unwrap(),
+            local.sign(b"doc 2").unwrap()
+        );
+
+        assert_eq!(
+            mock.signed_messages(),
+            vec![b"doc 1".to_vec(), b"doc 2".to_vec()]
+        );
+    }
+
+    #[test]
+    fn remote_signer_error_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let remote = remote_signer(format!("tcp://{}", listener.local_addr().unwrap()));
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            stream
+                .write_all(b"{\"error\":\"key is locked\"}\n")
+                .unwrap();
+        });
+
+        let err = remote.sign(b"doc").unwrap_err();
+        assert!(err.to_string().contains("key is locked"));
+    }
+
+    #[test]
+    fn remote_signer_address() {
+        assert_eq!(
+            Endpoint::parse("tcp://localhost:26659").unwrap(),
+            Endpoint::Tcp("localhost:26659".to_string())
+        );
+
+        #[cfg(unix)]
+        assert_eq!(
+            Endpoint::parse("unix:///run/signer.sock").unwrap(),
+            Endpoint::Unix(PathBuf::from("/run/signer.sock"))
+        );
+
+        for address in ["localhost:26659", "tcp://", "unix://", "http://localhost"] {
+            assert!(Endpoint::parse(address).is_err(), "{}", address);
+        }
+    }
+}
@@ -33,6 +33,7 @@ pub mod object;
 pub mod registry;
 pub mod rest;
 pub mod sdk_error;
+pub mod store;
 pub mod supervisor;
 pub mod telemetry;
 pub mod transfer;
@@ -1,3 +1,5 @@
+use core::time::Duration;
+
 use itertools::Itertools;
 
 use tendermint_light_client::{
@@ -9,7 +11,6 @@ use tendermint_light_client::{
     types::Height as TMHeight,
     types::{LightBlock, PeerId, Status},
 };
-use tendermint_rpc as rpc;
 
 use ibc::{
     clients::ics07_tendermint::{
@@ -30,14 +31,20 @@ use ibc::{
 };
 use tracing::trace;
 
-use crate::{chain::CosmosSdkChain, config::ChainConfig, error::Error};
+use crate::{
+    chain::cosmos::endpoints::EndpointPool, chain::CosmosSdkChain, config::ChainConfig,
+    error::Error,
+};
 
 use super::Verified;
 
 pub struct LightClient {
     chain_id: ChainId,
+    /// The identifier of the node of the endpoint active when the light client was created
     peer_id: PeerId,
-    io: components::io::ProdIo,
+    rpc_timeout: Duration,
+    /// The endpoints of the chain, the light blocks are fetched from the active one
+    endpoints: EndpointPool,
 }
 
 impl super::LightClient<CosmosSdkChain> for LightClient {
@@ -164,19 +171,30 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
 }
 
 impl LightClient {
-    pub fn from_config(config: &ChainConfig, peer_id: PeerId) -> Result<Self, Error> {
-        let rpc_client = rpc::HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
-
-        let io = components::io::ProdIo::new(peer_id, rpc_client, Some(config.rpc_timeout));
-
+    pub fn from_config(
+        config: &ChainConfig,
+        endpoints: EndpointPool,
+        peer_id: PeerId,
+    ) -> Result<Self, Error> {
         Ok(Self {
             chain_id: config.id.clone(),
             peer_id,
-            io,
+            rpc_timeout: config.rpc_timeout,
+            endpoints,
         })
     }
 
+    /// The identifier of the node of the active endpoint, which changes upon a failover
+    fn peer_id(&self) -> PeerId {
+        self.endpoints.active_peer_id().unwrap_or(self.peer_id)
+    }
+
+    /// The IO component fetching light blocks from the active endpoint
+    fn io(&self) -> components::io::ProdIo {
+        let rpc_client = self.endpoints.active().rpc_client.clone();
+        components::io::ProdIo::new(self.peer_id(), rpc_client, Some(self.rpc_timeout))
+    }
+
     fn prepare_client(&self, client_state: &AnyClientState) -> Result<TmLightClient, Error> {
         let clock = components::clock::SystemClock;
         let hasher = operations::hasher::ProdHasher;
@@ -198,13 +216,13 @@ impl LightClient {
         };
 
         Ok(TmLightClient::new(
-            self.peer_id,
+            self.peer_id(),
             params,
             clock,
             scheduler,
             verifier,
             hasher,
-            self.io.clone(),
+            self.io(),
         ))
     }
 
@@ -223,9 +241,10 @@ impl LightClient {
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
         use tendermint_light_client::components::io::Io;
 
-        self.io
-            .fetch_light_block(height)
-            .map_err(|e| Error::light_client_io(self.chain_id.to_string(), e))
+        self.io().fetch_light_block(height).map_err(|e| {
+            self.endpoints.record_failure();
+            Error::light_client_io(self.chain_id.to_string(), e)
+        })
     }
 
     fn adjust_headers(
@@ -1,7 +1,10 @@
+use core::ops::RangeInclusive;
+
 use ibc::{
     core::{
         ics03_connection::connection::State as ConnectionState,
         ics04_channel::channel::State as ChannelState,
+        ics04_channel::packet::Sequence,
         ics24_host::identifier::{ChannelId, PortChannelId, PortId},
     },
     events::IbcEvent,
@@ -14,6 +17,7 @@ use crate::channel::{Channel, ChannelSide};
 use crate::link::error::LinkError;
 use crate::link::relay_path::RelayPath;
 
+mod batcher;
 pub mod error;
 mod operational_data;
 mod pending;
@@ -26,6 +30,8 @@ use tx_hashes::TxHashes;
 // Re-export the telemetries summary
 pub use relay_summary::RelaySummary;
 
+pub use batcher::{Batcher, Batchers};
+
 #[derive(Clone, Debug)]
 pub struct LinkParameters {
     pub src_port_id: PortId,
@@ -161,30 +167,45 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         Link::new(channel, with_tx_confirmation)
     }
 
-    /// Implements the `packet-recv` CLI
-    pub fn build_and_send_recv_packet_messages(&mut self) -> Result<Vec<IbcEvent>, LinkError> {
-        self.a_to_b.build_recv_packet_and_timeout_msgs(None)?;
+    /// Implements the `packet-recv` CLI, relaying only the packets whose sequence is within
+    /// one of the `seq_ranges`, or all pending packets if no range is given.
+    pub fn build_and_send_recv_packet_messages(
+        &mut self,
+        seq_ranges: &[RangeInclusive<Sequence>],
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        self.a_to_b
+            .build_recv_packet_and_timeout_msgs(None, seq_ranges)?;
 
-        let mut results = vec![];
+        self.relay_scheduled_operational_data()
+    }
 
-        // Block waiting for all of the scheduled data (until `None` is returned)
-        while let Some(odata) = self.a_to_b.fetch_scheduled_operational_data() {
-            let mut last_res = self
-                .a_to_b
-                .relay_from_operational_data::<relay_sender::SyncSender>(odata)?;
-            results.append(&mut last_res.events);
-        }
+    /// Implements the `packet-ack` CLI, relaying only the acknowledgments of the packets whose
+    /// sequence is within one of the `seq_ranges`, or all pending ones if no range is given.
+    pub fn build_and_send_ack_packet_messages(
+        &mut self,
+        seq_ranges: &[RangeInclusive<Sequence>],
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        self.a_to_b.build_packet_ack_msgs(None, seq_ranges)?;
 
-        Ok(results)
+        self.relay_scheduled_operational_data()
     }
 
-    /// Implements the `packet-ack` CLI
-    pub fn build_and_send_ack_packet_messages(&mut self) -> Result<Vec<IbcEvent>, LinkError> {
-        self.a_to_b.build_packet_ack_msgs(None)?;
+    /// Implements the `clear packets` CLI
+    pub fn build_and_send_packet_messages_in_height_range(
+        &mut self,
+        from_height: Height,
+        to_height: Height,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        self.a_to_b
+            .build_msgs_from_height_range(from_height, to_height)?;
 
+        self.relay_scheduled_operational_data()
+    }
+
+    fn relay_scheduled_operational_data(&mut self) -> Result<Vec<IbcEvent>, LinkError> {
         let mut results = vec![];
 
-        // Block waiting for all of the scheduled data
+        // Block waiting for all of the scheduled data (until `None` is returned)
         while let Some(odata) = self.a_to_b.fetch_scheduled_operational_data() {
             let mut last_res = self
                 .a_to_b
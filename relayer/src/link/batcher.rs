@@ -0,0 +1,685 @@
+//! Batching of the messages submitted by the paths relaying to the same chain.
+//!
+//! Each path assembles its own operational data, prepending a client update
+//! to its messages, such that paths which share a client on the chain they
+//! relay to would otherwise each pay for updating it to the same height.
+//! The batcher of a chain instead collects the messages submitted by all the
+//! paths within a short window, merges those paid with the same fee policy,
+//! drops the client updates already carried by the batch, and splits the
+//! merged messages into transactions which respect the `max_msg_num` and
+//! `max_tx_size` of the chain. A client update is always carried by the same
+//! transaction as the messages which depend on it, and all the transactions
+//! of a batch are signed with the same key.
+//!
+//! The messages which are not carried by a transaction accepted by the chain
+//! are handed back to their submitters, to be submitted on their own.
+//!
+//! Only the packet workers, which relay through the asynchronous sender,
+//! submit their messages through the batchers.
+
+use alloc::collections::BTreeMap as HashMap;
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
+
+use crossbeam_channel::{Receiver, Sender};
+use prost_types::Any;
+use tendermint_proto::Protobuf;
+use tendermint_rpc::endpoint::broadcast::tx_sync;
+use tracing::{debug, error, warn};
+
+use ibc::core::ics02_client::header::Header;
+use ibc::core::ics02_client::msgs::update_client::{MsgUpdateAnyClient, TYPE_URL};
+use ibc::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc::events::IbcEvent;
+use ibc::Height;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::CheckTxResponses;
+use crate::config::FeePolicy;
+use crate::error::Error;
+use crate::util::lock::{LockExt, RwArc};
+
+/// The outcome of a submission to a batcher.
+#[derive(Clone, Debug, Default)]
+pub struct BatchReply {
+    /// The responses of the transactions which carried messages of the submission
+    pub responses: Vec<tx_sync::Response>,
+    /// The error events of the messages dropped from those transactions,
+    /// because they failed the simulation
+    pub dropped_events: Vec<IbcEvent>,
+    /// The messages of the submission which were not carried by a transaction
+    /// accepted by the chain, and are thus to be submitted on their own
+    pub unsent: Vec<Any>,
+}
+
+struct BatchRequest {
+    msgs: Vec<Any>,
+    fee_policy: Option<FeePolicy>,
+    reply_to: Sender<BatchReply>,
+}
+
+/// A message of a batch, along with the submissions it is carried for.
+#[derive(Clone, Debug)]
+struct BatchedMsg {
+    msg: Any,
+    submissions: Vec<usize>,
+}
+
+/// A client update followed by the messages of the batch which depend on it,
+/// or a single message which follows no client update in its submission.
+/// The messages of a group are carried by the same transaction.
+type MsgGroup = Vec<BatchedMsg>;
+
+/// A handle to the batcher of a chain, which submits the messages of the
+/// paths relaying to that chain.
+#[derive(Clone, Debug)]
+pub struct Batcher {
+    chain_id: ChainId,
+    request_tx: Sender<BatchRequest>,
+}
+
+impl Batcher {
+    /// Spawn the batcher of the given chain, which waits for the given window
+    /// after the first submission of a batch for more submissions to merge it with.
+    pub fn spawn<Chain: ChainHandle + 'static>(chain: Chain, window: Duration) -> Self {
+        let chain_id = chain.id();
+        let (request_tx, request_rx) = crossbeam_channel::unbounded();
+
+        let worker = BatchWorker {
+            chain,
+            window,
+            request_rx,
+        };
+
+        thread::spawn(move || worker.run());
+
+        Self {
+            chain_id,
+            request_tx,
+        }
+    }
+
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
+    }
+
+    /// Submit the given messages as part of the next batch, and wait for the replies of the
+    /// transactions which carried them. Returns `None` if the batcher is not running, in
+    /// which case the messages are to be submitted on their own.
+    pub fn submit(&self, msgs: Vec<Any>, fee_policy: Option<FeePolicy>) -> Option<BatchReply> {
+        let (reply_to, reply_rx) = crossbeam_channel::bounded(1);
+
+        let request = BatchRequest {
+            msgs,
+            fee_policy,
+            reply_to,
+        };
+
+        self.request_tx.send(request).ok()?;
+        reply_rx.recv().ok()
+    }
+}
+
+/// The batchers of the chains relayed to, each spawned upon the first request for it.
+#[derive(Clone, Debug)]
+pub struct Batchers {
+    window: Duration,
+    batchers: RwArc<HashMap<ChainId, Batcher>>,
+}
+
+impl Batchers {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            batchers: RwArc::new_lock(HashMap::new()),
+        }
+    }
+
+    /// The batcher of the given chain, spawned with a clone of its handle if there is none yet.
+    pub fn get_or_spawn<Chain: ChainHandle + 'static>(&self, chain: &Chain) -> Batcher {
+        let chain_id = chain.id();
+
+        if let Some(batcher) = self.batchers.acquire_read().get(&chain_id) {
+            return batcher.clone();
+        }
+
+        self.batchers
+            .acquire_write()
+            .entry(chain_id)
+            .or_insert_with(|| Batcher::spawn(chain.clone(), self.window))
+            .clone()
+    }
+}
+
+struct BatchWorker<Chain> {
+    chain: Chain,
+    window: Duration,
+    request_rx: Receiver<BatchRequest>,
+}
+
+impl<Chain: ChainHandle> BatchWorker<Chain> {
+    fn run(self) {
+        while let Ok(first) = self.request_rx.recv() {
+            let deadline = Instant::now() + self.window;
+
+            let mut requests = vec![first];
+            while let Ok(request) = self.request_rx.recv_deadline(deadline) {
+                requests.push(request);
+            }
+
+            for (fee_policy, requests) in group_by_fee_policy(requests) {
+                self.submit_batch(requests, fee_policy);
+            }
+        }
+    }
+
+    fn submit_batch(&self, requests: Vec<BatchRequest>, fee_policy: Option<FeePolicy>) {
+        let (max_msg_num, max_tx_size): (usize, usize) = match self.chain.config() {
+            Ok(config) => (config.max_msg_num.into(), config.max_tx_size.into()),
+            Err(e) => {
+                error!(
+                    "[{}] failed to fetch the chain config, not batching: {}",
+                    self.chain.id(),
+                    e
+                );
+
+                let replies = hand_back_all(&requests);
+                return reply_all(requests, replies);
+            }
+        };
+
+        let submitted: usize = requests.iter().map(|request| request.msgs.len()).sum();
+        let groups = merge_messages(requests.iter().map(|request| &request.msgs));
+        let (txs, oversized) = split_messages(groups, max_msg_num, max_tx_size);
+
+        debug!(
+            "[{}] merged {} message(s) from {} submission(s) into {} transaction(s)",
+            self.chain.id(),
+            submitted,
+            requests.len(),
+            txs.len()
+        );
+
+        if !oversized.is_empty() {
+            debug!(
+                "[{}] {} message(s) do not fit in a single transaction along with their client update, leaving them to their submissions",
+                self.chain.id(),
+                oversized.len()
+            );
+        }
+
+        let tx_msgs = txs
+            .iter()
+            .map(|tx| tx.iter().map(|batched| batched.msg.clone()).collect())
+            .collect();
+
+        let results = match self
+            .chain
+            .send_transactions_and_wait_check_tx(tx_msgs, fee_policy)
+        {
+            Ok(results) => results,
+            Err(e) => {
+                warn!(
+                    "[{}] failed to submit the batched transactions: {}",
+                    self.chain.id(),
+                    e
+                );
+
+                let replies = hand_back_all(&requests);
+                return reply_all(requests, replies);
+            }
+        };
+
+        for result in &results {
+            if let Err(e) = result {
+                warn!(
+                    "[{}] failed to submit batched transaction: {}",
+                    self.chain.id(),
+                    e
+                );
+            }
+        }
+
+        let replies = batch_replies(
+            requests.len(),
+            txs.into_iter().zip(results).collect(),
+            oversized,
+        );
+
+        reply_all(requests, replies);
+    }
+}
+
+fn reply_all(requests: Vec<BatchRequest>, replies: Vec<BatchReply>) {
+    for (request, reply) in requests.into_iter().zip(replies) {
+        // The submitter may have given up waiting for the reply
+        let _ = request.reply_to.send(reply);
+    }
+}
+
+/// The replies which hand all the messages of the given requests back to their submitters.
+fn hand_back_all(requests: &[BatchRequest]) -> Vec<BatchReply> {
+    requests
+        .iter()
+        .map(|request| BatchReply {
+            unsent: request.msgs.clone(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Groups the requests whose transactions are paid the same way, as the messages
+/// of requests with different fee policies cannot be carried by the same transaction.
+fn group_by_fee_policy(requests: Vec<BatchRequest>) -> Vec<(Option<FeePolicy>, Vec<BatchRequest>)> {
+    let mut groups: Vec<(Option<FeePolicy>, Vec<BatchRequest>)> = vec![];
+
+    for request in requests {
+        match groups
+            .iter_mut()
+            .find(|(fee_policy, _)| same_fees(fee_policy, &request.fee_policy))
+        {
+            Some((_, group)) => group.push(request),
+            None => groups.push((request.fee_policy.clone(), vec![request])),
+        }
+    }
+
+    groups
+}
+
+fn same_fees(a: &Option<FeePolicy>, b: &Option<FeePolicy>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => a.fee_granter == b.fee_granter && a.gas_price == b.gas_price,
+        _ => false,
+    }
+}
+
+/// The client and height a message updates a client to, if it is a client update.
+fn client_update(msg: &Any) -> Option<(ClientId, Height)> {
+    if msg.type_url != TYPE_URL {
+        return None;
+    }
+
+    let update = MsgUpdateAnyClient::decode_vec(&msg.value).ok()?;
+    Some((update.client_id, update.header.height()))
+}
+
+/// Merges the messages of the given submissions into groups, in order, keeping only the
+/// first of the updates of a client to the same height. The messages which depend on an
+/// update which was dropped join the group of the update which was kept, and their
+/// submissions are recorded as submissions of that update.
+fn merge_messages<'a>(submissions: impl Iterator<Item = &'a Vec<Any>>) -> Vec<MsgGroup> {
+    let mut groups: Vec<MsgGroup> = vec![];
+    let mut updates: HashMap<(ClientId, Height), usize> = HashMap::new();
+
+    for (submission, msgs) in submissions.enumerate() {
+        // The group of the last client update of the submission
+        let mut update_group = None;
+
+        for msg in msgs {
+            let batched = BatchedMsg {
+                msg: msg.clone(),
+                submissions: vec![submission],
+            };
+
+            match (client_update(msg), update_group) {
+                (Some(update), _) => match updates.get(&update) {
+                    Some(&group) => {
+                        groups[group][0].submissions.push(submission);
+                        update_group = Some(group);
+                    }
+                    None => {
+                        updates.insert(update, groups.len());
+                        update_group = Some(groups.len());
+                        groups.push(vec![batched]);
+                    }
+                },
+                (None, Some(group)) => groups[group].push(batched),
+                (None, None) => groups.push(vec![batched]),
+            }
+        }
+    }
+
+    groups
+}
+
+/// Splits the given groups of messages in transactions of at most `max_msg_num` messages,
+/// whose messages are at most `max_tx_size` bytes long unless there is only one of them,
+/// without splitting any group. The messages of the groups which do not fit in a single
+/// transaction are returned apart.
+fn split_messages(
+    groups: Vec<MsgGroup>,
+    max_msg_num: usize,
+    max_tx_size: usize,
+) -> (Vec<Vec<BatchedMsg>>, Vec<BatchedMsg>) {
+    let mut txs = vec![];
+    let mut oversized = vec![];
+
+    let mut size = 0;
+    let mut tx_msgs: Vec<BatchedMsg> = vec![];
+    for group in groups {
+        let group_size: usize = group
+            .iter()
+            .map(|batched| prost::Message::encoded_len(&batched.msg))
+            .sum();
+
+        if group.len() > max_msg_num || (group.len() > 1 && group_size > max_tx_size) {
+            oversized.extend(group);
+            continue;
+        }
+
+        if tx_msgs.len() + group.len() > max_msg_num
+            || (!tx_msgs.is_empty() && size + group_size > max_tx_size)
+        {
+            txs.push(core::mem::take(&mut tx_msgs));
+            size = 0;
+        }
+
+        size += group_size;
+        tx_msgs.extend(group);
+    }
+    if !tx_msgs.is_empty() {
+        txs.push(tx_msgs);
+    }
+
+    (txs, oversized)
+}
+
+/// Distributes the responses of the transactions of a batch, along with the error events
+/// of the messages dropped from them, to the submissions whose messages they carried.
+/// The messages of the transactions which failed or were rejected
+/// by the chain, along with the given unsent messages, are handed back to their submissions.
+fn batch_replies(
+    submissions: usize,
+    txs: Vec<(Vec<BatchedMsg>, Result<CheckTxResponses, Error>)>,
+    unsent: Vec<BatchedMsg>,
+) -> Vec<BatchReply> {
+    fn hand_back(replies: &mut [BatchReply], msgs: Vec<BatchedMsg>) {
+        for batched in msgs {
+            for submission in batched.submissions {
+                replies[submission].unsent.push(batched.msg.clone());
+            }
+        }
+    }
+
+    let mut replies = vec![BatchReply::default(); submissions];
+
+    for (tx_msgs, result) in txs {
+        match result {
+            Ok(check_tx) if !check_tx.responses.iter().any(|r| r.code.is_err()) => {
+                let mut carried: Vec<usize> = tx_msgs
+                    .iter()
+                    .flat_map(|batched| batched.submissions.iter().copied())
+                    .collect();
+                carried.sort_unstable();
+                carried.dedup();
+
+                for submission in carried {
+                    let reply = &mut replies[submission];
+                    reply.responses.extend(check_tx.responses.iter().cloned());
+                    reply
+                        .dropped_events
+                        .extend(check_tx.dropped_events.iter().cloned());
+                }
+            }
+            _ => hand_back(&mut replies, tx_msgs),
+        }
+    }
+
+    hand_back(&mut replies, unsent);
+
+    replies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::sync::Arc;
+
+    use ibc::core::ics02_client::client_consensus::AnyConsensusState;
+    use ibc::core::ics02_client::client_state::AnyClientState;
+    use ibc::core::ics02_client::header::AnyHeader;
+    use ibc::core::ics02_client::msgs::create_client::MsgCreateAnyClient;
+    use ibc::mock::client_state::{MockClientState, MockConsensusState};
+    use ibc::mock::header::MockHeader;
+    use ibc::test_utils::get_dummy_account_id;
+    use ibc::tx_msg::Msg;
+    use ibc_proto::ibc::core::client::v1::QueryClientStatesRequest;
+    use tendermint::abci::transaction::Hash;
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use crate::chain::handle::ProdChainHandle;
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::chain::mock::MockChain;
+    use crate::chain::runtime::ChainRuntime;
+    use crate::config::types::MaxMsgNum;
+    use crate::link::relay_sender::{AsyncSender, Submit};
+
+    fn update(client_id: &str, height: u64) -> Any {
+        MsgUpdateAnyClient::new(
+            client_id.parse().unwrap(),
+            AnyHeader::Mock(MockHeader::new(Height::new(0, height))),
+            "relayer".to_string().into(),
+        )
+        .to_any()
+    }
+
+    fn msg(value: u8) -> Any {
+        Any {
+            type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+            value: vec![value; 100],
+        }
+    }
+
+    fn create_client(height: u64) -> Any {
+        let header = MockHeader::new(Height::new(0, height));
+
+        MsgCreateAnyClient::new(
+            AnyClientState::Mock(MockClientState::new(header)),
+            AnyConsensusState::Mock(MockConsensusState::new(header)),
+            get_dummy_account_id(),
+        )
+        .unwrap()
+        .to_any()
+    }
+
+    fn check_tx(code: u32) -> CheckTxResponses {
+        CheckTxResponses {
+            responses: vec![tx_sync::Response {
+                code: code.into(),
+                data: Default::default(),
+                log: Default::default(),
+                hash: Hash::new([0; 32]),
+            }],
+            dropped_events: vec![],
+        }
+    }
+
+    #[test]
+    fn deduplicate_client_updates() {
+        let submissions = vec![
+            vec![update("07-tendermint-0", 10), msg(1), msg(2)],
+            vec![update("07-tendermint-0", 10), msg(3)],
+            vec![update("07-tendermint-0", 11), msg(4)],
+            vec![update("07-tendermint-1", 10), msg(5)],
+            vec![msg(6), msg(7)],
+        ];
+
+        let groups = merge_messages(submissions.iter());
+
+        let shape: Vec<Vec<_>> = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|batched| (client_update(&batched.msg), batched.submissions.clone()))
+                    .collect()
+            })
+            .collect();
+
+        let update_of = |client_id: &str, height| {
+            Some((
+                client_id.parse::<ClientId>().unwrap(),
+                Height::new(0, height),
+            ))
+        };
+
+        // The messages which depend on an update are grouped with it
+        assert_eq!(
+            shape,
+            vec![
+                vec![
+                    (update_of("07-tendermint-0", 10), vec![0, 1]),
+                    (None, vec![0]),
+                    (None, vec![0]),
+                    (None, vec![1]),
+                ],
+                vec![(update_of("07-tendermint-0", 11), vec![2]), (None, vec![2])],
+                vec![(update_of("07-tendermint-1", 10), vec![3]), (None, vec![3])],
+                vec![(None, vec![4])],
+                vec![(None, vec![4])],
+            ]
+        );
+    }
+
+    #[test]
+    fn split_by_max_msg_num_and_max_tx_size() {
+        let submissions = vec![(1..=5).map(msg).collect::<Vec<_>>()];
+        let msg_len = prost::Message::encoded_len(&msg(1));
+
+        let tx_lens = |max_msg_num, max_tx_size| {
+            split_messages(merge_messages(submissions.iter()), max_msg_num, max_tx_size)
+                .0
+                .iter()
+                .map(Vec::len)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(tx_lens(2, 1_000_000), vec![2, 2, 1]);
+        assert_eq!(tx_lens(30, 3 * msg_len), vec![3, 2]);
+        assert_eq!(tx_lens(30, 3 * msg_len - 1), vec![2, 2, 1]);
+        assert_eq!(tx_lens(30, 2 * msg_len), vec![2, 2, 1]);
+        assert_eq!(tx_lens(30, msg_len - 1), vec![1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn split_without_splitting_groups() {
+        let submissions = vec![
+            vec![msg(1)],
+            vec![update("07-tendermint-0", 10), msg(2), msg(3)],
+        ];
+
+        let split = |max_msg_num| {
+            let (txs, oversized) =
+                split_messages(merge_messages(submissions.iter()), max_msg_num, 1_000_000);
+            let txs: Vec<_> = txs.iter().map(Vec::len).collect();
+
+            (txs, oversized.len())
+        };
+
+        assert_eq!(split(4), (vec![4], 0));
+        assert_eq!(split(3), (vec![1, 3], 0));
+        assert_eq!(split(2), (vec![1], 3));
+    }
+
+    #[test]
+    fn hand_back_messages_of_failed_transactions() {
+        let submissions = vec![
+            vec![update("07-tendermint-0", 10), msg(1)],
+            vec![update("07-tendermint-0", 10), msg(2)],
+            vec![msg(3)],
+            vec![msg(4)],
+        ];
+
+        let (txs, oversized) = split_messages(merge_messages(submissions.iter()), 3, 1_000_000);
+        assert_eq!(txs.len(), 2);
+
+        // The tx carrying the shared update is rejected by the chain, the other one fails
+        let replies = batch_replies(
+            submissions.len(),
+            txs.into_iter()
+                .zip(vec![Ok(check_tx(1)), Err(Error::empty_response_value())])
+                .collect(),
+            oversized,
+        );
+
+        let unsent: Vec<_> = replies.iter().map(|reply| reply.unsent.clone()).collect();
+        assert_eq!(
+            unsent,
+            vec![
+                vec![update("07-tendermint-0", 10), msg(1)],
+                vec![update("07-tendermint-0", 10), msg(2)],
+                vec![msg(3)],
+                vec![msg(4)],
+            ]
+        );
+        assert!(replies.iter().all(|reply| reply.responses.is_empty()));
+
+        // Only the messages of the failed tx are handed back, along with their client update.
+        // The error events of the messages dropped from the accepted tx are reported.
+        let (txs, oversized) = split_messages(merge_messages(submissions.iter()), 3, 1_000_000);
+        let accepted = CheckTxResponses {
+            dropped_events: vec![IbcEvent::ChainError("dropped".to_string())],
+            ..check_tx(0)
+        };
+        let replies = batch_replies(
+            submissions.len(),
+            txs.into_iter()
+                .zip(vec![Err(Error::empty_response_value()), Ok(accepted)])
+                .collect(),
+            oversized,
+        );
+
+        let shape: Vec<_> = replies
+            .iter()
+            .map(|reply| {
+                (
+                    reply.responses.len(),
+                    reply.dropped_events.len(),
+                    reply.unsent.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            shape,
+            vec![
+                (0, 0, vec![update("07-tendermint-0", 10), msg(1)]),
+                (0, 0, vec![update("07-tendermint-0", 10), msg(2)]),
+                (1, 1, vec![]),
+                (1, 1, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn resubmit_messages_of_failed_transactions_only() {
+        let mut config = get_basic_chain_config("chain-a");
+        config.max_msg_num = MaxMsgNum(1);
+
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let chain = ChainRuntime::<MockChain>::spawn::<ProdChainHandle>(config, rt).unwrap();
+        let batcher = Batcher::spawn(chain.clone(), Duration::from_millis(10));
+
+        // Each message is carried by a tx of its own, and the tx of the invalid one fails
+        let reply = batcher
+            .submit(vec![create_client(5), msg(1)], None)
+            .unwrap();
+        assert_eq!(reply.responses.len(), 1);
+        assert_eq!(reply.unsent, vec![msg(1)]);
+
+        // Only the message of the failed tx is submitted again, on its own, and fails again,
+        // while the response of the accepted tx is kept along with an error event
+        let reply =
+            AsyncSender::submit_batched(&batcher, &chain, vec![create_client(6), msg(2)], None)
+                .unwrap();
+        assert_eq!(reply.responses.len(), 1);
+        assert!(matches!(reply.events.as_slice(), [IbcEvent::ChainError(_)]));
+
+        let clients = chain
+            .query_clients(QueryClientStatesRequest { pagination: None })
+            .unwrap();
+        assert_eq!(clients.len(), 2);
+    }
+}
@@ -1,5 +1,6 @@
 use flex_error::define_error;
 use ibc::core::ics02_client::error::Error as Ics02Error;
+use ibc::core::ics04_channel::packet::Sequence;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId};
 use ibc::events::IbcEvent;
 use ibc::Height;
@@ -8,6 +9,7 @@ use crate::channel::ChannelError;
 use crate::connection::ConnectionError;
 use crate::error::Error;
 use crate::foreign_client::ForeignClientError;
+use crate::store::Error as StoreError;
 use crate::supervisor::Error as SupervisorError;
 use crate::transfer::PacketError;
 
@@ -21,6 +23,10 @@ define_error! {
             [ SupervisorError ]
             |_| { "error originating from the supervisor" },
 
+        Store
+            [ StoreError ]
+            |_| { "error accessing the relayer store" },
+
         Initialization
             [ ChannelError ]
             |_| { "link initialization failed during channel counterparty verification" },
@@ -132,6 +138,16 @@ define_error! {
                     e.channel_id)
             },
 
+        PacketCommitmentMismatch
+            {
+                sequence: Sequence,
+                chain_id: ChainId,
+            }
+            |e| {
+                format!("packet commitment for sequence {} on chain {} does not match the packet data",
+                    e.sequence, e.chain_id)
+            },
+
         NoConnectionHop
             {
                 channel_id: ChannelId,
@@ -142,5 +158,15 @@ define_error! {
                     e.channel_id, e.chain_id)
             },
 
+        InvalidHeightRange
+            {
+                from_height: Height,
+                to_height: Height,
+            }
+            |e| {
+                format!("invalid height range: start height {} is greater than end height {}",
+                    e.from_height, e.to_height)
+            },
+
     }
 }
@@ -1,18 +1,22 @@
 use core::fmt;
 use core::iter;
-use std::time::Instant;
+use core::time::Duration;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use prost_types::Any;
+use serde_derive::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use ibc::events::IbcEvent;
 use ibc::Height;
 
 use crate::chain::handle::ChainHandle;
+use crate::config::FeePolicy;
 use crate::link::error::LinkError;
 use crate::link::RelayPath;
+use crate::store::Error as StoreError;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum OperationalDataTarget {
     Source,
     Destination,
@@ -50,15 +54,23 @@ pub struct OperationalData {
     /// Stores the time when the clients on the target chain has been updated, i.e., when this data
     /// was scheduled. Necessary for packet delays.
     pub scheduled_time: Instant,
+    /// The fee policy the transactions carrying this data are paid with on the target chain,
+    /// or `None` if they are paid according to the target chain's configuration.
+    pub fee_policy: Option<FeePolicy>,
 }
 
 impl OperationalData {
-    pub fn new(proofs_height: Height, target: OperationalDataTarget) -> Self {
+    pub fn new(
+        proofs_height: Height,
+        target: OperationalDataTarget,
+        fee_policy: Option<FeePolicy>,
+    ) -> Self {
         OperationalData {
             proofs_height,
             batch: vec![],
             target,
             scheduled_time: Instant::now(),
+            fee_policy,
         }
     }
 
@@ -130,3 +142,137 @@ impl fmt::Display for OperationalData {
         )
     }
 }
+
+/// The record of an [`OperationalData`] in the relayer store.
+#[derive(Serialize, Deserialize)]
+pub struct OperationalDataRecord {
+    proofs_height: Height,
+    target: OperationalDataTarget,
+    batch: Vec<TransitMessageRecord>,
+    /// Scheduling time, in seconds since the Unix epoch
+    scheduled_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TransitMessageRecord {
+    event: IbcEvent,
+    type_url: String,
+    /// Hex-encoded message
+    value: String,
+}
+
+impl OperationalDataRecord {
+    pub fn new(od: &OperationalData) -> Self {
+        Self {
+            proofs_height: od.proofs_height,
+            target: od.target,
+            batch: od
+                .batch
+                .iter()
+                .map(|tm| TransitMessageRecord {
+                    event: tm.event.clone(),
+                    type_url: tm.msg.type_url.clone(),
+                    value: hex::encode(&tm.msg.value),
+                })
+                .collect(),
+            scheduled_at: epoch_secs(od.scheduled_time),
+        }
+    }
+
+    pub fn into_operational_data(
+        self,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<OperationalData, StoreError> {
+        let batch = self
+            .batch
+            .into_iter()
+            .map(|tm| {
+                let value = hex::decode(&tm.value).map_err(|e| {
+                    StoreError::invalid_record(format!("invalid message encoding: {}", e))
+                })?;
+
+                Ok(TransitMessage {
+                    event: tm.event,
+                    msg: Any {
+                        type_url: tm.type_url,
+                        value,
+                    },
+                })
+            })
+            .collect::<Result<_, StoreError>>()?;
+
+        let mut od = OperationalData::new(self.proofs_height, self.target, fee_policy);
+        od.batch = batch;
+        // Carry the time elapsed since the scheduling over the restart,
+        // so that the packet delays are still accounted for.
+        od.scheduled_time = instant_at(self.scheduled_at);
+
+        Ok(od)
+    }
+}
+
+/// The number of seconds since the Unix epoch at the given instant.
+pub(crate) fn epoch_secs(instant: Instant) -> u64 {
+    SystemTime::now()
+        .checked_sub(instant.elapsed())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// The instant at the given number of seconds since the Unix epoch,
+/// or now if it is in the future.
+pub(crate) fn instant_at(epoch_secs: u64) -> Instant {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .saturating_sub(Duration::from_secs(epoch_secs));
+
+    Instant::now()
+        .checked_sub(elapsed)
+        .unwrap_or_else(Instant::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc::core::ics02_client::events::NewBlock;
+
+    use crate::store::RelayerStore;
+
+    #[test]
+    fn operational_data_record_roundtrip() {
+        let mut od =
+            OperationalData::new(Height::new(0, 10), OperationalDataTarget::Destination, None);
+        od.batch.push(TransitMessage {
+            event: IbcEvent::NewBlock(NewBlock::new(Height::new(0, 10))),
+            msg: Any {
+                type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+                value: vec![1, 2, 3],
+            },
+        });
+        od.scheduled_time = Instant::now() - Duration::from_secs(30);
+
+        let store = RelayerStore::temporary()
+            .unwrap()
+            .scheduled_ops("ibc-0:transfer/channel-0 -> ibc-1")
+            .unwrap();
+        store
+            .replace_all(&[OperationalDataRecord::new(&od)])
+            .unwrap();
+
+        let (_, record) = store.all::<OperationalDataRecord>().unwrap().pop().unwrap();
+        let restored = record.into_operational_data(None).unwrap();
+
+        assert_eq!(restored.proofs_height, od.proofs_height);
+        assert!(restored.target == od.target);
+        assert_eq!(restored.batch.len(), 1);
+        assert_eq!(restored.batch[0].event, od.batch[0].event);
+        assert_eq!(restored.batch[0].msg, od.batch[0].msg);
+
+        // The time elapsed since the scheduling is carried over
+        let elapsed = restored.scheduled_time.elapsed();
+        assert!(elapsed >= Duration::from_secs(29) && elapsed < Duration::from_secs(60));
+    }
+}
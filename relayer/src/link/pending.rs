@@ -1,20 +1,30 @@
 use core::fmt;
 use core::iter::Iterator;
+use core::mem;
 use core::time::Duration;
 use std::time::Instant;
 
-use tracing::{debug, error, trace};
+use serde_derive::{Deserialize, Serialize};
+use tendermint::abci::transaction;
+use tracing::{debug, error, info, trace, warn};
 
+use ibc::core::ics04_channel::packet::Packet;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::events::IbcEvent;
 use ibc::query::{QueryTxHash, QueryTxRequest};
 
+use crate::config::FeePolicy;
 use crate::error::Error as RelayerError;
 use crate::link::error::LinkError;
+use crate::store::{Error as StoreError, RecordStore};
 use crate::util::queue::Queue;
 use crate::{
     chain::handle::ChainHandle,
-    link::{operational_data::OperationalData, relay_sender::AsyncReply, RelaySummary, TxHashes},
+    link::{
+        operational_data::{epoch_secs, instant_at, OperationalData, OperationalDataRecord},
+        relay_sender::AsyncReply,
+        RelaySummary, TxHashes,
+    },
 };
 
 pub const TIMEOUT: Duration = Duration::from_secs(100);
@@ -32,6 +42,101 @@ pub struct PendingData {
     pub tx_hashes: TxHashes,
     pub submit_time: Instant,
     pub error_events: Vec<IbcEvent>,
+    /// The id of the record of this data in the relayer store, if persisted
+    pub store_id: Option<u64>,
+}
+
+impl PendingData {
+    /// Returns true if the given event does not pertain to a packet,
+    /// or pertains to the packet of one of the messages of this data.
+    fn carries_packet_of(&self, event: &IbcEvent) -> bool {
+        let packet = match event.packet() {
+            Some(packet) => packet,
+            None => return true,
+        };
+
+        self.original_od
+            .batch
+            .iter()
+            .any(|tm| tm.event.packet().map_or(false, |p| same_packet(p, packet)))
+    }
+
+    /// Error events for the messages of this data whose packet has no event among the
+    /// given events of its committed transactions, e.g. because they were dropped from
+    /// their transaction after failing its simulation. No such event is reported when
+    /// the transactions themselves report an error.
+    fn missing_packet_events(&self, chain_id: &ChainId, events: &[IbcEvent]) -> Vec<IbcEvent> {
+        if events
+            .iter()
+            .any(|event| matches!(event, IbcEvent::ChainError(_)))
+        {
+            return vec![];
+        }
+
+        self.original_od
+            .batch
+            .iter()
+            .filter_map(|tm| tm.event.packet())
+            .filter(|packet| {
+                !events
+                    .iter()
+                    .filter_map(|event| event.packet())
+                    .any(|p| same_packet(p, packet))
+            })
+            .map(|packet| {
+                IbcEvent::ChainError(format!(
+                    "the transactions on chain {} carry no message for packet {}/{}/{}, which was dropped from its transaction",
+                    chain_id,
+                    packet.source_port,
+                    packet.source_channel,
+                    packet.sequence
+                ))
+            })
+            .collect()
+    }
+}
+
+fn same_packet(a: &Packet, b: &Packet) -> bool {
+    a.sequence == b.sequence
+        && a.source_port == b.source_port
+        && a.source_channel == b.source_channel
+}
+
+/// The record of a [`PendingData`] in the relayer store.
+#[derive(Serialize, Deserialize)]
+struct PendingDataRecord {
+    data: OperationalDataRecord,
+    tx_hashes: Vec<transaction::Hash>,
+    /// Submission time, in seconds since the Unix epoch
+    submitted_at: u64,
+    error_events: Vec<IbcEvent>,
+}
+
+impl PendingDataRecord {
+    fn new(pending: &PendingData) -> Self {
+        Self {
+            data: OperationalDataRecord::new(&pending.original_od),
+            tx_hashes: pending.tx_hashes.0.clone(),
+            submitted_at: epoch_secs(pending.submit_time),
+            error_events: pending.error_events.clone(),
+        }
+    }
+
+    fn into_pending_data(
+        self,
+        store_id: u64,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<PendingData, StoreError> {
+        Ok(PendingData {
+            original_od: self.data.into_operational_data(fee_policy)?,
+            tx_hashes: TxHashes(self.tx_hashes),
+            // Carry the time elapsed since the submission over the restart,
+            // so that the data still times out after the expected duration.
+            submit_time: instant_at(self.submitted_at),
+            error_events: self.error_events,
+            store_id: Some(store_id),
+        })
+    }
 }
 
 /// The mediator stores all pending data
@@ -42,6 +147,7 @@ pub struct PendingTxs<Chain> {
     pub port_id: PortId,
     pub counterparty_chain_id: ChainId,
     pub pending_queue: Queue<PendingData>,
+    pub store: Option<RecordStore>,
 }
 
 impl<Chain> PendingTxs<Chain> {
@@ -57,6 +163,7 @@ impl<Chain> PendingTxs<Chain> {
             port_id,
             counterparty_chain_id,
             pending_queue: Queue::new(),
+            store: None,
         }
     }
 }
@@ -66,6 +173,69 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.chain.id()
     }
 
+    /// Persist the pending transactions in the given store from now on, and
+    /// resume tracking the confirmation of those already in the store.
+    pub fn attach_store(
+        &mut self,
+        store: RecordStore,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<(), StoreError> {
+        let records = store.all::<PendingDataRecord>()?;
+
+        if !records.is_empty() {
+            info!(
+                "[{}] resuming confirmation of {} pending transaction(s) from the store",
+                self,
+                records.len()
+            );
+        }
+
+        for (id, record) in records {
+            let pending = record.into_pending_data(id, fee_policy.clone())?;
+            self.pending_queue.push_back(pending);
+        }
+
+        self.store = Some(store);
+
+        Ok(())
+    }
+
+    /// Returns true if the packet of the given event is carried
+    /// by a transaction pending confirmation.
+    pub fn is_pending(&self, event: &IbcEvent) -> bool {
+        let packet = match event.packet() {
+            Some(packet) => packet,
+            None => return false,
+        };
+
+        self.pending_queue.clone_vec().iter().any(|pending| {
+            pending.original_od.batch.iter().any(|tm| {
+                mem::discriminant(&tm.event) == mem::discriminant(event)
+                    && tm.event.packet().map(|p| p.sequence) == Some(packet.sequence)
+            })
+        })
+    }
+
+    fn persist(&self, pending: &mut PendingData) {
+        if let Some(store) = &self.store {
+            match store.insert(&PendingDataRecord::new(pending)) {
+                Ok(id) => pending.store_id = Some(id),
+                Err(e) => warn!("[{}] failed to persist pending transaction: {}", self, e),
+            }
+        }
+    }
+
+    fn unpersist(&self, pending: &PendingData) {
+        if let (Some(store), Some(id)) = (&self.store, pending.store_id) {
+            if let Err(e) = store.remove(id) {
+                warn!(
+                    "[{}] failed to remove pending transaction from the store: {}",
+                    self, e
+                );
+            }
+        }
+    }
+
     // Insert new pending transaction to the back of the queue.
     pub fn insert_new_pending_tx(&self, r: AsyncReply, od: OperationalData) {
         let mut tx_hashes = Vec::new();
@@ -99,12 +269,19 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             }
         }
 
-        let u = PendingData {
+        // The events of the transactions simulated in dry-run mode, and the error events of
+        // the messages dropped from their transactions, are known right away, and reported
+        // along with the error events once this data is processed.
+        error_events.extend(r.events);
+
+        let mut u = PendingData {
             original_od: od,
             tx_hashes: TxHashes(tx_hashes),
             submit_time: Instant::now(),
             error_events,
+            store_id: None,
         };
+        self.persist(&mut u);
         self.pending_queue.push_back(u);
     }
 
@@ -137,6 +314,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             let submit_time = &pending.submit_time;
 
             if tx_hashes.0.is_empty() {
+                self.unpersist(&pending);
                 return Ok(Some(RelaySummary::from_events(pending.error_events)));
             }
 
@@ -166,6 +344,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
 
                         match resubmit_res {
                             Ok(reply) => {
+                                self.unpersist(&pending);
                                 self.insert_new_pending_tx(reply, pending.original_od);
                                 Ok(None)
                             }
@@ -194,9 +373,21 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         tx_hashes
                     );
 
+                    self.unpersist(&pending);
+
+                    // Transactions submitted through a batcher also carry the messages of
+                    // other paths, whose events are left to the pending data of those paths.
+                    let events: Vec<_> = events
+                        .into_iter()
+                        .filter(|event| pending.carries_packet_of(event))
+                        .collect();
+
+                    let missing_events = pending.missing_packet_events(&self.chain_id(), &events);
+
                     // Convert the events to RelaySummary and return them.
                     let mut summary = RelaySummary::from_events(events);
                     summary.extend(RelaySummary::from_events(pending.error_events));
+                    summary.extend(RelaySummary::from_events(missing_events));
 
                     Ok(Some(summary))
                 }
@@ -1,22 +1,27 @@
 use alloc::collections::BTreeMap as HashMap;
 use alloc::collections::VecDeque;
+use core::cmp;
 use core::fmt;
+use core::ops::RangeInclusive;
 use std::sync::{Arc, RwLock};
 use std::thread;
 use std::time::Instant;
 
 use itertools::Itertools;
 use prost_types::Any;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use ibc::{
     core::{
         ics04_channel::{
             channel::{ChannelEnd, Order, QueryPacketEventDataRequest, State as ChannelState},
+            commitment::compute_packet_commitment,
             events::{SendPacket, WriteAcknowledgement},
             msgs::{
-                acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
-                recv_packet::MsgRecvPacket, timeout::MsgTimeout,
+                acknowledgement::{AcknowledgementResponse, MsgAcknowledgement},
+                chan_close_confirm::MsgChannelCloseConfirm,
+                recv_packet::MsgRecvPacket,
+                timeout::MsgTimeout,
                 timeout_on_close::MsgTimeoutOnClose,
             },
             packet::{Packet, PacketMsgType, Sequence},
@@ -24,7 +29,7 @@ use ibc::{
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
     events::{IbcEvent, PrettyEvents, WithBlockDataType},
-    query::{QueryBlockRequest, QueryTxRequest},
+    query::{QueryBlockRangeRequest, QueryBlockRequest, QueryTxRequest},
     signer::Signer,
     timestamp::ZERO_DURATION,
     tx_msg::Msg,
@@ -41,16 +46,24 @@ use crate::chain::handle::ChainHandle;
 use crate::chain::StatusResponse;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
+use crate::config::{FeePolicy, TransferFilter};
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
+use crate::link::batcher::{Batcher, Batchers};
 use crate::link::error::{self, LinkError};
-use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+use crate::link::operational_data::{
+    OperationalData, OperationalDataRecord, OperationalDataTarget, TransitMessage,
+};
 use crate::link::pending::PendingTxs;
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
 use crate::link::{pending, relay_sender};
-use crate::util::lock::LockExt;
+use crate::store::{RecordStore, RelayerStore};
+use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
+use crate::util::priority::{PriorityGate, PriorityGates};
 use crate::util::queue::Queue;
+use crate::util::rate_limit::RateLimiter;
 
 const MAX_RETRIES: usize = 5;
 
@@ -78,6 +91,9 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     src_operational_data: Queue<OperationalData>,
     dst_operational_data: Queue<OperationalData>,
 
+    // Persists the operational data scheduled on this path, if a store is attached.
+    schedule_store: Option<RecordStore>,
+
     // Toggle for the transaction confirmation mechanism.
     confirm_txes: bool,
 
@@ -86,6 +102,34 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // The fee policies paying for the transactions submitted to the
+    // source and destination chain, respectively, if any applies to this path.
+    src_fee_policy: Option<FeePolicy>,
+    dst_fee_policy: Option<FeePolicy>,
+
+    // Caps the number of messages relayed on this path within a time window,
+    // if a rate limit applies to the source channel.
+    rate_limiter: Option<RwArc<RateLimiter>>,
+
+    // The priority of the submissions of this path over those of the other paths
+    // relaying to the same chain, as configured by the rate limit of the source channel.
+    priority: u32,
+
+    // Hold back the submissions to the source and destination chain, respectively,
+    // while those of a higher priority are pending, if attached.
+    src_priority_gate: Option<PriorityGate>,
+    dst_priority_gate: Option<PriorityGate>,
+
+    // Restricts the transfers relayed on this path based on the content of their
+    // packets, if a transfer filter applies to the source channel.
+    transfer_filter: Option<TransferFilter>,
+
+    // Merge the messages submitted asynchronously to the source and destination
+    // chain, respectively, with those of the other paths relaying to that chain,
+    // if batching across paths is enabled.
+    src_batcher: Option<Batcher>,
+    dst_batcher: Option<Batcher>,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -112,6 +156,42 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let src_port_id = channel.src_port_id().clone();
         let dst_port_id = channel.dst_port_id().clone();
 
+        let src_fee_policy = src_chain
+            .config()
+            .map_err(LinkError::relayer)?
+            .fee_policy(&src_port_id, &src_channel_id, &dst_chain_id)
+            .cloned();
+
+        let dst_fee_policy = dst_chain
+            .config()
+            .map_err(LinkError::relayer)?
+            .fee_policy(&dst_port_id, &dst_channel_id, &src_chain_id)
+            .cloned();
+
+        let src_config = src_chain.config().map_err(LinkError::relayer)?;
+
+        // A packet which is not received blocks all the packets sent after it
+        // on an ordered channel, until the channel is closed by its timeout
+        let transfer_filter = match src_config.transfer_filter(&src_port_id, &src_channel_id) {
+            Some(_) if channel.ordering == Order::Ordered => {
+                warn!(
+                    "ignoring the transfer filter configured for ordered channel {}/{} on chain {}",
+                    src_port_id, src_channel_id, src_chain_id
+                );
+                None
+            }
+            filter => filter.cloned(),
+        };
+
+        let rate_limit = src_config.packet_rate_limit(&src_port_id, &src_channel_id);
+
+        let rate_limiter = rate_limit.and_then(|limit| {
+            let max_msgs = limit.max_msgs?;
+            Some(RwArc::new_lock(RateLimiter::new(max_msgs, limit.window)))
+        });
+
+        let priority = rate_limit.map_or(0, |limit| limit.priority);
+
         Ok(Self {
             channel,
 
@@ -123,10 +203,23 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             clear_packets: Arc::new(RwLock::new(true)),
             src_operational_data: Queue::new(),
             dst_operational_data: Queue::new(),
+            schedule_store: None,
 
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            src_fee_policy,
+            dst_fee_policy,
+
+            rate_limiter,
+            priority,
+            src_priority_gate: None,
+            dst_priority_gate: None,
+            transfer_filter,
+
+            src_batcher: None,
+            dst_batcher: None,
         })
     }
 
@@ -138,6 +231,95 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         self.channel.dst_chain()
     }
 
+    /// The fee policy paying for the transactions submitted to the given target chain, if any.
+    pub fn fee_policy(&self, target: OperationalDataTarget) -> Option<&FeePolicy> {
+        match target {
+            OperationalDataTarget::Source => self.src_fee_policy.as_ref(),
+            OperationalDataTarget::Destination => self.dst_fee_policy.as_ref(),
+        }
+    }
+
+    /// Persist the operational data scheduled on this path in the given store, as well as
+    /// the transactions pending confirmation if the confirmation mechanism is enabled, and
+    /// resume relaying the data and tracking the transactions persisted before a restart.
+    pub fn attach_store(&mut self, store: &RelayerStore) -> Result<(), LinkError> {
+        let schedule_store = store
+            .scheduled_ops(&self.to_string())
+            .map_err(LinkError::store)?;
+
+        let records = schedule_store
+            .all::<OperationalDataRecord>()
+            .map_err(LinkError::store)?;
+
+        if !records.is_empty() {
+            info!(
+                "[{}] resuming {} scheduled op. data from the store",
+                self,
+                records.len()
+            );
+        }
+
+        for (_, record) in records {
+            let mut od = record
+                .into_operational_data(None)
+                .map_err(LinkError::store)?;
+            od.fee_policy = self.fee_policy(od.target).cloned();
+
+            match od.target {
+                OperationalDataTarget::Source => self.src_operational_data.push_back(od),
+                OperationalDataTarget::Destination => self.dst_operational_data.push_back(od),
+            }
+        }
+
+        self.schedule_store = Some(schedule_store);
+
+        if !self.confirm_txes {
+            return Ok(());
+        }
+
+        let src_store = store
+            .pending_txs(&self.pending_txs_src.to_string())
+            .map_err(LinkError::store)?;
+        let src_fee_policy = self.src_fee_policy.clone();
+        self.pending_txs_src
+            .attach_store(src_store, src_fee_policy)
+            .map_err(LinkError::store)?;
+
+        let dst_store = store
+            .pending_txs(&self.pending_txs_dst.to_string())
+            .map_err(LinkError::store)?;
+        let dst_fee_policy = self.dst_fee_policy.clone();
+        self.pending_txs_dst
+            .attach_store(dst_store, dst_fee_policy)
+            .map_err(LinkError::store)?;
+
+        Ok(())
+    }
+
+    /// Submit the messages sent asynchronously on this path through the batchers of the
+    /// source and destination chain, which merge them with those of the other paths.
+    pub fn attach_batchers(&mut self, batchers: &Batchers) {
+        self.src_batcher = Some(batchers.get_or_spawn(self.src_chain()));
+        self.dst_batcher = Some(batchers.get_or_spawn(self.dst_chain()));
+    }
+
+    /// Submit the messages of this path to the source and destination chain only once
+    /// the submissions of a higher priority of the other paths relaying to them are done.
+    pub fn attach_priority_gates(&mut self, gates: &PriorityGates) {
+        self.src_priority_gate = Some(gates.get_or_create(&self.src_chain().id()));
+        self.dst_priority_gate = Some(gates.get_or_create(&self.dst_chain().id()));
+    }
+
+    /// Creates empty operational data targeting the given chain, paid with the fee policy
+    /// which applies to this path on that chain.
+    fn new_operational_data(
+        &self,
+        proofs_height: Height,
+        target: OperationalDataTarget,
+    ) -> OperationalData {
+        OperationalData::new(proofs_height, target, self.fee_policy(target).cloned())
+    }
+
     pub fn src_client_id(&self) -> &ClientId {
         self.channel.src_client_id()
     }
@@ -287,11 +469,39 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         result
     }
 
+    /// Returns true if the transfer filter of this path, if any, allows the packet to be received
+    /// on the destination chain. The packets which do not carry a fungible token transfer are
+    /// allowed, while the timeouts and acknowledgements of all packets are always relayed.
+    fn is_transfer_allowed(&self, packet: &Packet) -> bool {
+        let filter = match &self.transfer_filter {
+            Some(filter) => filter,
+            None => return true,
+        };
+
+        match filter.allows(&packet.data) {
+            Ok(true) => true,
+            Ok(false) => {
+                debug!(
+                    "[{}] skipping packet {} whose transfer is not allowed by the transfer filter",
+                    self, packet.sequence
+                );
+                false
+            }
+            Err(e) => {
+                debug!(
+                    "[{}] not filtering packet {} which does not carry a fungible token transfer: {}",
+                    self, packet.sequence, e
+                );
+                true
+            }
+        }
+    }
+
     fn relay_pending_packets(&self, height: Option<Height>) -> Result<(), LinkError> {
         for i in 1..=MAX_RETRIES {
             let cleared = self
-                .build_recv_packet_and_timeout_msgs(height)
-                .and_then(|()| self.build_packet_ack_msgs(height));
+                .build_recv_packet_and_timeout_msgs(height, &[])
+                .and_then(|()| self.build_packet_ack_msgs(height, &[]));
 
             match cleared {
                 Ok(()) => return Ok(()),
@@ -345,7 +555,26 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 
     /// Produces and schedules operational data for this relaying path based on the input events.
+    /// Events whose packets are carried by a transaction pending confirmation are skipped,
+    /// so that packet clearing does not submit them again.
     fn events_to_operational_data(&self, events: Vec<IbcEvent>) -> Result<(), LinkError> {
+        let events = events
+            .into_iter()
+            .filter(|event| {
+                let pending = self.pending_txs_src.is_pending(event)
+                    || self.pending_txs_dst.is_pending(event);
+
+                if pending {
+                    debug!(
+                        "[{}] skipping event whose packet is pending confirmation: {}",
+                        self, event
+                    );
+                }
+
+                !pending
+            })
+            .collect();
+
         // Obtain the operational data for the source chain (mostly timeout packets) and for the
         // destination chain (e.g., receive packet messages).
         let (src_opt, dst_opt) = self.generate_operational_data(events)?;
@@ -390,9 +619,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
         let dst_latest_height = dst_latest_info.height;
         // Operational data targeting the source chain (e.g., Timeout packets)
-        let mut src_od = OperationalData::new(dst_latest_height, OperationalDataTarget::Source);
+        let mut src_od =
+            self.new_operational_data(dst_latest_height, OperationalDataTarget::Source);
         // Operational data targeting the destination chain (e.g., SendPacket messages)
-        let mut dst_od = OperationalData::new(src_height, OperationalDataTarget::Destination);
+        let mut dst_od = self.new_operational_data(src_height, OperationalDataTarget::Destination);
 
         for event in input {
             debug!("[{}] {} => {}", self, self.src_chain().id(), event);
@@ -441,6 +671,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                         debug!("[{}] {} already handled", self, write_ack_ev);
                         (None, None)
                     } else {
+                        if let Some(AcknowledgementResponse::Error(e)) = write_ack_ev.ack.response()
+                        {
+                            warn!(
+                                "[{}] packet {} failed on {} with application error: {}",
+                                self,
+                                write_ack_ev.packet.sequence,
+                                self.src_chain().id(),
+                                e
+                            );
+                        }
+
                         (self.build_ack_from_recv_event(write_ack_ev)?, None)
                     }
                 }
@@ -648,9 +889,25 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let msgs = odata.assemble_msgs(self)?;
 
+        let priority_gate = match odata.target {
+            OperationalDataTarget::Source => self.src_priority_gate.as_ref(),
+            OperationalDataTarget::Destination => self.dst_priority_gate.as_ref(),
+        };
+        let _priority_guard = priority_gate.map(|gate| gate.enter(self.priority));
+
         match odata.target {
-            OperationalDataTarget::Source => S::submit(self.src_chain(), msgs),
-            OperationalDataTarget::Destination => S::submit(self.dst_chain(), msgs),
+            OperationalDataTarget::Source => match &self.src_batcher {
+                Some(batcher) => {
+                    S::submit_batched(batcher, self.src_chain(), msgs, odata.fee_policy)
+                }
+                None => S::submit(self.src_chain(), msgs, odata.fee_policy),
+            },
+            OperationalDataTarget::Destination => match &self.dst_batcher {
+                Some(batcher) => {
+                    S::submit_batched(batcher, self.dst_chain(), msgs, odata.fee_policy)
+                }
+                None => S::submit(self.dst_chain(), msgs, odata.fee_policy),
+            },
         }
     }
 
@@ -685,6 +942,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
     /// Checks if a packet commitment has been cleared on source.
     /// The packet commitment is cleared when either an acknowledgment or a timeout is received on source.
+    ///
+    /// If the commitment is still present, it is checked against the commitment computed
+    /// from the packet, to catch events that are inconsistent with the source chain state.
     fn send_packet_commitment_cleared_on_src(&self, packet: &Packet) -> Result<bool, LinkError> {
         let (bytes, _) = self
             .src_chain()
@@ -697,7 +957,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             )
             .map_err(LinkError::relayer)?;
 
-        Ok(bytes.is_empty())
+        if bytes.is_empty() {
+            return Ok(true);
+        }
+
+        let expected = compute_packet_commitment(
+            &packet.data,
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        );
+
+        if bytes != expected.as_bytes() {
+            return Err(LinkError::packet_commitment_mismatch(
+                packet.sequence,
+                self.src_chain().id(),
+            ));
+        }
+
+        Ok(false)
     }
 
     /// Checks if a send packet event has already been handled (e.g. by another relayer).
@@ -818,11 +1095,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         )))
     }
 
-    /// Returns relevant packet events for building RecvPacket and timeout messages.
+    /// Returns relevant packet events for building RecvPacket and timeout messages,
+    /// restricted to the packets whose sequence is within `seq_ranges` if any are given.
     /// Additionally returns the height (on source chain) corresponding to these events.
     fn target_height_and_send_packet_events(
         &self,
         opt_query_height: Option<Height>,
+        seq_ranges: &[RangeInclusive<Sequence>],
     ) -> Result<(Vec<IbcEvent>, Height), LinkError> {
         let mut events_result = vec![];
 
@@ -841,7 +1120,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
-        let sequences: Vec<Sequence> = sequences.into_iter().map(From::from).collect();
+        let sequences: Vec<Sequence> = sequences
+            .into_iter()
+            .map(From::from)
+            .filter(|seq| in_seq_ranges(seq, seq_ranges))
+            .collect();
         if sequences.is_empty() {
             return Ok((events_result, query_height));
         }
@@ -935,11 +1218,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok((events_result, query_height))
     }
 
-    /// Returns relevant packet events for building ack messages.
+    /// Returns relevant packet events for building ack messages,
+    /// restricted to the packets whose sequence is within `seq_ranges` if any are given.
     /// Additionally returns the height (on source chain) corresponding to these events.
     fn target_height_and_write_ack_events(
         &self,
         opt_query_height: Option<Height>,
+        seq_ranges: &[RangeInclusive<Sequence>],
     ) -> Result<(Vec<IbcEvent>, Height), LinkError> {
         let mut events_result = vec![];
 
@@ -959,7 +1244,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
-        let sequences: Vec<Sequence> = unreceived_acks_by_dst.into_iter().map(From::from).collect();
+        let sequences: Vec<Sequence> = unreceived_acks_by_dst
+            .into_iter()
+            .map(From::from)
+            .filter(|seq| in_seq_ranges(seq, seq_ranges))
+            .collect();
         if sequences.is_empty() {
             return Ok((events_result, query_height));
         }
@@ -1025,13 +1314,20 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// The `opt_query_height` parameter allows to optionally use a specific height on the source
     /// chain where to query for packet data. If `None`, the latest available height on the source
     /// chain is used.
+    /// Only the packets whose sequence is within one of the `seq_ranges` are relayed,
+    /// or all of them if no range is given.
     pub fn build_recv_packet_and_timeout_msgs(
         &self,
         opt_query_height: Option<Height>,
+        seq_ranges: &[RangeInclusive<Sequence>],
     ) -> Result<(), LinkError> {
         // Get the events for the send packets on source chain that have not been received on
         // destination chain (i.e. ack was not seen on source chain).
-        let (mut events, height) = self.target_height_and_send_packet_events(opt_query_height)?;
+        let (events, height) =
+            self.target_height_and_send_packet_events(opt_query_height, seq_ranges)?;
+
+        // Skip the packets whose content is not allowed
+        let mut events = self.filter_relaying_events(events);
 
         // Skip: no relevant events found.
         if events.is_empty() {
@@ -1051,10 +1347,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// The `opt_query_height` parameter allows to optionally use a specific height on the source
     /// chain where to query for packet data. If `None`, the latest available height on the source
     /// chain is used.
-    pub fn build_packet_ack_msgs(&self, opt_query_height: Option<Height>) -> Result<(), LinkError> {
+    /// Only the acknowledgments of the packets whose sequence is within one of the `seq_ranges`
+    /// are relayed, or all of them if no range is given.
+    pub fn build_packet_ack_msgs(
+        &self,
+        opt_query_height: Option<Height>,
+        seq_ranges: &[RangeInclusive<Sequence>],
+    ) -> Result<(), LinkError> {
         // Get the sequences of packets that have been acknowledged on destination chain but still
         // have commitments on source chain (i.e. ack was not seen on source chain)
-        let (mut events, height) = self.target_height_and_write_ack_events(opt_query_height)?;
+        let (mut events, height) =
+            self.target_height_and_write_ack_events(opt_query_height, seq_ranges)?;
 
         // Skip: no relevant events found.
         if events.is_empty() {
@@ -1069,7 +1372,143 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(())
     }
 
+    /// Schedules the relaying of the packets sent, and of the acknowledgments written, on the
+    /// source chain from `from_height` to `to_height` (inclusive) which were not relayed yet.
+    /// The events are recovered by scanning each block of the range, such that packets can be
+    /// cleared even when the source chain node does not index transactions.
+    pub fn build_msgs_from_height_range(
+        &self,
+        from_height: Height,
+        to_height: Height,
+    ) -> Result<(), LinkError> {
+        if from_height > to_height {
+            return Err(LinkError::invalid_height_range(from_height, to_height));
+        }
+
+        let (_, unreceived_packets, query_height) = unreceived_packets_sequences(
+            self.dst_chain(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+            self.src_chain(),
+            self.src_port_id(),
+            self.src_channel_id(),
+        )
+        .map_err(LinkError::supervisor)?;
+
+        // Blocks above the height at which the source chain was queried cannot be scanned yet
+        let to_height = cmp::min(to_height, query_height);
+
+        let send_packet_events = self.packet_events_in_height_range(
+            QueryPacketEventDataRequest {
+                event_id: WithBlockDataType::SendPacket,
+                source_port_id: self.src_port_id().clone(),
+                source_channel_id: self.src_channel_id().clone(),
+                destination_port_id: self.dst_port_id().clone(),
+                destination_channel_id: self.dst_channel_id().clone(),
+                sequences: unreceived_packets.into_iter().map(From::from).collect(),
+                height: query_height,
+            },
+            from_height,
+            to_height,
+        )?;
+
+        info!(
+            "[{}] found {} unprocessed SendPacket events from height {} to {}",
+            self,
+            send_packet_events.len(),
+            from_height,
+            to_height
+        );
+
+        // Skip the packets whose content is not allowed
+        let send_packet_events = self.filter_relaying_events(send_packet_events);
+        self.events_to_operational_data(send_packet_events)?;
+
+        let (_, unreceived_acks, query_height) = unreceived_acknowledgements_sequences(
+            self.dst_chain(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+            self.src_chain(),
+            self.src_port_id(),
+            self.src_channel_id(),
+        )
+        .map_err(LinkError::supervisor)?;
+
+        let write_ack_events = self.packet_events_in_height_range(
+            QueryPacketEventDataRequest {
+                event_id: WithBlockDataType::WriteAck,
+                source_port_id: self.dst_port_id().clone(),
+                source_channel_id: self.dst_channel_id().clone(),
+                destination_port_id: self.src_port_id().clone(),
+                destination_channel_id: self.src_channel_id().clone(),
+                sequences: unreceived_acks.into_iter().map(From::from).collect(),
+                height: query_height,
+            },
+            from_height,
+            to_height,
+        )?;
+
+        info!(
+            "[{}] found {} unprocessed WriteAcknowledgement events from height {} to {}",
+            self,
+            write_ack_events.len(),
+            from_height,
+            to_height
+        );
+
+        self.events_to_operational_data(write_ack_events)
+    }
+
+    /// Scans the blocks of the source chain within the given range for the packet events
+    /// matching `query`, and sets their height to the height of the query, at which the
+    /// proofs are then built.
+    fn packet_events_in_height_range(
+        &self,
+        query: QueryPacketEventDataRequest,
+        from_height: Height,
+        to_height: Height,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        // Nothing left to relay
+        if query.sequences.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let query_height = query.height;
+
+        let (begin_block_and_tx_events, end_block_events) = self
+            .src_chain()
+            .query_blocks(QueryBlockRequest::PacketRange(QueryBlockRangeRequest {
+                packet: query,
+                from_height,
+                to_height,
+            }))
+            .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+        let mut events: Vec<IbcEvent> = begin_block_and_tx_events
+            .into_iter()
+            .chain(end_block_events)
+            .collect();
+
+        // The events of the begin and end blocks are not interleaved by height,
+        // restore the order in which the packets were sent
+        events.sort_by_key(|event| match event {
+            IbcEvent::SendPacket(send_ev) => send_ev.packet.sequence,
+            IbcEvent::WriteAcknowledgement(ack_ev) => ack_ev.packet.sequence,
+            _ => Sequence::default(),
+        });
+
+        for event in events.iter_mut() {
+            event.set_height(query_height);
+        }
+
+        Ok(events)
+    }
+
     fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Option<Any>, LinkError> {
+        if !self.is_transfer_allowed(packet) {
+            return Ok(None);
+        }
+
         let (_, proofs) = self
             .src_chain()
             .build_packet_proofs(
@@ -1219,7 +1658,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .state_matches(&ChannelState::Closed)
         {
             Ok(self.build_timeout_on_close_packet(&event.packet, dst_info.height)?)
-        } else if packet.timed_out(&dst_info.timestamp, dst_info.height) {
+        } else if packet.timed_out(dst_info.height, dst_info.timestamp) {
             Ok(self.build_timeout_packet(&event.packet, dst_info.height)?)
         } else {
             Ok(None)
@@ -1249,22 +1688,39 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     pub fn execute_schedule(&self) -> Result<(), LinkError> {
         let (src_ods, dst_ods) = self.try_fetch_scheduled_operational_data();
 
-        for od in dst_ods {
-            let reply =
-                self.relay_from_operational_data::<relay_sender::AsyncSender>(od.clone())?;
+        let mut unsubmitted = self.apply_rate_limit(dst_ods.into_iter().chain(src_ods).collect());
 
-            self.enqueue_pending_tx(reply, od);
-        }
-
-        for od in src_ods {
+        while let Some(od) = unsubmitted.pop_front() {
             let reply =
                 self.relay_from_operational_data::<relay_sender::AsyncSender>(od.clone())?;
+
             self.enqueue_pending_tx(reply, od);
+            self.persist_schedule(&unsubmitted);
         }
 
         Ok(())
     }
 
+    /// Persist the operational data scheduled on this path, along with the given data
+    /// which was fetched from the schedule but not submitted yet, if a store is attached.
+    fn persist_schedule(&self, unsubmitted: &VecDeque<OperationalData>) {
+        let store = match &self.schedule_store {
+            Some(store) => store,
+            None => return,
+        };
+
+        let records = unsubmitted
+            .iter()
+            .chain(self.src_operational_data.clone_vec().iter())
+            .chain(self.dst_operational_data.clone_vec().iter())
+            .map(OperationalDataRecord::new)
+            .collect::<Vec<_>>();
+
+        if let Err(e) = store.replace_all(&records) {
+            warn!("[{}] failed to persist the scheduled op. data: {}", self, e);
+        }
+    }
+
     pub fn process_pending_txs(&self) -> RelaySummary {
         if !self.confirm_txes {
             return RelaySummary::empty();
@@ -1328,6 +1784,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         // Intermediary data struct to help better manage the transfer from dst. operational data
         // to source operational data.
         let mut all_dst_odata = self.dst_operational_data.clone_vec();
+        let msgs_count = |ods: &VecDeque<OperationalData>| -> usize {
+            ods.iter().map(|od| od.batch.len()).sum()
+        };
+        let initial_msgs_count = msgs_count(&all_dst_odata);
 
         let mut timed_out: HashMap<usize, Vec<TransitMessage>> = HashMap::default();
 
@@ -1388,8 +1848,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         all_dst_odata.retain(|o| !o.batch.is_empty());
 
         // Replace the original operational data with the updated one
+        let refreshed = msgs_count(&all_dst_odata) != initial_msgs_count;
         self.dst_operational_data.replace(all_dst_odata);
 
+        if refreshed {
+            self.persist_schedule(&VecDeque::new());
+        }
+
         // Handle timed-out events
         if timed_out.is_empty() {
             // Nothing timed out in the meantime
@@ -1399,7 +1864,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         // Schedule new operational data targeting the source chain
         for (_, batch) in timed_out.into_iter() {
             let mut new_od =
-                OperationalData::new(dst_current_height, OperationalDataTarget::Source);
+                self.new_operational_data(dst_current_height, OperationalDataTarget::Source);
 
             new_od.batch = batch;
 
@@ -1451,9 +1916,82 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             OperationalDataTarget::Destination => self.dst_operational_data.push_back(od),
         };
 
+        self.persist_schedule(&VecDeque::new());
+
         Ok(())
     }
 
+    /// Keeps the messages of the given operational data within what remains of the rate limit
+    /// of this path for the current window, and returns those exceeding it to the front of the
+    /// schedule, to be relayed in a subsequent window.
+    fn apply_rate_limit(&self, ods: VecDeque<OperationalData>) -> VecDeque<OperationalData> {
+        let rate_limiter = match &self.rate_limiter {
+            Some(rate_limiter) => rate_limiter,
+            None => return ods,
+        };
+
+        let mut rate_limiter = rate_limiter.acquire_write();
+
+        let mut granted_ods = VecDeque::new();
+        let mut throttled_ods = VecDeque::new();
+
+        for mut od in ods {
+            let granted = rate_limiter.acquire(od.batch.len() as u64) as usize;
+
+            if granted < od.batch.len() {
+                let mut throttled_od = od.clone();
+                throttled_od.batch = od.batch.split_off(granted);
+                throttled_ods.push_back(throttled_od);
+            }
+
+            if !od.batch.is_empty() {
+                granted_ods.push_back(od);
+            }
+        }
+
+        if throttled_ods.is_empty() {
+            return granted_ods;
+        }
+
+        let throttled: usize = throttled_ods.iter().map(|od| od.batch.len()).sum();
+
+        // Report the throttling once per window, when the limit is reached,
+        // rather than on every fetch while it remains exhausted
+        if granted_ods.is_empty() {
+            trace!(
+                "[{}] rate limit exhausted, delaying {} msg(s) to a subsequent window",
+                self,
+                throttled
+            );
+        } else {
+            warn!(
+                "[{}] rate limit of {} msg(s) per {:?} reached, delaying {} msg(s) to a subsequent window",
+                self,
+                rate_limiter.limit(),
+                rate_limiter.window(),
+                throttled,
+            );
+
+            telemetry!(
+                packet_msgs_throttled,
+                &self.src_chain().id(),
+                self.src_channel_id(),
+                self.src_port_id(),
+                throttled as u64,
+            );
+        }
+
+        // The throttled data remains ahead of the data scheduled since, in the same order
+        for od in throttled_ods.into_iter().rev() {
+            match od.target {
+                OperationalDataTarget::Source => self.src_operational_data.push_front(od),
+                OperationalDataTarget::Destination => self.dst_operational_data.push_front(od),
+            }
+        }
+
+        granted_ods
+    }
+
     /// Pulls out the operational elements with elapsed delay period and that can
     /// now be processed. Does not block: if no OD fulfilled the delay period (or none is
     /// scheduled), returns immediately with `vec![]`.
@@ -1570,3 +2108,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> fmt::Display for RelayPath<ChainA
         )
     }
 }
+
+/// Whether the sequence is within one of the given ranges, any sequence being
+/// accepted when no range is given.
+fn in_seq_ranges(seq: &Sequence, seq_ranges: &[RangeInclusive<Sequence>]) -> bool {
+    seq_ranges.is_empty() || seq_ranges.iter().any(|range| range.contains(seq))
+}
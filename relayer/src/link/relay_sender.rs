@@ -2,11 +2,14 @@ use core::fmt;
 
 use prost_types::Any;
 use tendermint_rpc::endpoint::broadcast::tx_sync;
-use tracing::info;
+use tracing::{info, warn};
 
 use ibc::events::{IbcEvent, PrettyEvents};
 
+use crate::chain::dry_run;
 use crate::chain::handle::ChainHandle;
+use crate::config::FeePolicy;
+use crate::link::batcher::{BatchReply, Batcher};
 use crate::link::error::LinkError;
 use crate::link::RelaySummary;
 
@@ -24,7 +27,24 @@ impl SubmitReply for RelaySummary {
 pub trait Submit {
     type Reply: SubmitReply;
 
-    fn submit(target: &impl ChainHandle, msgs: Vec<Any>) -> Result<Self::Reply, LinkError>;
+    fn submit(
+        target: &impl ChainHandle,
+        msgs: Vec<Any>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Self::Reply, LinkError>;
+
+    /// Submit the messages through the batcher of the target chain, which may carry them
+    /// in the same transactions as the messages of other paths. Senders which do not
+    /// support batching, such as the [`SyncSender`] used by the commands relaying
+    /// packets directly, submit the messages on their own.
+    fn submit_batched(
+        _batcher: &Batcher,
+        target: &impl ChainHandle,
+        msgs: Vec<Any>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Self::Reply, LinkError> {
+        Self::submit(target, msgs, fee_policy)
+    }
 }
 
 /// Synchronous sender
@@ -36,9 +56,13 @@ impl Submit for SyncSender {
     // TODO: Switch from the `Chain::send_msgs` interface in this method
     //  to use `Chain::submit_msgs` instead; implement waiting for block
     //  commits directly here (instead of blocking in the chain runtime).
-    fn submit(target: &impl ChainHandle, msgs: Vec<Any>) -> Result<Self::Reply, LinkError> {
+    fn submit(
+        target: &impl ChainHandle,
+        msgs: Vec<Any>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Self::Reply, LinkError> {
         let tx_events = target
-            .send_messages_and_wait_commit(msgs)
+            .send_messages_and_wait_commit_with_fee_policy(msgs, fee_policy)
             .map_err(LinkError::relayer)?;
 
         info!(
@@ -61,11 +85,18 @@ impl Submit for SyncSender {
 
 pub struct AsyncReply {
     pub responses: Vec<tx_sync::Response>,
+    /// The events known as soon as the messages are submitted: the events of the
+    /// transactions simulated in dry-run mode, or the error events of the messages
+    /// dropped from their transactions because they failed the simulation
+    pub events: Vec<IbcEvent>,
 }
 
 impl SubmitReply for AsyncReply {
     fn empty() -> Self {
-        Self { responses: vec![] }
+        Self {
+            responses: vec![],
+            events: vec![],
+        }
     }
 }
 
@@ -76,15 +107,107 @@ pub struct AsyncSender;
 impl Submit for AsyncSender {
     type Reply = AsyncReply;
 
-    fn submit(target: &impl ChainHandle, msgs: Vec<Any>) -> Result<Self::Reply, LinkError> {
-        let a = target
-            .send_messages_and_wait_check_tx(msgs)
+    fn submit(
+        target: &impl ChainHandle,
+        msgs: Vec<Any>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Self::Reply, LinkError> {
+        // The simulated transactions are not broadcast, such that their events
+        // are only reported by the chain once they are "committed".
+        if dry_run::is_enabled() {
+            let events = target
+                .send_messages_and_wait_commit_with_fee_policy(msgs, fee_policy)
+                .map_err(LinkError::relayer)?;
+            let reply = AsyncReply {
+                responses: vec![],
+                events,
+            };
+            info!("[Async~>{} dry run] {}\n", target.id(), reply);
+
+            return Ok(reply);
+        }
+
+        let check_tx = target
+            .send_messages_and_wait_check_tx_with_fee_policy(msgs, fee_policy)
             .map_err(LinkError::relayer)?;
-        let reply = AsyncReply { responses: a };
+        let reply = AsyncReply {
+            responses: check_tx.responses,
+            events: check_tx.dropped_events,
+        };
         info!("[Async~>{}] {}\n", target.id(), reply);
 
         Ok(reply)
     }
+
+    fn submit_batched(
+        batcher: &Batcher,
+        target: &impl ChainHandle,
+        msgs: Vec<Any>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Self::Reply, LinkError> {
+        if dry_run::is_enabled() {
+            return Self::submit(target, msgs, fee_policy);
+        }
+
+        let BatchReply {
+            mut responses,
+            dropped_events: mut events,
+            unsent,
+        } = match batcher.submit(msgs.clone(), fee_policy.clone()) {
+            Some(reply) => reply,
+            None => {
+                warn!(
+                    "[Async~>{}] failed to submit {} message(s) in a batch, submitting them on their own",
+                    target.id(),
+                    msgs.len()
+                );
+
+                return Self::submit(target, msgs, fee_policy);
+            }
+        };
+
+        // Only the messages which were not carried by a tx accepted by the chain are resubmitted
+        if !unsent.is_empty() {
+            warn!(
+                "[Async~>{}] {} message(s) were not submitted in a batch, submitting them on their own",
+                target.id(),
+                unsent.len()
+            );
+
+            let unsent_count = unsent.len();
+
+            match Self::submit(target, unsent, fee_policy) {
+                Ok(unsent_reply) => {
+                    responses.extend(unsent_reply.responses);
+                    events.extend(unsent_reply.events);
+                }
+                Err(e) if responses.is_empty() => return Err(e),
+                // The transactions accepted in a batch are still tracked until they are
+                // confirmed, along with an error event for the messages which failed,
+                // such that they are reported once the accepted transactions are.
+                Err(e) => {
+                    warn!(
+                        "[Async~>{}] failed to submit {} message(s) on their own: {}",
+                        target.id(),
+                        unsent_count,
+                        e
+                    );
+
+                    events.push(IbcEvent::ChainError(format!(
+                        "failed to submit {} message(s) to chain {} on their own: {}",
+                        unsent_count,
+                        target.id(),
+                        e
+                    )));
+                }
+            }
+        }
+
+        let reply = AsyncReply { responses, events };
+        info!("[Async~>{} batched] {}\n", target.id(), reply);
+
+        Ok(reply)
+    }
 }
 
 impl fmt::Display for AsyncReply {
@@ -92,6 +215,12 @@ impl fmt::Display for AsyncReply {
         write!(f, "response(s): {}", self.responses.len())?;
         self.responses
             .iter()
-            .try_for_each(|r| write!(f, "; {:?}:{}", r.code, r.hash))
+            .try_for_each(|r| write!(f, "; {:?}:{}", r.code, r.hash))?;
+
+        if !self.events.is_empty() {
+            write!(f, "; events {}", PrettyEvents(&self.events))?;
+        }
+
+        Ok(())
     }
 }
@@ -12,7 +12,7 @@ use ibc::core::ics24_host::identifier::ChainId;
 
 use crate::util::lock::RwArc;
 use crate::{
-    chain::{handle::ChainHandle, runtime::ChainRuntime, CosmosSdkChain},
+    chain::{backend::spawn_chain_backend, handle::ChainHandle},
     config::Config,
     error::Error as RelayerError,
 };
@@ -164,8 +164,7 @@ pub fn spawn_chain_runtime<Chain: ChainHandle>(
         .cloned()
         .ok_or_else(|| SpawnError::missing_chain(chain_id.clone()))?;
 
-    let handle =
-        ChainRuntime::<CosmosSdkChain>::spawn(chain_config, rt).map_err(SpawnError::relayer)?;
+    let handle = spawn_chain_backend(chain_config, rt).map_err(SpawnError::relayer)?;
 
     Ok(handle)
 }
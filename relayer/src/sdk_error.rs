@@ -19,6 +19,10 @@ define_error! {
         OutOfGas
             { code: u32 }
             |_| { "the price configuration for this chain may be too low! please check the `gas_price.price` Hermes config.toml".to_string() },
+
+        IncorrectAccountSequence
+            { code: u32 }
+            |_| { "the account sequence of the transaction does not match that of the account on chain, is the key used by another process?".to_string() },
     }
 }
 
@@ -181,6 +185,55 @@ pub fn sdk_error_from_tx_sync_error_code(code: u32) -> SdkError {
         // is due to "out of gas" errors. These are unrecoverable at the moment
         // on the Hermes side. We'll inform the user to check for misconfig.
         11 => SdkError::out_of_gas(code),
+        32 => SdkError::incorrect_account_sequence(code),
         _ => SdkError::unknown_sdk(code),
     }
 }
+
+/// Returns true if the given error code originating from a `broadcast_tx_sync`
+/// response denotes an account sequence mismatch.
+pub fn is_incorrect_account_sequence_code(code: u32) -> bool {
+    matches!(
+        sdk_error_from_tx_sync_error_code(code).detail(),
+        SdkErrorDetail::IncorrectAccountSequence(_)
+    )
+}
+
+/// Extracts the index of the message which failed the execution of a transaction
+/// from the error log returned by the chain, as formatted by the cosmos-sdk
+/// `baseapp`, e.g. `failed to execute message; message index: 1: ...`.
+/// Cf: <https://github.com/cosmos/cosmos-sdk/blob/v0.44.5/baseapp/baseapp.go>
+pub fn failed_message_index(log: &str) -> Option<usize> {
+    const MARKER: &str = "message index: ";
+
+    let start = log.find(MARKER)? + MARKER.len();
+    let digits = log[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{failed_message_index, is_incorrect_account_sequence_code};
+
+    #[test]
+    fn parse_failed_message_index() {
+        assert_eq!(
+            failed_message_index(
+                "failed to execute message; message index: 12: receive packet verification failed"
+            ),
+            Some(12)
+        );
+        assert_eq!(failed_message_index("account sequence mismatch"), None);
+        assert_eq!(failed_message_index("message index: abc"), None);
+    }
+
+    #[test]
+    fn detect_incorrect_account_sequence() {
+        assert!(is_incorrect_account_sequence_code(32));
+        assert!(!is_incorrect_account_sequence_code(11));
+    }
+}
@@ -0,0 +1,297 @@
+//! Persistent store of the relayer state.
+//!
+//! When enabled, the relayer persists in an embedded on-disk database the
+//! operational data scheduled on each relaying path, the transactions it
+//! submitted and which are pending confirmation, as well as the last height
+//! at which it processed events of each chain. A restarted relayer thus
+//! relays the data it had scheduled, resumes tracking the confirmation of
+//! the transactions it had in flight instead of submitting their messages
+//! anew, and collects the events of the blocks committed since the last
+//! height it processed.
+
+use std::path::PathBuf;
+
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::debug;
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::Height;
+
+use crate::config::StoreConfig;
+
+pub mod error;
+pub use error::Error;
+
+pub const STORE_DEFAULT_FOLDER: &str = ".hermes/store";
+
+const HEIGHTS_TREE: &str = "heights";
+const PENDING_TXS_TREE_PREFIX: &str = "pending_txs";
+const SCHEDULED_OPS_TREE_PREFIX: &str = "scheduled_ops";
+
+/// A handle to the relayer store, which can be cheaply cloned.
+#[derive(Clone, Debug)]
+pub struct RelayerStore {
+    db: sled::Db,
+}
+
+impl RelayerStore {
+    /// Open the store configured by the given configuration, if it is enabled.
+    pub fn from_config(config: &StoreConfig) -> Result<Option<Self>, Error> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let path = match &config.path {
+            Some(path) => path.clone(),
+            None => dirs_next::home_dir()
+                .ok_or_else(Error::home_location_unavailable)?
+                .join(STORE_DEFAULT_FOLDER),
+        };
+
+        Self::open(path).map(Some)
+    }
+
+    pub fn open(path: PathBuf) -> Result<Self, Error> {
+        debug!("opening relayer store at {}", path.display());
+
+        let db = sled::open(&path).map_err(|e| Error::open(path, e))?;
+
+        Ok(Self { db })
+    }
+
+    /// Open a store which is deleted once dropped.
+    pub fn temporary() -> Result<Self, Error> {
+        let db = sled::Config::new()
+            .temporary(true)
+            .open()
+            .map_err(Error::db)?;
+
+        Ok(Self { db })
+    }
+
+    /// Record the last height at which events of the given chain were processed.
+    pub fn record_height(&self, chain_id: &ChainId, height: Height) -> Result<(), Error> {
+        let tree = self.db.open_tree(HEIGHTS_TREE).map_err(Error::db)?;
+        tree.insert(chain_id.as_str(), encode(&height)?)
+            .map_err(Error::db)?;
+
+        Ok(())
+    }
+
+    /// The last height at which events of the given chain were processed, if any was recorded.
+    pub fn last_height(&self, chain_id: &ChainId) -> Result<Option<Height>, Error> {
+        let tree = self.db.open_tree(HEIGHTS_TREE).map_err(Error::db)?;
+
+        tree.get(chain_id.as_str())
+            .map_err(Error::db)?
+            .map(|bytes| decode(&bytes))
+            .transpose()
+    }
+
+    /// The store of the pending transactions with the given name,
+    /// which identifies the relaying path and the chain they were submitted to.
+    pub fn pending_txs(&self, name: &str) -> Result<RecordStore, Error> {
+        self.records(PENDING_TXS_TREE_PREFIX, name)
+    }
+
+    /// The store of the operational data scheduled on the relaying path with the given name.
+    pub fn scheduled_ops(&self, name: &str) -> Result<RecordStore, Error> {
+        self.records(SCHEDULED_OPS_TREE_PREFIX, name)
+    }
+
+    fn records(&self, prefix: &str, name: &str) -> Result<RecordStore, Error> {
+        let tree = self
+            .db
+            .open_tree(format!("{}/{}", prefix, name))
+            .map_err(Error::db)?;
+
+        Ok(RecordStore {
+            db: self.db.clone(),
+            tree,
+        })
+    }
+}
+
+/// A store of records, such as the transactions submitted on a relaying path which
+/// are pending confirmation. Each record is identified by a unique id, assigned when
+/// it is inserted.
+#[derive(Clone, Debug)]
+pub struct RecordStore {
+    db: sled::Db,
+    tree: sled::Tree,
+}
+
+impl RecordStore {
+    /// Insert the given record, returning its id.
+    pub fn insert<T: Serialize>(&self, record: &T) -> Result<u64, Error> {
+        let id = self.db.generate_id().map_err(Error::db)?;
+
+        self.tree
+            .insert(id.to_be_bytes(), encode(record)?)
+            .map_err(Error::db)?;
+        self.tree.flush().map_err(Error::db)?;
+
+        Ok(id)
+    }
+
+    /// Remove the record with the given id.
+    pub fn remove(&self, id: u64) -> Result<(), Error> {
+        self.tree.remove(id.to_be_bytes()).map_err(Error::db)?;
+        self.tree.flush().map_err(Error::db)?;
+
+        Ok(())
+    }
+
+    /// Replace all the records with the given ones, atomically.
+    pub fn replace_all<T: Serialize>(&self, records: &[T]) -> Result<(), Error> {
+        let mut batch = sled::Batch::default();
+
+        for key in self.tree.iter().keys() {
+            batch.remove(key.map_err(Error::db)?);
+        }
+
+        for record in records {
+            let id = self.db.generate_id().map_err(Error::db)?;
+            batch.insert(&id.to_be_bytes(), encode(record)?);
+        }
+
+        self.tree.apply_batch(batch).map_err(Error::db)?;
+        self.tree.flush().map_err(Error::db)?;
+
+        Ok(())
+    }
+
+    /// All the records, in the order they were inserted.
+    pub fn all<T: DeserializeOwned>(&self) -> Result<Vec<(u64, T)>, Error> {
+        self.tree
+            .iter()
+            .map(|entry| {
+                let (key, value) = entry.map_err(Error::db)?;
+
+                let mut id = [0; 8];
+                id.copy_from_slice(&key);
+
+                Ok((u64::from_be_bytes(id), decode(&value)?))
+            })
+            .collect()
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, Error> {
+    serde_json::to_vec(value).map_err(Error::encode)
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    serde_json::from_slice(bytes).map_err(Error::decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_txs_roundtrip() {
+        let store = RelayerStore::temporary().unwrap();
+
+        let pending = store
+            .pending_txs("ibc-0:transfer/channel-0 -> ibc-1")
+            .unwrap();
+        let first = pending.insert(&"first".to_string()).unwrap();
+        let second = pending.insert(&"second".to_string()).unwrap();
+
+        let other = store
+            .pending_txs("ibc-1:transfer/channel-0 -> ibc-0")
+            .unwrap();
+        assert!(other.all::<String>().unwrap().is_empty());
+
+        assert_eq!(
+            pending.all::<String>().unwrap(),
+            vec![(first, "first".to_string()), (second, "second".to_string())]
+        );
+
+        pending.remove(first).unwrap();
+        assert_eq!(
+            pending.all::<String>().unwrap(),
+            vec![(second, "second".to_string())]
+        );
+    }
+
+    #[test]
+    fn replace_records() {
+        let store = RelayerStore::temporary().unwrap();
+        let scheduled = store.scheduled_ops("ibc-0:transfer/channel-0").unwrap();
+
+        scheduled
+            .replace_all(&["first".to_string(), "second".to_string()])
+            .unwrap();
+        scheduled.replace_all(&["third".to_string()]).unwrap();
+
+        let records = scheduled.all::<String>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].1, "third");
+    }
+
+    #[test]
+    fn restart_roundtrip() {
+        let path = std::env::temp_dir().join(format!(
+            "hermes-store-restart-roundtrip-{}",
+            std::process::id()
+        ));
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+
+        {
+            let store = RelayerStore::open(path.clone()).unwrap();
+            store.record_height(&chain_id, Height::new(0, 42)).unwrap();
+            store
+                .pending_txs("ibc-0:transfer/channel-0 -> ibc-1")
+                .unwrap()
+                .insert(&"pending".to_string())
+                .unwrap();
+            store
+                .scheduled_ops("ibc-0:transfer/channel-0")
+                .unwrap()
+                .replace_all(&["scheduled".to_string()])
+                .unwrap();
+        }
+
+        let store = RelayerStore::open(path.clone()).unwrap();
+
+        assert_eq!(
+            store.last_height(&chain_id).unwrap(),
+            Some(Height::new(0, 42))
+        );
+
+        let pending = store
+            .pending_txs("ibc-0:transfer/channel-0 -> ibc-1")
+            .unwrap()
+            .all::<String>()
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].1, "pending");
+
+        let scheduled = store
+            .scheduled_ops("ibc-0:transfer/channel-0")
+            .unwrap()
+            .all::<String>()
+            .unwrap();
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(scheduled[0].1, "scheduled");
+
+        drop(store);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn last_heights() {
+        let store = RelayerStore::temporary().unwrap();
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+
+        assert_eq!(store.last_height(&chain_id).unwrap(), None);
+
+        store.record_height(&chain_id, Height::new(0, 42)).unwrap();
+        assert_eq!(
+            store.last_height(&chain_id).unwrap(),
+            Some(Height::new(0, 42))
+        );
+    }
+}
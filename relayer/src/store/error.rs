@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use flex_error::{define_error, TraceError};
+
+define_error! {
+    Error {
+        Open
+            { path: PathBuf }
+            [ TraceError<sled::Error> ]
+            |e| { format!("failed to open the relayer store at {}", e.path.display()) },
+
+        Db
+            [ TraceError<sled::Error> ]
+            |_| { "error accessing the relayer store" },
+
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode a value of the relayer store" },
+
+        Decode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to decode a value of the relayer store" },
+
+        InvalidRecord
+            { reason: String }
+            |e| { format!("invalid record in the relayer store: {}", e.reason) },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+    }
+}
@@ -17,13 +17,15 @@ use ibc::{
 use crate::util::lock::LockExt;
 use crate::util::task::{spawn_background_task, TaskError, TaskHandle};
 use crate::{
-    chain::{handle::ChainHandle, HealthCheck},
-    config::{ChainConfig, Config},
+    chain::{dry_run, handle::ChainHandle, HealthCheck},
+    config::{ChainConfig, Config, StoreConfig},
     event,
     event::monitor::{Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
+    link::Batchers,
     object::Object,
     registry::{Registry, SharedRegistry},
     rest,
+    store::RelayerStore,
     util::try_recv_multiple,
     worker::WorkerMap,
 };
@@ -113,7 +115,22 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle + 'static>(
         health_check(&config.acquire_read(), &mut registry.write());
     }
 
-    let workers = Arc::new(RwLock::new(WorkerMap::new()));
+    let store = open_store(&config.acquire_read().store, dry_run::is_enabled())?;
+
+    if let Some(store) = &store {
+        resume_events(&config.acquire_read(), &mut registry.write(), store);
+    }
+
+    let batchers = config
+        .acquire_read()
+        .mode
+        .packets
+        .batch_window
+        .map(Batchers::new);
+
+    let workers = Arc::new(RwLock::new(
+        WorkerMap::with_store(store).with_batchers(batchers),
+    ));
     let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
 
     spawn_context(
@@ -157,6 +174,68 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle + 'static>(
     Ok(tasks)
 }
 
+/// Open the relayer store, if it is enabled and unless in dry-run mode, in which nothing
+/// is submitted and thus neither the scheduled data nor the processed heights persist.
+fn open_store(config: &StoreConfig, dry_run: bool) -> Result<Option<RelayerStore>, Error> {
+    if dry_run {
+        if config.enabled {
+            info!("the relayer store is not used in dry-run mode");
+        }
+
+        return Ok(None);
+    }
+
+    RelayerStore::from_config(config).map_err(Error::store)
+}
+
+/// Resume collecting the events of each chain from the last height at which they were
+/// processed before a restart, such that the events of the blocks committed while the
+/// relayer was down are relayed.
+fn resume_events<Chain: ChainHandle>(
+    config: &Config,
+    registry: &mut Registry<Chain>,
+    store: &RelayerStore,
+) {
+    for chain_config in &config.chains {
+        let height = match store.last_height(&chain_config.id) {
+            Ok(Some(height)) => height,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!(
+                    "failed to read the last processed height of chain {} from the store: {}",
+                    chain_config.id, e
+                );
+
+                continue;
+            }
+        };
+
+        let chain = match registry.get_or_spawn(&chain_config.id) {
+            Ok(chain) => chain,
+            Err(e) => {
+                error!(
+                    "failed to spawn chain runtime for {}: {}",
+                    chain_config.id, e
+                );
+
+                continue;
+            }
+        };
+
+        info!(
+            "resuming relaying on chain {} from last processed height {}",
+            chain_config.id, height
+        );
+
+        if let Err(e) = chain.resume_events_from(height) {
+            warn!(
+                "failed to resume the events of chain {} from height {}: {}",
+                chain_config.id, height, e
+            );
+        }
+    }
+}
+
 fn spawn_batch_worker<Chain: ChainHandle + 'static>(
     config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
@@ -636,6 +715,17 @@ fn process_batch<Chain: ChainHandle + 'static>(
             .map_err(Error::worker)?
     }
 
+    // Record the height once its events were handed off to the workers,
+    // so that they are collected again after a restart otherwise.
+    if let Some(store) = workers.store() {
+        if let Err(e) = store.record_height(&chain_id, height) {
+            warn!(
+                "failed to record the last processed height of chain {}: {}",
+                chain_id, e
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -845,3 +935,26 @@ impl CollectedEvents {
         self.new_block.is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_is_not_opened_in_dry_run_mode() {
+        let path =
+            std::env::temp_dir().join(format!("hermes-store-dry-run-{}", std::process::id()));
+        let config = StoreConfig {
+            enabled: true,
+            path: Some(path.clone()),
+        };
+
+        assert!(open_store(&config, true).unwrap().is_none());
+        assert!(!path.exists());
+
+        assert!(open_store(&config, false).unwrap().is_some());
+        assert!(path.exists());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
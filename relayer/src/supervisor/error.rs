@@ -5,6 +5,7 @@ use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId
 
 use crate::error::Error as RelayerError;
 use crate::registry::SpawnError;
+use crate::store::Error as StoreError;
 use crate::worker::WorkerError;
 
 define_error! {
@@ -69,5 +70,9 @@ define_error! {
         Worker
             [ WorkerError ]
             |_| { "worker error" },
+
+        Store
+            [ StoreError ]
+            |_| { "relayer store error" },
     }
 }
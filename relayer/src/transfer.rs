@@ -4,11 +4,11 @@ use core::time::Duration;
 
 use flex_error::{define_error, DetailOnly};
 use ibc::applications::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
+use ibc::core::ics04_channel::timeout::TimeoutHeight;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::events::IbcEvent;
 use ibc::timestamp::{Timestamp, TimestampOverflowError};
 use ibc::tx_msg::Msg;
-use ibc::Height;
 use uint::FromStrRadixErr;
 
 use crate::chain::handle::ChainHandle;
@@ -101,12 +101,13 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
     };
 
     let timeout_height = if opts.timeout_height_offset == 0 {
-        Height::zero()
+        TimeoutHeight::Never
     } else {
-        packet_dst_chain
+        let latest_height = packet_dst_chain
             .query_latest_height()
-            .map_err(PacketError::relayer)?
-            .add(opts.timeout_height_offset)
+            .map_err(PacketError::relayer)?;
+
+        TimeoutHeight::At(latest_height.add(opts.timeout_height_offset))
     };
 
     let msg = MsgTransfer {
@@ -44,6 +44,16 @@ define_error! {
                     e.event)
             },
 
+        InvalidUpgradedChainId
+            {
+                chain_id: ChainId,
+                upgraded_chain_id: ChainId,
+            }
+            |e| {
+                format!("cannot upgrade chain {0} to chain {1}: the revision number of the upgraded chain must not be lower than {2}",
+                    e.chain_id, e.upgraded_chain_id, e.chain_id.version())
+            },
+
     }
 }
 
@@ -65,6 +75,13 @@ pub fn build_and_send_ibc_upgrade_proposal(
     src_chain: CosmosSdkChain, // the source chain; supplies a client state for building the upgrade plan
     opts: &UpgradePlanOptions,
 ) -> Result<Vec<IbcEvent>, UpgradeChainError> {
+    if opts.upgraded_chain_id.version() < dst_chain.id().version() {
+        return Err(UpgradeChainError::invalid_upgraded_chain_id(
+            dst_chain.id().clone(),
+            opts.upgraded_chain_id.clone(),
+        ));
+    }
+
     let upgrade_height = dst_chain
         .query_latest_height()
         .map_err(UpgradeChainError::query)?
@@ -79,8 +96,13 @@ pub fn build_and_send_ibc_upgrade_proposal(
         .upgraded_unbonding_period
         .unwrap_or(client_state.unbonding_period);
 
+    // The first height of the upgraded chain follows the upgrade height, and belongs
+    // to the revision of the upgraded chain identifier, eg. `2` when `foo-1` is
+    // upgraded to `foo-2`. Otherwise, the client could not be updated past the upgrade.
     let mut upgraded_client_state = ClientState::zero_custom_fields(client_state);
-    upgraded_client_state.latest_height = upgrade_height.increment();
+    upgraded_client_state.latest_height = upgrade_height
+        .increment()
+        .with_revision_number(opts.upgraded_chain_id.version());
     upgraded_client_state.unbonding_period = upgraded_unbonding_period;
     upgraded_client_state.chain_id = opts.upgraded_chain_id.clone();
 
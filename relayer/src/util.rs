@@ -8,7 +8,10 @@ pub mod bigint;
 pub mod diff;
 pub mod iter;
 pub mod lock;
+pub mod priority;
 pub mod queue;
+pub mod rate_limit;
 pub mod retry;
+pub mod seq_range;
 pub mod stream;
 pub mod task;
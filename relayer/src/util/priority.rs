@@ -0,0 +1,132 @@
+//! Prioritisation of the submissions of the paths relaying to the same chain.
+//!
+//! Each path relays its packets in its own worker, such that the submissions of
+//! the paths relaying to a chain compete for its runtime, account sequences and
+//! fee budget. The priority gate of a chain holds back the submissions of the
+//! paths with a lower priority for as long as a submission of a higher priority
+//! is waiting or in progress, while the submissions of the same priority proceed
+//! concurrently.
+
+use alloc::collections::BTreeMap as HashMap;
+use core::ops::Bound;
+use std::sync::{Arc, Condvar, Mutex};
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::util::lock::{LockExt, RwArc};
+
+/// The priority gate of a chain, which can be cheaply cloned.
+#[derive(Clone, Debug, Default)]
+pub struct PriorityGate {
+    /// The number of submissions waiting or in progress, per priority
+    pending: Arc<(Mutex<HashMap<u32, usize>>, Condvar)>,
+}
+
+impl PriorityGate {
+    /// Wait until no submission of a higher priority is waiting or in progress,
+    /// and enter the gate with the given priority until the returned guard is dropped.
+    pub fn enter(&self, priority: u32) -> PriorityGuard {
+        let (pending, cvar) = &*self.pending;
+
+        let mut pending = pending.lock().unwrap();
+        *pending.entry(priority).or_insert(0) += 1;
+
+        while pending
+            .range((Bound::Excluded(priority), Bound::Unbounded))
+            .next()
+            .is_some()
+        {
+            pending = cvar.wait(pending).unwrap();
+        }
+
+        PriorityGuard {
+            gate: self.clone(),
+            priority,
+        }
+    }
+}
+
+/// A submission in progress through a [`PriorityGate`], which leaves the gate once dropped.
+#[derive(Debug)]
+pub struct PriorityGuard {
+    gate: PriorityGate,
+    priority: u32,
+}
+
+impl Drop for PriorityGuard {
+    fn drop(&mut self) {
+        let (pending, cvar) = &*self.gate.pending;
+
+        let mut pending = pending.lock().unwrap();
+        if let Some(count) = pending.get_mut(&self.priority) {
+            *count -= 1;
+            if *count == 0 {
+                pending.remove(&self.priority);
+            }
+        }
+
+        cvar.notify_all();
+    }
+}
+
+/// The priority gates of the chains relayed to, each created upon the first request for it.
+#[derive(Clone, Debug, Default)]
+pub struct PriorityGates {
+    gates: RwArc<HashMap<ChainId, PriorityGate>>,
+}
+
+impl PriorityGates {
+    /// The priority gate of the given chain.
+    pub fn get_or_create(&self, chain_id: &ChainId) -> PriorityGate {
+        if let Some(gate) = self.gates.acquire_read().get(chain_id) {
+            return gate.clone();
+        }
+
+        self.gates
+            .acquire_write()
+            .entry(chain_id.clone())
+            .or_insert_with(PriorityGate::default)
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    #[test]
+    fn lower_priority_waits_for_higher_priority() {
+        let gate = PriorityGate::default();
+        let high = gate.enter(10);
+
+        let entered = Arc::new(AtomicBool::new(false));
+        let low = {
+            let gate = gate.clone();
+            let entered = entered.clone();
+            thread::spawn(move || {
+                let _guard = gate.enter(1);
+                entered.store(true, Ordering::SeqCst);
+            })
+        };
+
+        thread::sleep(Duration::from_millis(100));
+        assert!(!entered.load(Ordering::SeqCst));
+
+        drop(high);
+        low.join().unwrap();
+        assert!(entered.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn same_or_higher_priority_proceeds() {
+        let gate = PriorityGate::default();
+
+        let _low = gate.enter(1);
+        let _same = gate.enter(1);
+        let _high = gate.enter(10);
+    }
+}
@@ -0,0 +1,96 @@
+//! Accounting of the amounts spent within a sliding time window, used to cap
+//! the number of messages relayed on a channel and the fees paid on a chain.
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use std::time::Instant;
+
+/// Limits the total amount spent within any window of the given duration.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    limit: u64,
+    window: Duration,
+    spent: VecDeque<(Instant, u64)>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u64, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            spent: VecDeque::new(),
+        }
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// The amount which can still be spent within the current window.
+    pub fn remaining(&mut self) -> u64 {
+        self.expire();
+
+        let spent = self
+            .spent
+            .iter()
+            .fold(0u64, |total, (_, amount)| total.saturating_add(*amount));
+
+        self.limit.saturating_sub(spent)
+    }
+
+    /// Record the given amount as spent now.
+    pub fn record(&mut self, amount: u64) {
+        if amount > 0 {
+            self.spent.push_back((Instant::now(), amount));
+        }
+    }
+
+    /// Spend as much as possible of the requested amount, within what remains
+    /// for the current window, and return the amount actually granted.
+    pub fn acquire(&mut self, amount: u64) -> u64 {
+        let granted = amount.min(self.remaining());
+        self.record(granted);
+        granted
+    }
+
+    fn expire(&mut self) {
+        while let Some((spent_at, _)) = self.spent.front() {
+            if spent_at.elapsed() < self.window {
+                break;
+            }
+
+            self.spent.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acquire_within_limit() {
+        let mut limiter = RateLimiter::new(10, Duration::from_secs(60));
+
+        assert_eq!(limiter.acquire(4), 4);
+        assert_eq!(limiter.acquire(4), 4);
+        assert_eq!(limiter.remaining(), 2);
+        assert_eq!(limiter.acquire(4), 2);
+        assert_eq!(limiter.acquire(1), 0);
+
+        limiter.record(5);
+        assert_eq!(limiter.remaining(), 0);
+    }
+
+    #[test]
+    fn spent_amounts_expire() {
+        let mut limiter = RateLimiter::new(10, Duration::from_secs(0));
+
+        assert_eq!(limiter.acquire(10), 10);
+        assert_eq!(limiter.remaining(), 10);
+    }
+}
@@ -0,0 +1,87 @@
+//! Parsing of the ranges of packet sequences given on the command line,
+//! eg. `5`, `1..10`, `..10` or `10..`, all ranges being inclusive.
+
+use core::ops::RangeInclusive;
+
+use flex_error::define_error;
+use ibc::core::ics04_channel::error::Error as ChannelError;
+use ibc::core::ics04_channel::packet::Sequence;
+
+define_error! {
+    Error {
+        InvalidSequence
+            [ ChannelError ]
+            |_| { "invalid packet sequence" },
+
+        InvalidRange
+            { range: String }
+            |e| {
+                format!("invalid range of packet sequences '{}': the start of the range is greater than its end", e.range)
+            },
+    }
+}
+
+/// Parses either a single sequence or an inclusive range of sequences, whose bounds
+/// are separated by `..`. The start of the range defaults to 1 when omitted, and
+/// its end to the largest sequence.
+pub fn parse_seq_range(input: &str) -> Result<RangeInclusive<Sequence>, Error> {
+    let input = input.trim();
+
+    let range = match input.split_once("..") {
+        None => {
+            let seq = parse_seq(input)?;
+            seq..=seq
+        }
+        Some((start, end)) => {
+            let start = if start.trim().is_empty() {
+                Sequence::from(1)
+            } else {
+                parse_seq(start)?
+            };
+
+            let end = if end.trim().is_empty() {
+                Sequence::from(u64::MAX)
+            } else {
+                parse_seq(end)?
+            };
+
+            start..=end
+        }
+    };
+
+    if range.is_empty() {
+        return Err(Error::invalid_range(input.to_string()));
+    }
+
+    Ok(range)
+}
+
+fn parse_seq(input: &str) -> Result<Sequence, Error> {
+    input.trim().parse().map_err(Error::invalid_sequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> RangeInclusive<Sequence> {
+        Sequence::from(start)..=Sequence::from(end)
+    }
+
+    #[test]
+    fn parse_single_sequences_and_ranges() {
+        assert_eq!(parse_seq_range("5").unwrap(), range(5, 5));
+        assert_eq!(parse_seq_range("1..10").unwrap(), range(1, 10));
+        assert_eq!(parse_seq_range(" 3 .. 3 ").unwrap(), range(3, 3));
+        assert_eq!(parse_seq_range("..10").unwrap(), range(1, 10));
+        assert_eq!(parse_seq_range("10..").unwrap(), range(10, u64::MAX));
+    }
+
+    #[test]
+    fn reject_invalid_ranges() {
+        assert!(parse_seq_range("").is_err());
+        assert!(parse_seq_range("a").is_err());
+        assert!(parse_seq_range("1..b").is_err());
+        assert!(parse_seq_range("10..1").is_err());
+    }
+}
@@ -1,13 +1,16 @@
 use alloc::sync::Arc;
 use core::fmt;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 use crate::foreign_client::ForeignClient;
-use crate::link::{Link, LinkParameters};
+use crate::link::{Batchers, Link, LinkParameters};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    store::RelayerStore,
+    util::priority::PriorityGates,
 };
 
 pub mod retry_strategy;
@@ -57,6 +60,9 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle + 'static, ChainB: ChainHandle + '
     id: WorkerId,
     object: Object,
     config: &Config,
+    store: Option<&RelayerStore>,
+    batchers: Option<&Batchers>,
+    priority_gates: &PriorityGates,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
@@ -94,7 +100,23 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle + 'static, ChainB: ChainHandle + '
                 packets_config.tx_confirmation,
             );
 
-            if let Ok(link) = link {
+            if let Ok(mut link) = link {
+                if let Some(store) = store {
+                    if let Err(e) = link.a_to_b.attach_store(store) {
+                        warn!(
+                            "failed to restore the state of {} from the store: {}",
+                            path.short_name(),
+                            e
+                        );
+                    }
+                }
+
+                if let Some(batchers) = batchers {
+                    link.a_to_b.attach_batchers(batchers);
+                }
+
+                link.a_to_b.attach_priority_gates(priority_gates);
+
                 let link = Arc::new(link);
                 let packet_task = packet::spawn_packet_cmd_worker(
                     cmd_rx,
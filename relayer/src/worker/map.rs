@@ -7,8 +7,11 @@ use tracing::{debug, trace};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
+    link::Batchers,
     object::Object,
+    store::RelayerStore,
     telemetry,
+    util::priority::PriorityGates,
 };
 
 use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
@@ -18,6 +21,9 @@ use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
     latest_worker_id: WorkerId,
+    store: Option<RelayerStore>,
+    batchers: Option<Batchers>,
+    priority_gates: PriorityGates,
 }
 
 impl Default for WorkerMap {
@@ -25,6 +31,9 @@ impl Default for WorkerMap {
         Self {
             workers: HashMap::new(),
             latest_worker_id: WorkerId::new(0),
+            store: None,
+            batchers: None,
+            priority_gates: PriorityGates::default(),
         }
     }
 }
@@ -37,6 +46,25 @@ impl WorkerMap {
         Self::default()
     }
 
+    /// Create a new worker map, whose workers persist their state in the given store.
+    pub fn with_store(store: Option<RelayerStore>) -> Self {
+        Self {
+            store,
+            ..Self::default()
+        }
+    }
+
+    /// Submit the messages of the packet workers through the given batchers, if any,
+    /// which merge the messages of the paths relaying to the same chain.
+    pub fn with_batchers(self, batchers: Option<Batchers>) -> Self {
+        Self { batchers, ..self }
+    }
+
+    /// The store in which the workers persist their state, if enabled.
+    pub fn store(&self) -> Option<&RelayerStore> {
+        self.store.as_ref()
+    }
+
     /// Returns `true` if there is a spawned [`Worker`] associated with the given [`Object`].
     pub fn contains(&self, object: &Object) -> bool {
         self.workers.contains_key(object)
@@ -156,6 +184,9 @@ impl WorkerMap {
             self.next_worker_id(),
             object.clone(),
             config,
+            self.store.as_ref(),
+            self.batchers.as_ref(),
+            &self.priority_gates,
         )
     }
 
@@ -156,18 +156,31 @@ fn packet_metrics(path: &Packet, summary: &RelaySummary) {
 fn receive_packet_metrics(path: &Packet, summary: &RelaySummary) {
     use ibc::events::IbcEvent::WriteAcknowledgement;
 
-    let count = summary
+    let (count, failed) = summary
         .events
         .iter()
-        .filter(|e| matches!(e, WriteAcknowledgement(_)))
-        .count();
+        .filter_map(|e| match e {
+            WriteAcknowledgement(ev) => Some(ev.ack.is_error()),
+            _ => None,
+        })
+        .fold((0, 0), |(count, failed), is_error| {
+            (count + 1, failed + is_error as u64)
+        });
 
     telemetry!(
         ibc_receive_packets,
         &path.src_chain_id,
         &path.src_channel_id,
         &path.src_port_id,
-        count as u64,
+        count,
+    );
+
+    telemetry!(
+        ibc_receive_packets_failed,
+        &path.src_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        failed,
     );
 }
 
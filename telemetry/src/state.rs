@@ -2,7 +2,7 @@ use core::fmt;
 
 use opentelemetry::{
     global,
-    metrics::{Counter, UpDownCounter},
+    metrics::{Counter, UpDownCounter, ValueRecorder},
     KeyValue,
 };
 use opentelemetry_prometheus::PrometheusExporter;
@@ -45,11 +45,27 @@ pub struct TelemetryState {
     /// Number of receive packets relayed, per channel
     receive_packets: Counter<u64>,
 
+    /// Number of receive packets relayed for which the destination
+    /// application returned an error acknowledgement, per channel
+    receive_packets_failed: Counter<u64>,
+
     /// Number of acknowledgment packets relayed, per channel
     acknowledgment_packets: Counter<u64>,
 
     /// Number of timeout packets relayed, per channel
     timeout_packets: Counter<u64>,
+
+    /// Effective gas price paid for transactions, per chain and denomination
+    gas_price: ValueRecorder<f64>,
+
+    /// Number of packet messages delayed to a subsequent window by the rate limit of their channel
+    packet_msgs_throttled: Counter<u64>,
+
+    /// Number of transactions not submitted because the fee budget of their chain was exhausted
+    fee_budget_exhausted: Counter<u64>,
+
+    /// Whether an endpoint of a chain is the active one, per chain and RPC address
+    active_endpoint: UpDownCounter<i64>,
 }
 
 impl TelemetryState {
@@ -101,6 +117,24 @@ impl TelemetryState {
         self.receive_packets.add(count, labels);
     }
 
+    /// Number of receive packets relayed for which the destination
+    /// application returned an error acknowledgement, per channel
+    pub fn ibc_receive_packets_failed(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.receive_packets_failed.add(count, labels);
+    }
+
     pub fn ibc_acknowledgment_packets(
         &self,
         src_chain: &ChainId,
@@ -132,6 +166,51 @@ impl TelemetryState {
 
         self.timeout_packets.add(count, labels);
     }
+
+    /// Record the effective gas price paid for transactions on a chain
+    pub fn gas_price(&self, chain: &ChainId, denom: &str, price: f64) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.gas_price.record(price, labels);
+    }
+
+    /// Count the packet messages on a channel which were throttled by its rate limit
+    pub fn packet_msgs_throttled(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.packet_msgs_throttled.add(count, labels);
+    }
+
+    /// Count a transaction which was not submitted to a chain because of its fee budget
+    pub fn fee_budget_exhausted(&self, chain: &ChainId) {
+        let labels = &[KeyValue::new("chain", chain.to_string())];
+
+        self.fee_budget_exhausted.add(1, labels);
+    }
+
+    /// Mark the endpoint of a chain with the given RPC address as active or inactive
+    pub fn active_endpoint(&self, chain: &ChainId, rpc_addr: &str, active: bool) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("rpc_addr", rpc_addr.to_string()),
+        ];
+
+        self.active_endpoint
+            .add(if active { 1 } else { -1 }, labels);
+    }
 }
 
 impl Default for TelemetryState {
@@ -162,6 +241,13 @@ impl Default for TelemetryState {
                 .with_description("Number of receive packets relayed per channel")
                 .init(),
 
+            receive_packets_failed: meter
+                .u64_counter("ibc_receive_packets_failed")
+                .with_description(
+                    "Number of receive packets relayed per channel which resulted in an error acknowledgement",
+                )
+                .init(),
+
             acknowledgment_packets: meter
                 .u64_counter("ibc_acknowledgment_packets")
                 .with_description("Number of acknowledgment packets relayed per channel")
@@ -171,6 +257,30 @@ impl Default for TelemetryState {
                 .u64_counter("ibc_timeout_packets")
                 .with_description("Number of timeout packets relayed per channel")
                 .init(),
+
+            gas_price: meter
+                .f64_value_recorder("gas_price")
+                .with_description("Effective gas price paid for transactions per chain")
+                .init(),
+
+            packet_msgs_throttled: meter
+                .u64_counter("packet_msgs_throttled")
+                .with_description(
+                    "Number of packet messages per channel delayed to a subsequent window by its rate limit",
+                )
+                .init(),
+
+            fee_budget_exhausted: meter
+                .u64_counter("fee_budget_exhausted")
+                .with_description(
+                    "Number of transactions per chain not submitted because its fee budget was exhausted",
+                )
+                .init(),
+
+            active_endpoint: meter
+                .i64_up_down_counter("active_endpoint")
+                .with_description("Whether an endpoint of a chain, per RPC address, is the active one")
+                .init(),
         }
     }
 }
@@ -57,8 +57,8 @@ use ibc_proto::ibc::core::connection::v1::QueryClientConnectionsRequest;
 use ibc_proto::ibc::core::connection::v1::QueryConnectionsRequest;
 use ibc_relayer::chain::handle::requests::AppVersion;
 use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
-use ibc_relayer::chain::{HealthCheck, StatusResponse};
-use ibc_relayer::config::ChainConfig;
+use ibc_relayer::chain::{CheckTxResponses, HealthCheck, StatusResponse};
+use ibc_relayer::config::{ChainConfig, FeePolicy};
 use ibc_relayer::error::Error;
 use ibc_relayer::{connection::ConnectionMsgType, keyring::KeyEntry};
 
@@ -89,18 +89,35 @@ where
         self.value().subscribe()
     }
 
-    fn send_messages_and_wait_commit(
+    fn resume_events_from(&self, height: Height) -> Result<(), Error> {
+        self.value().resume_events_from(height)
+    }
+
+    fn send_messages_and_wait_commit_with_fee_policy(
         &self,
         proto_msgs: Vec<prost_types::Any>,
+        fee_policy: Option<FeePolicy>,
     ) -> Result<Vec<IbcEvent>, Error> {
-        self.value().send_messages_and_wait_commit(proto_msgs)
+        self.value()
+            .send_messages_and_wait_commit_with_fee_policy(proto_msgs, fee_policy)
     }
 
-    fn send_messages_and_wait_check_tx(
+    fn send_messages_and_wait_check_tx_with_fee_policy(
         &self,
         proto_msgs: Vec<prost_types::Any>,
-    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
-        self.value().send_messages_and_wait_check_tx(proto_msgs)
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<CheckTxResponses, Error> {
+        self.value()
+            .send_messages_and_wait_check_tx_with_fee_policy(proto_msgs, fee_policy)
+    }
+
+    fn send_transactions_and_wait_check_tx(
+        &self,
+        txs: Vec<Vec<prost_types::Any>>,
+        fee_policy: Option<FeePolicy>,
+    ) -> Result<Vec<Result<CheckTxResponses, Error>>, Error> {
+        self.value()
+            .send_transactions_and_wait_check_tx(txs, fee_policy)
     }
 
     fn get_signer(&self) -> Result<Signer, Error> {
@@ -30,6 +30,7 @@ impl TestOverrides for SupervisorTest {
                 clear_on_start: true,
                 filter: false,
                 tx_confirmation: true,
+                batch_window: None,
             },
         };
     }
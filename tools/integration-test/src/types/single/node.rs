@@ -121,12 +121,14 @@ impl FullNode {
     pub fn generate_chain_config(&self) -> Result<config::ChainConfig, Error> {
         Ok(config::ChainConfig {
             id: self.chain_driver.chain_id.clone(),
+            chain_type: config::default::chain_type(),
             rpc_addr: Url::from_str(&self.chain_driver.rpc_address())?,
             websocket_addr: Url::from_str(&self.chain_driver.websocket_address())?,
             grpc_addr: Url::from_str(&self.chain_driver.grpc_address())?,
             rpc_timeout: Duration::from_secs(10),
             account_prefix: "cosmos".to_string(),
             key_name: self.wallets.relayer.id.0.clone(),
+            extra_key_names: vec![],
 
             // By default we use in-memory key store to avoid polluting
             // ~/.hermes/keys. See
@@ -137,16 +139,28 @@ impl FullNode {
             default_gas: None,
             max_gas: Some(3000000),
             gas_adjustment: Some(0.1),
+            resend_gas_estimate_ttl: None,
             fee_granter: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
             max_block_time: Default::default(),
             clock_drift: Duration::from_secs(5),
             trusting_period: Some(Duration::from_secs(14 * 24 * 3600)),
+            block_time: None,
             trust_threshold: Default::default(),
             gas_price: config::GasPrice::new(0.001, "stake".to_string()),
+            dynamic_gas_price: None,
             packet_filter: Default::default(),
             address_type: Default::default(),
+            remote_signer: None,
+            fee_budget: None,
+            fee_policies: vec![],
+            packet_rate_limits: vec![],
+            transfer_filters: vec![],
+            event_source: Default::default(),
+            extra_endpoints: vec![],
+            failover: Default::default(),
+            backend: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         })